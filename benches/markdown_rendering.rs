@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use runorna::syntax::{clear_highlight_cache, markdown_to_html};
+
+// Builds a ~5,000 line runbook alternating prose and fenced code blocks.
+fn large_note() -> String {
+    let mut note = String::new();
+    for section in 0..300 {
+        note.push_str(&format!("## Step {}\n\nRun the following and check the output.\n\n", section));
+        note.push_str("```rust\n");
+        for line in 0..8 {
+            note.push_str(&format!("let value_{} = compute({}, \"step\");\n", line, section));
+        }
+        note.push_str("```\n\n");
+        note.push_str("- check logs\n- restart service\n\n");
+    }
+    note
+}
+
+fn bench_markdown_rendering(c: &mut Criterion) {
+    let note = large_note();
+    assert!(note.lines().count() >= 5_000);
+
+    c.bench_function("render 5k lines (cold cache)", |b| {
+        b.iter(|| {
+            clear_highlight_cache();
+            markdown_to_html(black_box(&note))
+        })
+    });
+
+    markdown_to_html(&note);
+    c.bench_function("render 5k lines (warm cache)", |b| {
+        b.iter(|| markdown_to_html(black_box(&note)))
+    });
+
+    let mut edited = note.clone();
+    edited.push_str("One more keystroke");
+    c.bench_function("render 5k lines after typing in prose", |b| {
+        b.iter(|| markdown_to_html(black_box(&edited)))
+    });
+}
+
+criterion_group!(benches, bench_markdown_rendering);
+criterion_main!(benches);
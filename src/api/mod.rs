@@ -0,0 +1,5 @@
+mod routes;
+mod server;
+
+pub use routes::*;
+pub use server::*;
@@ -0,0 +1,176 @@
+use crate::data::{
+    add_note_at_path, delete_note, get_folder_name, get_folders, get_note, get_notes, save_folder,
+    save_note, search_notes, update_note, Folder, Note, NoteSearchHit,
+};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// OpenAPI 3 description of the routes below, served at `/api/openapi.json`.
+pub const OPENAPI_JSON: &str = include_str!("openapi.json");
+
+/// Error body of every failed request: `{"error": "..."}`.
+pub struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        let not_found = error
+            .chain()
+            .any(|cause| matches!(cause.downcast_ref(), Some(rusqlite::Error::QueryReturnedNoRows)));
+        let status = if not_found {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        ApiError(status, format!("{:#}", error))
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Every route except the OpenAPI description requires `Authorization: Bearer <token>`.
+pub fn router(token: String) -> Router {
+    let token: Arc<str> = token.into();
+    let protected = Router::new()
+        .route("/api/folders", get(list_folders).post(create_folder))
+        .route("/api/folders/{id}/notes", get(list_folder_notes))
+        .route("/api/notes", post(create_note))
+        .route("/api/notes/{id}", get(read_note).put(replace_note).delete(remove_note))
+        .route("/api/search", get(search))
+        .route_layer(middleware::from_fn_with_state(token, require_token));
+
+    Router::new()
+        .route("/api/openapi.json", get(openapi))
+        .merge(protected)
+}
+
+// Compares every byte so the response time doesn't reveal how much of the token matched.
+fn tokens_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given.iter().zip(expected).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn require_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let given = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match given {
+        Some(given) if tokens_match(given.as_bytes(), token.as_bytes()) => next.run(request).await,
+        _ => ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid API token".to_string())
+            .into_response(),
+    }
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_JSON)
+}
+
+async fn list_folders() -> ApiResult<Json<Vec<Folder>>> {
+    Ok(Json(get_folders().await?))
+}
+
+#[derive(Deserialize)]
+struct NewFolder {
+    name: String,
+    parent_id: Option<i32>,
+}
+
+async fn create_folder(Json(body): Json<NewFolder>) -> ApiResult<(StatusCode, Json<Folder>)> {
+    let name = body.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError(StatusCode::BAD_REQUEST, "Folder name cannot be empty".to_string()));
+    }
+    if let Some(parent_id) = body.parent_id {
+        get_folder_name(parent_id).await?;
+    }
+
+    let date_created = chrono::Local::now().to_rfc3339();
+    let id = save_folder(name.clone(), date_created.clone(), body.parent_id).await?;
+    let folder = Folder {
+        id,
+        name,
+        date_created,
+        parent_id: body.parent_id,
+        children: Vec::new(),
+    };
+    Ok((StatusCode::CREATED, Json(folder)))
+}
+
+async fn list_folder_notes(Path(id): Path<i32>) -> ApiResult<Json<Vec<Note>>> {
+    get_folder_name(id).await?;
+    Ok(Json(get_notes(id).await?))
+}
+
+/// Notes go into `folder_id`, or into `folder_path` (e.g. "Docker/Compose"), created if missing.
+#[derive(Deserialize)]
+struct NewNote {
+    content: String,
+    folder_id: Option<i32>,
+    folder_path: Option<String>,
+}
+
+async fn create_note(Json(body): Json<NewNote>) -> ApiResult<(StatusCode, Json<Note>)> {
+    let note_id = match (body.folder_id, body.folder_path) {
+        (Some(folder_id), None) => {
+            get_folder_name(folder_id).await?;
+            let date_created = chrono::Local::now().to_rfc3339();
+            save_note(body.content, date_created, folder_id).await?
+        }
+        (None, Some(folder_path)) if !folder_path.trim_matches('/').trim().is_empty() => {
+            add_note_at_path(folder_path, body.content).await?.1
+        }
+        _ => {
+            return Err(ApiError(
+                StatusCode::BAD_REQUEST,
+                "Provide exactly one of folder_id or folder_path".to_string(),
+            ))
+        }
+    };
+    Ok((StatusCode::CREATED, Json(get_note(note_id).await?)))
+}
+
+async fn read_note(Path(id): Path<i32>) -> ApiResult<Json<Note>> {
+    Ok(Json(get_note(id).await?))
+}
+
+#[derive(Deserialize)]
+struct NoteContent {
+    content: String,
+}
+
+async fn replace_note(Path(id): Path<i32>, Json(body): Json<NoteContent>) -> ApiResult<Json<Note>> {
+    get_note(id).await?;
+    update_note(id, body.content, chrono::Local::now().to_rfc3339()).await?;
+    Ok(Json(get_note(id).await?))
+}
+
+async fn remove_note(Path(id): Path<i32>) -> ApiResult<StatusCode> {
+    get_note(id).await?;
+    delete_note(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+}
+
+async fn search(Query(params): Query<SearchParams>) -> ApiResult<Json<Vec<NoteSearchHit>>> {
+    if params.q.trim().is_empty() {
+        return Ok(Json(Vec::new()));
+    }
+    Ok(Json(search_notes(params.q).await?))
+}
@@ -0,0 +1,82 @@
+use crate::api::router;
+use crate::data::ApiConfig;
+use anyhow::{Context, Result};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use tokio::sync::oneshot;
+
+static SERVER: Mutex<Option<ApiServer>> = Mutex::new(None);
+
+/// The local API, served from its own thread. Only ever bound to 127.0.0.1.
+/// Dropping it shuts the server down.
+pub struct ApiServer {
+    pub addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApiServer {
+    /// Port 0 picks a free port, see `addr`.
+    pub fn start(port: u16, token: String) -> Result<ApiServer> {
+        // Bound here rather than on the server thread so a taken port is reported to the caller
+        let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("Failed to listen on 127.0.0.1:{}", port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+
+        let thread = std::thread::Builder::new()
+            .name("runorna-api".to_string())
+            .spawn(move || {
+                let serve = async move {
+                    let listener = tokio::net::TcpListener::from_std(listener)?;
+                    axum::serve(listener, router(token))
+                        .with_graceful_shutdown(async {
+                            let _ = shutdown_signal.await;
+                        })
+                        .await
+                };
+                let result = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .and_then(|runtime| runtime.block_on(serve));
+                if let Err(e) = result {
+                    log::error!("Local API server stopped: {}", e);
+                }
+            })
+            .context("Failed to start the API server thread")?;
+
+        Ok(ApiServer {
+            addr,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Starts, restarts or stops the app's API server to match `config`.
+/// Returns the address it listens on when enabled.
+pub fn apply_api_config(config: &ApiConfig) -> Result<Option<SocketAddr>> {
+    let mut server = SERVER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    // The old server must release its port before a new one binds it
+    server.take();
+    if !config.enabled {
+        return Ok(None);
+    }
+    let started = ApiServer::start(config.port, config.token.clone())?;
+    let addr = started.addr;
+    *server = Some(started);
+    Ok(Some(addr))
+}
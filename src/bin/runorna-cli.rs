@@ -0,0 +1,178 @@
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use runorna::data::{
+    add_note_at_path, find_folder_by_path, get_folders, get_note, get_notes, search_notes, Folder,
+    Note, DATABASE_ENV,
+};
+use runorna::syntax::{fenced_code_blocks, note_title, with_title};
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Capture and retrieve Runorna notes from the terminal.
+/// Safe to use while the desktop app is open on the same database.
+#[derive(Parser)]
+#[command(name = "runorna", version)]
+struct Cli {
+    /// Notes database, defaults to $RUNORNA_DB or ./folder.db
+    #[arg(long, global = true)]
+    db: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Save stdin as a new note, creating the folder path if needed
+    Add {
+        /// Folder path such as "Docker/Compose"
+        #[arg(long)]
+        folder: String,
+        /// Title written into the note's front matter
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// List folders and their notes
+    Ls,
+    /// Print a note by id or "Folder/Path/Note title", or list the notes of a folder path
+    Show { target: String },
+    /// Find notes containing the query
+    Search { query: String },
+    /// Print a note's markdown
+    Cat {
+        id: i32,
+        /// Print only the fenced code, ready to pipe into a shell
+        #[arg(long)]
+        code_only: bool,
+    },
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    // Read once when the connection opens, so it must be set before any query
+    if let Some(db) = &cli.db {
+        std::env::set_var(DATABASE_ENV, db);
+    }
+
+    let result = match cli.command {
+        Command::Add { folder, title } => add(folder, title).await,
+        Command::Ls => ls().await,
+        Command::Show { target } => show(target).await,
+        Command::Search { query } => search(query).await,
+        Command::Cat { id, code_only } => cat(id, code_only).await,
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("runorna: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn add(folder: String, title: Option<String>) -> Result<()> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        bail!("Pipe the note into runorna add, e.g. runorna add --folder Notes < note.md");
+    }
+    let mut content = String::new();
+    stdin.read_to_string(&mut content).context("Failed to read stdin")?;
+    if content.trim().is_empty() {
+        bail!("Nothing to add: stdin was empty");
+    }
+    if let Some(title) = title {
+        content = with_title(&content, &title).map_err(anyhow::Error::msg)?;
+    }
+
+    let (_, note_id) = add_note_at_path(folder.clone(), content).await?;
+    println!("Added note {} to {}", note_id, folder.trim_matches('/'));
+    Ok(())
+}
+
+fn flatten<'a>(folders: &'a [Folder], depth: usize, out: &mut Vec<(usize, &'a Folder)>) {
+    for folder in folders {
+        out.push((depth, folder));
+        flatten(&folder.children, depth + 1, out);
+    }
+}
+
+fn print_note_line(note: &Note, indent: usize) {
+    println!("{}#{:<5} {}", "  ".repeat(indent), note.id, note_title(&note.content));
+}
+
+async fn ls() -> Result<()> {
+    let folders = get_folders().await?;
+    let mut flat = Vec::new();
+    flatten(&folders, 0, &mut flat);
+    for (depth, folder) in flat {
+        println!("{}{}/", "  ".repeat(depth), folder.name);
+        for note in get_notes(folder.id).await? {
+            print_note_line(&note, depth + 1);
+        }
+    }
+    Ok(())
+}
+
+async fn show(target: String) -> Result<()> {
+    if let Ok(id) = target.parse::<i32>() {
+        print!("{}", get_note(id).await?.content);
+        return Ok(());
+    }
+
+    if let Some(folder_id) = find_folder_by_path(target.clone()).await? {
+        for note in get_notes(folder_id).await? {
+            print_note_line(&note, 0);
+        }
+        return Ok(());
+    }
+
+    // "Folder/Path/Note title"
+    if let Some((folder_path, title)) = target.trim_matches('/').rsplit_once('/') {
+        if let Some(folder_id) = find_folder_by_path(folder_path.to_string()).await? {
+            let notes = get_notes(folder_id).await?;
+            if let Some(note) = notes.iter().find(|note| note_title(&note.content) == title) {
+                print!("{}", note.content);
+                return Ok(());
+            }
+        }
+    }
+    bail!("No note or folder matches {}", target)
+}
+
+async fn search(query: String) -> Result<()> {
+    let hits = search_notes(query).await?;
+    if hits.is_empty() {
+        eprintln!("No notes found");
+    }
+    for hit in hits {
+        let title = hit.title.unwrap_or_else(|| format!("Note {}", hit.note_id));
+        println!("#{:<5} {} / {}", hit.note_id, hit.folder_name, title);
+        println!("       {}", hit.snippet);
+    }
+    Ok(())
+}
+
+async fn cat(id: i32, code_only: bool) -> Result<()> {
+    let note = get_note(id).await?;
+    if !code_only {
+        print!("{}", note.content);
+        return Ok(());
+    }
+
+    // Output blocks left by runs in the app are results, not code
+    let blocks: Vec<_> = fenced_code_blocks(&note.content)
+        .into_iter()
+        .filter(|block| block.fence.lang != "output")
+        .collect();
+    if blocks.is_empty() {
+        bail!("Note {} has no fenced code", id);
+    }
+    for (index, block) in blocks.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print!("{}", block.code);
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::components::{CommandPaletteComponent, QuickSwitcherComponent, SearchNotesComponent};
+use crate::helpers::{use_command_handler, use_settings, AppCommand, CommandBus, Keybindings};
+use crate::Route;
+use dioxus::document::eval;
+use dioxus::prelude::*;
+
+// Captures bound combos before the focused element sees them, so Ctrl+S never
+// reaches the webview. Elements marked `data-key-capture` are left alone.
+const SHORTCUTS_JS: &str = r#"
+    if (window.__runornaShortcuts) {
+        document.removeEventListener("keydown", window.__runornaShortcuts, true);
+    }
+    window.__runornaShortcuts = (event) => {
+        if (event.target.closest?.("[data-key-capture]")) return;
+        if (["Control", "Alt", "Shift", "Meta", "AltGraph"].includes(event.key)) return;
+        let key = event.key;
+        if (/^Key[A-Z]$/.test(event.code)) key = event.code.slice(3);
+        else if (/^Digit[0-9]$/.test(event.code)) key = event.code.slice(5);
+        else if (key.length === 1) key = key.toUpperCase();
+        let combo = "";
+        if (event.ctrlKey || event.metaKey) combo += "Ctrl+";
+        if (event.altKey) combo += "Alt+";
+        if (event.shiftKey) combo += "Shift+";
+        combo += key;
+        if (window.__runornaBoundKeys?.has(combo)) {
+            event.preventDefault();
+            event.stopPropagation();
+            dioxus.send(combo);
+        }
+    };
+    document.addEventListener("keydown", window.__runornaShortcuts, true);
+    await new Promise(() => {});
+"#;
+
+/// Global keyboard shortcuts plus the overlays they open, mounted once in the layout.
+#[component]
+pub fn CommandLayerComponent() -> Element {
+    let bus = use_context::<CommandBus>();
+    let keybindings = use_context::<Keybindings>();
+    let settings = use_settings();
+    let nav = navigator();
+    let mut show_palette = use_signal(|| false);
+    let mut palette_themes = use_signal(|| false);
+    let mut show_search = use_signal(|| false);
+    let mut show_switcher = use_signal(|| false);
+
+    use_effect(move || {
+        let combos: Vec<String> = keybindings.0.read().values().cloned().collect();
+        let update = eval("window.__runornaBoundKeys = new Set(await dioxus.recv());");
+        let _ = update.send(combos);
+    });
+
+    use_future(move || async move {
+        let mut shortcuts = eval(SHORTCUTS_JS);
+        while let Ok(combo) = shortcuts.recv::<String>().await {
+            let command = keybindings
+                .0
+                .peek()
+                .iter()
+                .find(|(_, bound)| **bound == combo)
+                .map(|(command, _)| command.clone());
+            if let Some(command) = command {
+                bus.dispatch(command);
+            }
+        }
+    });
+
+    use_command_handler(move |command| match command {
+        AppCommand::CommandPalette => {
+            palette_themes.set(false);
+            show_search.set(false);
+            show_switcher.set(false);
+            show_palette.set(!show_palette());
+        }
+        AppCommand::QuickSwitcher => {
+            show_palette.set(false);
+            show_search.set(false);
+            show_switcher.set(!show_switcher());
+        }
+        AppCommand::SwitchSyntaxTheme => {
+            palette_themes.set(true);
+            show_palette.set(true);
+        }
+        AppCommand::SearchNotes => {
+            show_palette.set(false);
+            show_switcher.set(false);
+            show_search.set(true);
+        }
+        AppCommand::ToggleDarkMode => {
+            settings.update(|settings| settings.dark_mode = !settings.dark_mode);
+        }
+        AppCommand::GoHome => {
+            nav.push(Route::Home {});
+        }
+        AppCommand::GoBack => nav.go_back(),
+        AppCommand::GoForward => nav.go_forward(),
+        AppCommand::OpenSettings => {
+            nav.push(Route::SettingsPage {});
+        }
+        AppCommand::OpenFaq => {
+            nav.push(Route::Faq {});
+        }
+        _ => {}
+    });
+
+    rsx! {
+        if show_palette() {
+            CommandPaletteComponent {
+                themes_only: palette_themes(),
+                on_close: move |_| show_palette.set(false),
+            }
+        }
+        if show_search() {
+            SearchNotesComponent { on_close: move |_| show_search.set(false) }
+        }
+        if show_switcher() {
+            QuickSwitcherComponent { on_close: move |_| show_switcher.set(false) }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::helpers::{fuzzy_score, use_settings, use_tr, AppCommand, CommandBus, Keybindings};
+use crate::syntax::theme_names;
+use dioxus::prelude::*;
+use std::cmp::Reverse;
+
+#[derive(Clone, PartialEq)]
+enum PaletteItem {
+    Command(AppCommand),
+    Theme(String),
+}
+
+#[component]
+pub fn CommandPaletteComponent(themes_only: bool, on_close: EventHandler<()>) -> Element {
+    let bus = use_context::<CommandBus>();
+    let keybindings = use_context::<Keybindings>();
+    let settings = use_settings();
+    let tr = use_tr();
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+    let mut themes_mode = use_signal(|| themes_only);
+
+    let items = use_memo(move || {
+        let query = query();
+        let candidates: Vec<(PaletteItem, String)> = if themes_mode() {
+            let mut names = theme_names();
+            names.sort();
+            names.into_iter().map(|name| (PaletteItem::Theme(name.clone()), name)).collect()
+        } else {
+            AppCommand::ALL
+                .into_iter()
+                .filter(AppCommand::is_available)
+                .map(|command| (PaletteItem::Command(command.clone()), command.label(tr)))
+                .collect()
+        };
+
+        let mut ranked: Vec<(i32, PaletteItem, String)> = candidates
+            .into_iter()
+            .filter_map(|(item, label)| Some((fuzzy_score(&query, &label)?, item, label)))
+            .collect();
+        ranked.sort_by_key(|(score, ..)| Reverse(*score));
+        ranked.into_iter().map(|(_, item, label)| (item, label)).collect::<Vec<_>>()
+    });
+
+    let mut run_item = move |item: PaletteItem| match item {
+        PaletteItem::Command(AppCommand::SwitchSyntaxTheme) => {
+            themes_mode.set(true);
+            query.set(String::new());
+            selected.set(0);
+        }
+        PaletteItem::Command(command) => {
+            on_close.call(());
+            bus.dispatch(command);
+        }
+        PaletteItem::Theme(name) => {
+            on_close.call(());
+            settings.update(|settings| settings.set_syntax_theme(name));
+        }
+    };
+
+    let handle_key = move |e: KeyboardEvent| {
+        let count = items.read().len();
+        match e.key() {
+            Key::ArrowDown if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + 1) % count);
+            }
+            Key::ArrowUp if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + count - 1) % count);
+            }
+            Key::Enter => {
+                let item = items.read().get(selected()).map(|(item, _)| item.clone());
+                if let Some(item) = item {
+                    run_item(item);
+                }
+            }
+            Key::Escape => on_close.call(()),
+            _ => {}
+        }
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-start justify-center pt-24 z-50",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-[var(--surface-container-low)] rounded-xl shadow-lg w-[36rem] max-w-[90vw] overflow-hidden border border-[var(--outline-variant)]",
+                onclick: move |e| e.stop_propagation(),
+
+                input {
+                    r#type: "text",
+                    class: "w-full p-4 bg-[var(--surface-container-low)] text-[var(--on-surface)] outline-none border-b border-[var(--outline-variant)]",
+                    placeholder: if themes_mode() { tr.t("palette-pick-theme") } else { tr.t("palette-type-command") },
+                    value: "{query}",
+                    onmounted: move |e| async move {
+                        let _ = e.set_focus(true).await;
+                    },
+                    oninput: move |e| {
+                        query.set(e.value());
+                        selected.set(0);
+                    },
+                    onkeydown: handle_key,
+                }
+
+                div { class: "max-h-80 overflow-y-auto py-2",
+                    if items.read().is_empty() {
+                        div { class: "px-4 py-2 text-sm text-[var(--on-surface-variant)]", {tr.t("no-matches")} }
+                    }
+                    for (index, (item, label)) in items.read().iter().cloned().enumerate() {
+                        button {
+                            key: "{label}",
+                            class: "cursor-pointer w-full flex items-center justify-between px-4 py-2 text-left text-sm text-[var(--on-surface)]",
+                            class: if index == selected() { "bg-[var(--secondary-container)]" } else { "hover:bg-[var(--surface-container-highest)]" },
+                            onclick: {
+                                let item = item.clone();
+                                move |_| run_item(item.clone())
+                            },
+                            span { "{label}" }
+                            if let PaletteItem::Command(command) = &item {
+                                if let Some(combo) = keybindings.0.read().get(command) {
+                                    kbd { class: "text-xs px-2 py-0.5 rounded border border-[var(--outline-variant)] text-[var(--on-surface-variant)]",
+                                        "{combo}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
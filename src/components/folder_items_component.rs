@@ -1,53 +1,194 @@
 use std::collections::HashSet;
 use crate::data::Folder;
+use crate::helpers::use_tr;
+use dioxus::document::eval;
 use dioxus::prelude::*;
 
+// Moves focus between the rendered tree items, which are in visual order since
+// collapsed children aren't rendered. Replies with the newly focused folder id.
+const TREE_FOCUS_JS: &str = r#"
+const [id, direction] = await dioxus.recv();
+const current = document.getElementById(`folder-${id}`);
+const items = [...document.querySelectorAll('[role="tree"] [role="treeitem"]')];
+let target = null;
+if (current) {
+    switch (direction) {
+        case "next": target = items[items.indexOf(current) + 1]; break;
+        case "previous": target = items[items.indexOf(current) - 1]; break;
+        case "first": target = items[0]; break;
+        case "last": target = items[items.length - 1]; break;
+        case "parent": target = current.parentElement.closest('[role="treeitem"]'); break;
+        case "child": target = current.querySelector('[role="treeitem"]'); break;
+        case "self": target = current; break;
+    }
+}
+if (target) {
+    target.focus();
+    dioxus.send(Number(target.dataset.folderId));
+} else {
+    dioxus.send(null);
+}
+"#;
+
+// Roving focus inside an open folder menu.
+const MENU_FOCUS_JS: &str = r#"
+const [id, direction] = await dioxus.recv();
+const items = [...document.querySelectorAll(`#folder-menu-${id} [role="menuitem"]`)];
+const index = items.indexOf(document.activeElement);
+const target = {
+    first: items[0],
+    last: items[items.length - 1],
+    next: items[(index + 1) % items.length],
+    previous: items[(index - 1 + items.length) % items.length],
+}[direction];
+target?.focus();
+"#;
+
+fn move_tree_focus(folder_id: i32, direction: &'static str, mut focused_folder: Signal<Option<i32>>) {
+    spawn(async move {
+        let mut focus = eval(TREE_FOCUS_JS);
+        let _ = focus.send((folder_id, direction));
+        if let Ok(Some(id)) = focus.recv::<Option<i32>>().await {
+            focused_folder.set(Some(id));
+        }
+    });
+}
+
+fn move_menu_focus(folder_id: i32, direction: &'static str) {
+    let focus = eval(MENU_FOCUS_JS);
+    let _ = focus.send((folder_id, direction));
+}
+
 #[component]
 pub fn FolderItem(
     folder: Folder,
     show_dropdown_for_folder: Signal<Option<i32>>,
-    on_delete: EventHandler<i32>,
+    /// Asks to delete the folder, given its id and name.
+    on_delete: EventHandler<(i32, String)>,
     on_update_click: EventHandler<(i32, String)>,
     on_create_subfolder_click: EventHandler<i32>,
     on_select_subfolder: EventHandler<i32>,
+    on_export_click: EventHandler<i32>,
     expanded_folders: Signal<HashSet<i32>>,
+    selected_folder: Signal<Option<i32>>,
+    /// The tree's single tab stop, moved by the arrow keys.
+    focused_folder: Signal<Option<i32>>,
+    /// Nesting level, 0 for root folders.
+    #[props(default)]
+    depth: usize,
 ) -> Element {
+    let tr = use_tr();
     let folder_id = folder.id;
     let is_dropdown_open = show_dropdown_for_folder
         .read()
-        .map_or(false, |id| id == folder_id);
+        .is_some_and(|id| id == folder_id);
     let is_parent_folder = folder.parent_id.is_none();
     let is_expanded = expanded_folders.read().contains(&folder_id);
     let has_children = !folder.children.is_empty();
+    let is_selected = selected_folder() == Some(folder_id);
+    let is_tab_stop = focused_folder() == Some(folder_id);
 
-    let toggle_expanded = move |evt: Event<MouseData>| {
-        evt.stop_propagation();
+    let mut set_expanded = move |expand: bool| {
         let mut expanded = expanded_folders.write();
-        if expanded.contains(&folder_id) {
-            expanded.remove(&folder_id);
-        } else {
+        if expand {
             expanded.insert(folder_id);
+        } else {
+            expanded.remove(&folder_id);
         }
     };
 
-    let depth = if is_parent_folder { 0 } else { 1 };
+    let toggle_expanded = move |evt: Event<MouseData>| {
+        evt.stop_propagation();
+        set_expanded(!is_expanded);
+    };
+
+    let mut open_menu = move || {
+        show_dropdown_for_folder.set(Some(folder_id));
+    };
+
+    let mut close_menu = move || {
+        show_dropdown_for_folder.set(None);
+        move_tree_focus(folder_id, "self", focused_folder);
+    };
+
+    let key_name = folder.name.clone();
+    let delete_name = folder.name.clone();
+    let actions_label = tr.t_args("folder-actions", &[("name", &folder.name)]);
+    let handle_tree_key = move |evt: KeyboardEvent| {
+        // Nested items handle their own keys
+        evt.stop_propagation();
+        match evt.key() {
+            Key::ArrowDown => move_tree_focus(folder_id, "next", focused_folder),
+            Key::ArrowUp => move_tree_focus(folder_id, "previous", focused_folder),
+            Key::Home => move_tree_focus(folder_id, "first", focused_folder),
+            Key::End => move_tree_focus(folder_id, "last", focused_folder),
+            Key::ArrowRight if has_children => {
+                if is_expanded {
+                    move_tree_focus(folder_id, "child", focused_folder);
+                } else {
+                    set_expanded(true);
+                }
+            }
+            Key::ArrowLeft => {
+                if has_children && is_expanded {
+                    set_expanded(false);
+                } else {
+                    move_tree_focus(folder_id, "parent", focused_folder);
+                }
+            }
+            Key::Enter => {
+                if depth > 0 {
+                    on_select_subfolder.call(folder_id);
+                } else if has_children {
+                    set_expanded(!is_expanded);
+                }
+            }
+            Key::F2 => on_update_click.call((folder_id, key_name.clone())),
+            Key::Delete => on_delete.call((folder_id, key_name.clone())),
+            Key::ContextMenu => open_menu(),
+            Key::F10 if evt.modifiers().shift() => open_menu(),
+            _ => return,
+        }
+        evt.prevent_default();
+    };
+
+    let handle_menu_key = move |evt: KeyboardEvent| {
+        evt.stop_propagation();
+        match evt.key() {
+            Key::ArrowDown => move_menu_focus(folder_id, "next"),
+            Key::ArrowUp => move_menu_focus(folder_id, "previous"),
+            Key::Home => move_menu_focus(folder_id, "first"),
+            Key::End => move_menu_focus(folder_id, "last"),
+            Key::Escape | Key::Tab => close_menu(),
+            _ => return,
+        }
+        evt.prevent_default();
+    };
 
     rsx! {
         div {
-            class: "flex flex-col px-1",
+            id: "folder-{folder_id}",
+            class: "flex flex-col px-1 outline-none focus-visible:[&>div:first-child]:ring-2 focus-visible:[&>div:first-child]:ring-[var(--primary)]",
             style: if depth == 0 { "border-bottom: 1px solid var(--primary);" } else { "" },
+            role: "treeitem",
+            aria_level: "{depth + 1}",
+            aria_selected: "{is_selected}",
+            aria_expanded: if has_children { Some(is_expanded.to_string()) } else { None },
+            "data-folder-id": "{folder_id}",
+            tabindex: if is_tab_stop { "0" } else { "-1" },
+            onkeydown: handle_tree_key,
 
             div {
                 class: "group flex items-center py-2 px-3 rounded-lg justify-between relative transition-colors duration-150",
                 style: match depth {
                     0 => "background: var(--surface-container-low); margin-bottom: 4px;",
-                    1 => "background: var(--surface-container-highest); margin: 7px 0;",
-                    _ => "",
+                    _ => "background: var(--surface-container-highest); margin: 7px 0;",
                 },
                 // Main content container
                 div {
                     class: "flex items-center min-w-0 flex-1",
                     onclick: move |_| {
+                        focused_folder.set(Some(folder_id));
                         if depth > 0 {
                             on_select_subfolder.call(folder_id);
                         }
@@ -58,6 +199,9 @@ pub fn FolderItem(
                         button {
                             class: "cursor-pointer text-[var(--on-surface-variant)] hover:text-[var(--on-surface)] mr-2 transition-all duration-150 flex-shrink-0",
                             style: if !is_expanded { "transform: rotate(0deg);" } else { "transform: rotate(90deg);" },
+                            // The tree item itself expands with the arrow keys
+                            tabindex: "-1",
+                            aria_hidden: "true",
                             onclick: toggle_expanded,
                             svg {
                                 xmlns: "http://www.w3.org/2000/svg",
@@ -81,9 +225,9 @@ pub fn FolderItem(
                             class: if depth > 0 { "text-[var(--on-surface)] truncate hover:text-[var(--primary)] cursor-pointer block w-full" } else { "text-[var(--on-surface)] truncate block w-full" },
                             style: match depth {
                                 0 => "font-weight: 500; font-size: 0.95rem;",
-                                1 => "font-weight: 400; font-size: 0.9rem;",
-                                _ => "",
+                                _ => "font-weight: 400; font-size: 0.9rem;",
                             },
+                            title: tr.t_args("folder-created", &[("date", &tr.date(&folder.date_created))]),
                             "{folder.name}"
                         }
                     }
@@ -93,6 +237,10 @@ pub fn FolderItem(
                 div { class: "relative flex-shrink-0 ml-2 w-6",
                     button {
                         class: "cursor-pointer text-[var(--on-surface-variant)] hover:text-[var(--on-surface)] p-1 rounded-full hover:bg-[var(--surface-container-highest)] transition-colors duration-150",
+                        tabindex: "-1",
+                        aria_label: actions_label.clone(),
+                        aria_haspopup: "menu",
+                        aria_expanded: "{is_dropdown_open}",
                         onclick: move |evt| {
                             evt.stop_propagation();
                             show_dropdown_for_folder
@@ -114,42 +262,66 @@ pub fn FolderItem(
                     // Dropdown menu
                     if is_dropdown_open {
                         div {
+                            id: "folder-menu-{folder_id}",
                             class: "absolute right-0 mt-2 w-48 bg-[var(--surface-container-high)] rounded-md shadow-lg z-10 border border-[var(--outline-variant)] animate-fade-in",
+                            role: "menu",
+                            aria_label: actions_label.clone(),
                             onclick: move |evt| evt.stop_propagation(),
+                            onkeydown: handle_menu_key,
+                            onmounted: move |_| move_menu_focus(folder_id, "first"),
 
                             if is_parent_folder {
                                 button {
+                                    role: "menuitem",
+                                    tabindex: "-1",
                                     class: "cursor-pointer block w-full text-left px-4 py-2 text-sm text-[var(--on-surface)] hover:bg-[var(--surface-container-highest)] transition-colors duration-100",
                                     onclick: move |_| {
                                         on_create_subfolder_click.call(folder_id);
                                         show_dropdown_for_folder.set(None);
                                     },
-                                    "Create Subfolder"
+                                    {tr.t("folder-create-subfolder")}
                                 }
                             }
                             button {
+                                role: "menuitem",
+                                tabindex: "-1",
                                 class: "cursor-pointer block w-full text-left px-4 py-2 text-sm text-[var(--on-surface)] hover:bg-[var(--surface-container-highest)] transition-colors duration-100",
                                 onclick: move |_| {
                                     on_update_click.call((folder_id, folder.name.clone()));
                                     show_dropdown_for_folder.set(None);
                                 },
                                 if is_parent_folder {
-                                    "Rename Folder"
+                                    {tr.t("folder-rename")}
                                 } else {
-                                    "Rename Subfolder"
+                                    {tr.t("folder-rename-subfolder")}
+                                }
+                            }
+
+                            if cfg!(not(feature = "web")) {
+                                button {
+                                    role: "menuitem",
+                                    tabindex: "-1",
+                                    class: "cursor-pointer block w-full text-left px-4 py-2 text-sm text-[var(--on-surface)] hover:bg-[var(--surface-container-highest)] transition-colors duration-100",
+                                    onclick: move |_| {
+                                        on_export_click.call(folder_id);
+                                        show_dropdown_for_folder.set(None);
+                                    },
+                                    {tr.t("folder-export")}
                                 }
                             }
 
                             button {
+                                role: "menuitem",
+                                tabindex: "-1",
                                 class: "cursor-pointer block w-full text-left px-4 py-2 text-sm text-[var(--error)] hover:bg-[var(--error-container)] transition-colors duration-100",
                                 onclick: move |_| {
-                                    on_delete.call(folder_id);
+                                    on_delete.call((folder_id, delete_name.clone()));
                                     show_dropdown_for_folder.set(None);
                                 },
                                 if is_parent_folder {
-                                    "Delete Folder"
+                                    {tr.t("folder-delete")}
                                 } else {
-                                    "Delete Subfolder"
+                                    {tr.t("folder-delete-subfolder")}
                                 }
                             }
                         }
@@ -160,6 +332,7 @@ pub fn FolderItem(
             // Render subfolders recursively if expanded and has children
             if has_children && is_expanded {
                 div {
+                    role: "group",
                     class: "ml-6 pl-2 border-l-2 border-[var(--outline-variant)]",
                     style: "border-left-color: var(--primary-container); margin-bottom: 10px;",
                     for subfolder in folder.children.iter() {
@@ -169,8 +342,12 @@ pub fn FolderItem(
                             on_delete,
                             on_update_click,
                             on_create_subfolder_click,
-                            on_select_subfolder: on_select_subfolder.clone(),
+                            on_select_subfolder,
+                            on_export_click,
                             expanded_folders,
+                            selected_folder,
+                            focused_folder,
+                            depth: depth + 1,
                         }
                     }
                 }
@@ -0,0 +1,126 @@
+use crate::data::{load_keybindings, reset_keybinding, save_keybinding};
+use crate::helpers::{effective_bindings, key_combo, use_tr, AppCommand, Keybindings};
+use dioxus::prelude::*;
+
+/// Settings section listing every command with its binding, remappable by pressing a new combo.
+#[component]
+pub fn KeyboardShortcutsComponent() -> Element {
+    let tr = use_tr();
+    let mut keybindings = use_context::<Keybindings>();
+    let mut capturing = use_signal(|| None::<AppCommand>);
+    let mut conflict = use_signal(|| None::<String>);
+
+    let mut update_binding = move |command: AppCommand, combo: Option<String>| {
+        capturing.set(None);
+        spawn(async move {
+            let saved = match combo {
+                Some(combo) => save_keybinding(command.id().to_string(), combo).await,
+                None => reset_keybinding(command.id().to_string()).await,
+            };
+            if let Err(e) = saved {
+                log::error!("Failed to save keybinding: {}", e);
+            }
+            match load_keybindings().await {
+                Ok(overrides) => keybindings.0.set(effective_bindings(&overrides)),
+                Err(e) => log::error!("Failed to load keybindings: {}", e),
+            }
+        });
+    };
+
+    let handle_capture = move |e: KeyboardEvent| {
+        let Some(command) = capturing() else {
+            return;
+        };
+        e.prevent_default();
+        e.stop_propagation();
+        if e.key() == Key::Escape {
+            capturing.set(None);
+            return;
+        }
+        let modifiers = e.modifiers();
+        let Some(combo) = key_combo(
+            modifiers.ctrl() || modifiers.meta(),
+            modifiers.alt(),
+            modifiers.shift(),
+            &e.code().to_string(),
+            &e.key().to_string(),
+        ) else {
+            return;
+        };
+
+        let taken_by = keybindings
+            .0
+            .read()
+            .iter()
+            .find(|(other, bound)| **other != command && **bound == combo)
+            .map(|(other, _)| other.label(tr));
+        conflict.set(taken_by.as_ref().map(|label| {
+            tr.t_args("shortcuts-conflict", &[("combo", &combo), ("command", label)])
+        }));
+        if taken_by.is_none() {
+            update_binding(command, Some(combo));
+        }
+    };
+
+    rsx! {
+        div { class: "border-t border-[var(--outline-variant)] pt-6",
+            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                {tr.t("shortcuts-title")}
+            }
+            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                {tr.t("shortcuts-description")}
+            }
+            if let Some(message) = conflict() {
+                div { class: "mb-4 p-2 rounded-lg bg-[var(--error)] text-[var(--on-error)] text-sm", "{message}" }
+            }
+            div { class: "divide-y divide-[var(--outline-variant)]",
+                for command in AppCommand::ALL.into_iter().filter(AppCommand::is_available) {
+                    div {
+                        key: "{command.id()}",
+                        class: "flex items-center justify-between py-2",
+                        span { class: "text-[var(--on-surface)]", "{command.label(tr)}" }
+                        div { class: "flex items-center gap-2",
+                            button {
+                                "data-key-capture": "true",
+                                class: "min-w-32 px-3 py-1 rounded border text-sm font-mono",
+                                class: if capturing() == Some(command.clone()) { "border-[var(--primary)] text-[var(--primary)]" } else { "border-[var(--outline-variant)] text-[var(--on-surface-variant)]" },
+                                onclick: {
+                                    let command = command.clone();
+                                    move |_| {
+                                        conflict.set(None);
+                                        capturing.set(Some(command.clone()));
+                                    }
+                                },
+                                onkeydown: handle_capture,
+                                onblur: move |_| capturing.set(None),
+                                if capturing() == Some(command.clone()) {
+                                    {tr.t("shortcuts-press-keys")}
+                                } else {
+                                    {keybindings.0.read().get(&command).cloned().unwrap_or_else(|| tr.t("shortcuts-unassigned"))}
+                                }
+                            }
+                            button {
+                                class: "px-2 py-1 text-xs rounded text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-highest)]",
+                                title: tr.t("shortcuts-clear-title"),
+                                onclick: {
+                                    let command = command.clone();
+                                    move |_| update_binding(command.clone(), Some(String::new()))
+                                },
+                                {tr.t("shortcuts-clear")}
+                            }
+                            button {
+                                class: "px-2 py-1 text-xs rounded text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-highest)]",
+                                title: tr.t("shortcuts-reset-title"),
+                                onclick: {
+                                    let command = command.clone();
+                                    move |_| update_binding(command.clone(), None)
+                                },
+                                {tr.t("shortcuts-reset")}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
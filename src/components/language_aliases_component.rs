@@ -0,0 +1,112 @@
+use crate::data::{delete_language_alias, load_language_aliases, save_language_alias};
+use crate::syntax::{is_known_language, set_custom_language_aliases, DEFAULT_LANGUAGE_ALIASES};
+use crate::helpers::use_tr;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+
+/// Settings section mapping extra fence tags onto the languages the highlighter knows.
+#[component]
+pub fn LanguageAliasesComponent() -> Element {
+    let tr = use_tr();
+    let mut custom = use_signal(BTreeMap::<String, String>::new);
+    let mut alias_input = use_signal(String::new);
+    let mut lang_input = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let reload = move || {
+        spawn(async move {
+            match load_language_aliases().await {
+                Ok(aliases) => {
+                    set_custom_language_aliases(&aliases);
+                    custom.set(aliases);
+                }
+                Err(e) => error.set(Some(format!("{:#}", e))),
+            }
+        });
+    };
+
+    use_effect(reload);
+
+    let add_alias = move |_| {
+        let alias = alias_input().trim().to_lowercase();
+        let lang = lang_input().trim().to_string();
+        if alias.is_empty() || alias.contains(char::is_whitespace) {
+            error.set(Some(tr.t("aliases-invalid-tag")));
+            return;
+        }
+        if !is_known_language(&lang) {
+            error.set(Some(tr.t_args("aliases-unknown-language", &[("lang", &lang)])));
+            return;
+        }
+        error.set(None);
+        alias_input.set(String::new());
+        lang_input.set(String::new());
+        spawn(async move {
+            if let Err(e) = save_language_alias(alias, lang).await {
+                error.set(Some(format!("{:#}", e)));
+            }
+            reload();
+        });
+    };
+
+    rsx! {
+        div { class: "border-t border-[var(--outline-variant)] pt-6",
+            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                {tr.t("aliases-title")}
+            }
+            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                {tr.t("aliases-description")}
+            }
+            div { class: "flex items-center gap-2 mb-4",
+                input {
+                    r#type: "text",
+                    class: "w-32 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                    placeholder: tr.t("aliases-tag"),
+                    value: "{alias_input}",
+                    oninput: move |e| alias_input.set(e.value()),
+                }
+                span { class: "text-[var(--on-surface-variant)]", "→" }
+                input {
+                    r#type: "text",
+                    class: "w-40 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                    placeholder: tr.t("aliases-language"),
+                    value: "{lang_input}",
+                    oninput: move |e| lang_input.set(e.value()),
+                }
+                button {
+                    class: "px-4 py-2 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--tertiary)] hover:text-[var(--on-tertiary)] text-sm",
+                    onclick: add_alias,
+                    {tr.t("aliases-add")}
+                }
+            }
+            if let Some(err) = error() {
+                div { class: "mb-4 p-2 rounded-lg bg-[var(--error)] text-[var(--on-error)] text-sm", "{err}" }
+            }
+            div { class: "grid grid-cols-[1fr_1fr_auto] gap-x-4 gap-y-1 text-sm",
+                for (alias, lang) in custom() {
+                    span { key: "{alias}-alias", class: "font-mono text-[var(--on-surface)]", "{alias}" }
+                    span { key: "{alias}-lang", class: "font-mono text-[var(--on-surface)]", "{lang}" }
+                    button {
+                        key: "{alias}-remove",
+                        class: "px-2 text-xs rounded text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-highest)]",
+                        onclick: move |_| {
+                            let alias = alias.clone();
+                            spawn(async move {
+                                if let Err(e) = delete_language_alias(alias).await {
+                                    error.set(Some(format!("{:#}", e)));
+                                }
+                                reload();
+                            });
+                        },
+                        {tr.t("aliases-remove")}
+                    }
+                }
+                for (alias, lang) in DEFAULT_LANGUAGE_ALIASES.iter().filter(|(alias, _)| !custom.read().contains_key(*alias)) {
+                    span { key: "default-{alias}-alias", class: "font-mono text-[var(--on-surface-variant)]", "{alias}" }
+                    span { key: "default-{alias}-lang", class: "font-mono text-[var(--on-surface-variant)]", "{lang}" }
+                    span { key: "default-{alias}-note", class: "px-2 text-xs text-[var(--on-surface-variant)]", {tr.t("aliases-built-in")} }
+                }
+            }
+        }
+    }
+}
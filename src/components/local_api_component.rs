@@ -0,0 +1,136 @@
+#[cfg(not(feature = "web"))]
+use crate::api::apply_api_config;
+#[cfg(not(feature = "web"))]
+use crate::data::{load_api_config, new_api_token, save_api_config, ApiConfig};
+#[cfg(not(feature = "web"))]
+use crate::helpers::use_tr;
+use dioxus::prelude::*;
+
+/// Settings section for the opt-in localhost HTTP API used by editor plugins and scripts.
+#[cfg(not(feature = "web"))]
+#[component]
+pub fn LocalApiComponent() -> Element {
+    let tr = use_tr();
+    let mut config = use_signal(|| None::<ApiConfig>);
+    let mut port_input = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    use_effect(move || {
+        spawn(async move {
+            match load_api_config().await {
+                Ok(loaded) => {
+                    port_input.set(loaded.port.to_string());
+                    if loaded.enabled {
+                        status.set(Some(Ok(tr.t_args("api-listening", &[("address", &format!("127.0.0.1:{}", loaded.port))]))));
+                    }
+                    config.set(Some(loaded));
+                }
+                Err(e) => status.set(Some(Err(format!("{:#}", e)))),
+            }
+        });
+    });
+
+    let mut apply = move |updated: ApiConfig| {
+        config.set(Some(updated.clone()));
+        spawn(async move {
+            if let Err(e) = save_api_config(updated.clone()).await {
+                status.set(Some(Err(format!("{:#}", e))));
+                return;
+            }
+            status.set(match apply_api_config(&updated) {
+                Ok(Some(addr)) => Some(Ok(tr.t_args("api-listening", &[("address", &addr)]))),
+                Ok(None) => None,
+                Err(e) => Some(Err(format!("{:#}", e))),
+            });
+        });
+    };
+
+    let Some(current) = config() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { class: "border-t border-[var(--outline-variant)] pt-6",
+            div { class: "flex items-center justify-between mb-4",
+                h2 { class: "text-xl font-semibold text-[var(--on-surface)]", {tr.t("api-title")} }
+                button {
+                    class: "relative inline-flex h-6 w-11 items-center rounded-full transition-colors focus:outline-none focus:ring-2 focus:ring-[var(--primary)] focus:ring-offset-2",
+                    class: if current.enabled { "bg-[var(--primary)]" } else { "bg-[var(--outline-variant)]" },
+                    onclick: {
+                        let current = current.clone();
+                        move |_| apply(ApiConfig { enabled: !current.enabled, ..current.clone() })
+                    },
+                    span { class: "sr-only", {tr.t("api-toggle")} }
+                    span {
+                        class: "inline-block h-4 w-4 transform rounded-full bg-white transition-transform",
+                        class: if current.enabled { "translate-x-6" } else { "translate-x-1" },
+                    }
+                }
+            }
+            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                {tr.t("api-description")}
+                " "
+                code { "Authorization: Bearer <token>" }
+                " "
+                {tr.t("api-openapi")}
+            }
+            div { class: "grid grid-cols-[8rem_1fr] gap-3 items-center text-sm",
+                label { class: "text-[var(--on-surface)]", r#for: "api-port", {tr.t("api-port")} }
+                input {
+                    id: "api-port",
+                    r#type: "number",
+                    min: "1024",
+                    max: "65535",
+                    class: "w-32 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                    value: "{port_input}",
+                    oninput: move |e| port_input.set(e.value()),
+                    onchange: {
+                        let current = current.clone();
+                        move |_| match port_input().trim().parse::<u16>() {
+                            Ok(port) if port >= 1024 => apply(ApiConfig { port, ..current.clone() }),
+                            _ => status.set(Some(Err(tr.t("api-port-invalid")))),
+                        }
+                    },
+                }
+                label { class: "text-[var(--on-surface)]", r#for: "api-token", {tr.t("api-token")} }
+                div { class: "flex items-center gap-2",
+                    input {
+                        id: "api-token",
+                        r#type: "text",
+                        readonly: true,
+                        class: "flex-1 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] font-mono text-xs",
+                        value: "{current.token}",
+                        onfocus: move |_| {
+                            document::eval(r#"document.getElementById("api-token").select();"#);
+                        },
+                    }
+                    button {
+                        class: "px-3 py-2 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--tertiary)] hover:text-[var(--on-tertiary)]",
+                        title: tr.t("api-regenerate-title"),
+                        onclick: {
+                            let current = current.clone();
+                            move |_| apply(ApiConfig { token: new_api_token(), ..current.clone() })
+                        },
+                        {tr.t("api-regenerate")}
+                    }
+                }
+            }
+            match status() {
+                Some(Ok(msg)) => rsx! {
+                    div { class: "mt-4 text-sm text-[var(--primary)]", "{msg}" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "mt-4 p-2 rounded-lg bg-[var(--error)] text-[var(--on-error)] text-sm", "{err}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+/// A browser page can't listen on a port, so the web build has no local API.
+#[cfg(feature = "web")]
+#[component]
+pub fn LocalApiComponent() -> Element {
+    rsx! {}
+}
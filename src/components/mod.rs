@@ -1,9 +1,27 @@
 mod navbar_component;
-mod button_component;
 mod folder_items_component;
 mod accordion_component;
+mod theme_thumbnail_component;
+mod outline_component;
+mod command_layer_component;
+mod command_palette_component;
+mod search_notes_component;
+mod keyboard_shortcuts_component;
+mod quick_switcher_component;
+mod local_api_component;
+mod language_aliases_component;
+mod palettes_component;
 
 pub use navbar_component::*;
-pub use button_component::*;
 pub use folder_items_component::*;
-pub use accordion_component::*;
\ No newline at end of file
+pub use accordion_component::*;
+pub use theme_thumbnail_component::*;
+pub use outline_component::*;
+pub use command_layer_component::*;
+pub use command_palette_component::*;
+pub use search_notes_component::*;
+pub use keyboard_shortcuts_component::*;
+pub use quick_switcher_component::*;
+pub use local_api_component::*;
+pub use language_aliases_component::*;
+pub use palettes_component::*;
\ No newline at end of file
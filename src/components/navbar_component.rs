@@ -1,18 +1,23 @@
 use dioxus::prelude::*;
+use crate::components::CommandLayerComponent;
+use crate::helpers::use_tr;
 use crate::Route;
 
 #[component]
 pub fn NavbarComponent() -> Element {
+    let tr = use_tr();
     let nav_items: Vec<(String, Route, &str)> = vec![
-        ("Home".to_string(), Route::Home {}, "M3 12l2-2m0 0l7-7 7 7M5 10v10a1 1 0 001 1h3m10-11l2 2m-2-2v10a1 1 0 01-1 1h-3m-6 0a1 1 0 001-1v-4a1 1 0 011-1h2a1 1 0 011 1v4a1 1 0 001 1m-6 0h6"),
-        ("Settings".to_string(), Route::SettingsPage {}, "M10.325 4.317c.426-1.756 2.924-1.756 3.35 0a1.724 1.724 0 002.573 1.066c1.543-.94 3.31.826 2.37 2.37a1.724 1.724 0 001.065 2.572c1.756.426 1.756 2.924 0 3.35a1.724 1.724 0 00-1.066 2.573c.94 1.543-.826 3.31-2.37 2.37a1.724 1.724 0 00-2.572 1.065c-.426 1.756-2.924 1.756-3.35 0a1.724 1.724 0 00-2.573-1.066c-1.543.94-3.31-.826-2.37-2.37a1.724 1.724 0 00-1.065-2.572c-1.756-.426-1.756-2.924 0-3.35a1.724 1.724 0 001.066-2.573c-.94-1.543.826-3.31 2.37-2.37.996.608 2.296.07 2.572-1.065z"),
-        ("FAQ".to_string(), Route::Faq {}, "M8.25 9a3.75 3.75 0 117.5 0c0 1.586-.876 2.372-1.711 2.947-.653.448-1.289.883-1.289 1.803a.75.75 0 01-1.5 0c0-1.586.876-2.372 1.711-2.947.653-.448 1.289-.883 1.289-1.803a2.25 2.25 0 10-4.5 0 .75.75 0 01-1.5 0ZM12 17.25a.75.75 0 100 1.5.75.75 0 000-1.5Z")
+        (tr.t("nav-home"), Route::Home {}, "M3 12l2-2m0 0l7-7 7 7M5 10v10a1 1 0 001 1h3m10-11l2 2m-2-2v10a1 1 0 01-1 1h-3m-6 0a1 1 0 001-1v-4a1 1 0 011-1h2a1 1 0 011 1v4a1 1 0 001 1m-6 0h6"),
+        (tr.t("nav-settings"), Route::SettingsPage {}, "M10.325 4.317c.426-1.756 2.924-1.756 3.35 0a1.724 1.724 0 002.573 1.066c1.543-.94 3.31.826 2.37 2.37a1.724 1.724 0 001.065 2.572c1.756.426 1.756 2.924 0 3.35a1.724 1.724 0 00-1.066 2.573c.94 1.543-.826 3.31-2.37 2.37a1.724 1.724 0 00-2.572 1.065c-.426 1.756-2.924 1.756-3.35 0a1.724 1.724 0 00-2.573-1.066c-1.543.94-3.31-.826-2.37-2.37a1.724 1.724 0 00-1.065-2.572c-1.756-.426-1.756-2.924 0-3.35a1.724 1.724 0 001.066-2.573c-.94-1.543.826-3.31 2.37-2.37.996.608 2.296.07 2.572-1.065z"),
+        (tr.t("nav-faq"), Route::Faq {}, "M8.25 9a3.75 3.75 0 117.5 0c0 1.586-.876 2.372-1.711 2.947-.653.448-1.289.883-1.289 1.803a.75.75 0 01-1.5 0c0-1.586.876-2.372 1.711-2.947.653-.448 1.289-.883 1.289-1.803a2.25 2.25 0 10-4.5 0 .75.75 0 01-1.5 0ZM12 17.25a.75.75 0 100 1.5.75.75 0 000-1.5Z")
     ];
 
     let mut mobile_menu_open = use_signal(|| false);
 
     rsx! {
         div {
+            CommandLayerComponent {}
+
             // Mobile menu button (hamburger)
             button {
                 class: "
@@ -22,8 +27,12 @@ pub fn NavbarComponent() -> Element {
                     transition-all duration-200 hover:shadow-xl
                     focus:outline-none focus:ring-2 focus:ring-[var(--primary)]/50
                 ",
+                aria_label: tr.t("nav-menu"),
+                aria_expanded: "{mobile_menu_open}",
+                aria_controls: "main-navigation",
                 onclick: move |_| mobile_menu_open.set(!mobile_menu_open()),
                 svg {
+                    "aria-hidden": "true",
                     class: "w-6 h-6",
                     fill: "none",
                     view_box: "0 0 24 24",
@@ -49,6 +58,8 @@ pub fn NavbarComponent() -> Element {
 
             // Slim sidebar nav
             nav {
+                id: "main-navigation",
+                aria_label: tr.t("nav-label"),
                 class: "
                     fixed top-0 left-0 z-40 w-16 h-screen
                     bg-[var(--surface-container-high)] backdrop-blur-lg
@@ -71,12 +82,15 @@ pub fn NavbarComponent() -> Element {
                         Link {
                             to: link.clone(),
                             class: "w-full h-full flex items-center justify-center",
-                            // Tooltip container
-                            div { class: "
+                            aria_label: "{item}",
+                            // Tooltip container, the link's aria-label already names it
+                            div {
+                                aria_hidden: "true",
+                                class: "
                                     absolute left-full ml-2 px-3 py-2
                                     bg-[var(--surface-container-high)] rounded-lg shadow-lg
                                     text-sm font-medium text-[var(--on-surface)]
-                                    opacity-0 group-hover:opacity-100
+                                    opacity-0 group-hover:opacity-100 group-focus-within:opacity-100
                                     pointer-events-none
                                     transition-opacity duration-200
                                     whitespace-nowrap
@@ -85,6 +99,7 @@ pub fn NavbarComponent() -> Element {
                             }
                             // Icon
                             svg {
+                                "aria-hidden": "true",
                                 class: "
                                     w-6 h-6 text-[var(--on-surface-variant)]
                                     group-hover:text-[var(--on-surface)]
@@ -0,0 +1,30 @@
+use crate::syntax::HeadingEntry;
+use crate::helpers::use_tr;
+use dioxus::prelude::*;
+
+#[component]
+pub fn OutlineComponent(outline: Vec<HeadingEntry>, on_select: EventHandler<HeadingEntry>) -> Element {
+    let tr = use_tr();
+    let min_level = outline.iter().map(|h| h.level).min().unwrap_or(1);
+
+    rsx! {
+        nav { class: "flex flex-col h-full",
+            h2 { class: "text-lg font-medium text-[var(--on-surface-variant)] mb-2", {tr.t("editor-outline")} }
+            div { class: "flex-1 overflow-y-auto border border-[var(--outline-variant)] rounded-xl bg-[var(--surface-container-high)] py-2",
+                for heading in outline.into_iter() {
+                    button {
+                        key: "{heading.id}",
+                        class: "cursor-pointer block w-full text-left pr-3 py-1 text-sm truncate text-[var(--on-surface)] hover:bg-[var(--surface-container-highest)] hover:text-[var(--primary)]",
+                        style: "padding-left: {0.75 + (heading.level - min_level) as f32 * 0.9}rem;",
+                        title: "{heading.title}",
+                        onclick: {
+                            let heading = heading.clone();
+                            move |_| on_select.call(heading.clone())
+                        },
+                        "{heading.title}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::data::{
+    builtin_colors, contrast_pairs, contrast_ratio, delete_palette, get_palette, get_palettes,
+    parse_shared_palette, save_palette, update_palette, Palette, SharedPalette, MIN_CONTRAST,
+    PALETTE_VARIABLES,
+};
+use crate::helpers::{use_settings, use_tr, ActivePalette};
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, PartialEq)]
+struct PaletteDraft {
+    // None until the palette is saved for the first time
+    id: Option<i32>,
+    name: String,
+    colors: BTreeMap<String, String>,
+}
+
+/// Settings section for creating, editing, sharing and applying color palettes.
+#[component]
+pub fn PalettesComponent() -> Element {
+    let settings = use_settings();
+    let tr = use_tr();
+    let mut active_palette = use_context::<ActivePalette>();
+    let mut palettes = use_signal(Vec::<Palette>::new);
+    let mut draft = use_signal(|| None::<PaletteDraft>);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+
+    let reload = move || {
+        spawn(async move {
+            match get_palettes().await {
+                Ok(loaded) => palettes.set(loaded),
+                Err(e) => message.set(Some(Err(format!("{:#}", e)))),
+            }
+        });
+    };
+
+    use_effect(reload);
+
+    // Puts the stored palette back after a live preview was discarded
+    let restore_active = move || {
+        let Some(id) = settings.0.peek().palette_id else {
+            return;
+        };
+        spawn(async move {
+            if let Ok(palette) = get_palette(id).await {
+                active_palette.0.set(Some(palette));
+            }
+        });
+    };
+
+    let mut set_color = move |name: String, value: String| {
+        let Some(mut updated) = draft() else {
+            return;
+        };
+        updated.colors.insert(name, value);
+        // Editing the applied palette previews it across the whole app
+        if updated.id.is_some() && updated.id == settings.0.peek().palette_id {
+            if let Some(id) = updated.id {
+                active_palette.0.set(Some(Palette {
+                    id,
+                    name: updated.name.clone(),
+                    colors: updated.colors.clone(),
+                }));
+            }
+        }
+        draft.set(Some(updated));
+    };
+
+    let save_draft = move |_| {
+        let Some(current) = draft() else {
+            return;
+        };
+        if current.name.trim().is_empty() {
+            message.set(Some(Err(tr.t("palette-name-required"))));
+            return;
+        }
+        spawn(async move {
+            let name = current.name.trim().to_string();
+            let result = match current.id {
+                Some(id) => update_palette(Palette { id, name: name.clone(), colors: current.colors }).await,
+                None => save_palette(name.clone(), current.colors).await.map(|_| ()),
+            };
+            match result {
+                Ok(()) => {
+                    draft.set(None);
+                    message.set(Some(Ok(tr.t_args("palette-saved", &[("name", &name)]))));
+                }
+                Err(e) => message.set(Some(Err(format!("{:#}", e)))),
+            }
+            reload();
+            restore_active();
+        });
+    };
+
+    let import_json = move |json: String| async move {
+        let shared = parse_shared_palette(&json)?;
+        let name = shared.name.clone();
+        save_palette(shared.name, shared.colors).await?;
+        anyhow::Ok(name)
+    };
+
+    let paste_palette = move |_| {
+        spawn(async move {
+            let mut paste = eval("dioxus.send(await navigator.clipboard.readText());");
+            let Ok(json) = paste.recv::<String>().await else {
+                message.set(Some(Err(tr.t("palette-clipboard-failed"))));
+                return;
+            };
+            match import_json(json).await {
+                Ok(name) => message.set(Some(Ok(tr.t_args("palette-imported", &[("name", &name)])))),
+                Err(e) => message.set(Some(Err(format!("{:#}", e)))),
+            }
+            reload();
+        });
+    };
+
+    let handle_import = move |evt: Event<FormData>| {
+        let Some(file_engine) = evt.files() else {
+            return;
+        };
+        spawn(async move {
+            for file_name in file_engine.files() {
+                let Some(contents) = file_engine.read_file_to_string(&file_name).await else {
+                    message.set(Some(Err(tr.t_args("file-read-failed", &[("file", &file_name)]))));
+                    return;
+                };
+                if let Err(e) = import_json(contents).await {
+                    message.set(Some(Err(format!("{}: {:#}", file_name, e))));
+                    reload();
+                    return;
+                }
+            }
+            message.set(Some(Ok(tr.t("palette-imported-files"))));
+            reload();
+        });
+    };
+
+    let mut copy_palette = move |palette: Palette| {
+        let Ok(json) = serde_json::to_string_pretty(&SharedPalette::from(&palette)) else {
+            return;
+        };
+        let copy = eval("await navigator.clipboard.writeText(await dioxus.recv());");
+        let _ = copy.send(json);
+        message.set(Some(Ok(tr.t_args("palette-copied", &[("name", &palette.name)]))));
+    };
+
+    let button_class = "px-3 py-2 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--tertiary)] hover:text-[var(--on-tertiary)] text-sm";
+    let small_button_class = "px-2 text-xs rounded text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-highest)]";
+    let active_id = settings.0.read().palette_id;
+
+    rsx! {
+        div { class: "border-t border-[var(--outline-variant)] pt-6",
+            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4", {tr.t("palettes-title")} }
+            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                {tr.t("palettes-description")}
+            }
+            div { class: "flex flex-wrap items-center gap-2 mb-4",
+                label { class: "text-sm text-[var(--on-surface)]", r#for: "active-palette", {tr.t("palettes-active")} }
+                select {
+                    id: "active-palette",
+                    class: "p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                    onchange: move |e| {
+                        let id = e.value().parse::<i32>().ok();
+                        settings.update(|settings| settings.palette_id = id);
+                    },
+                    option { value: "", selected: active_id.is_none(), {tr.t("palettes-built-in")} }
+                    for palette in palettes() {
+                        option {
+                            key: "{palette.id}",
+                            value: "{palette.id}",
+                            selected: active_id == Some(palette.id),
+                            "{palette.name}"
+                        }
+                    }
+                }
+                button {
+                    class: button_class,
+                    onclick: move |_| {
+                        draft.set(Some(PaletteDraft {
+                            id: None,
+                            name: String::new(),
+                            colors: builtin_colors(settings.0.peek().dark_mode),
+                        }))
+                    },
+                    {tr.t("palettes-new")}
+                }
+                button { class: button_class, onclick: paste_palette, {tr.t("palettes-paste")} }
+                label { class: "cursor-pointer {button_class}",
+                    {tr.t("palettes-import")}
+                    input {
+                        r#type: "file",
+                        class: "hidden",
+                        accept: ".json",
+                        multiple: true,
+                        onchange: handle_import,
+                    }
+                }
+            }
+
+            div { class: "grid grid-cols-[1fr_auto_auto_auto] gap-x-2 gap-y-1 text-sm mb-4",
+                for palette in palettes() {
+                    span { key: "{palette.id}-name", class: "text-[var(--on-surface)]", "{palette.name}" }
+                    button {
+                        key: "{palette.id}-edit",
+                        class: small_button_class,
+                        onclick: {
+                            let palette = palette.clone();
+                            move |_| {
+                                // Start from the built-in colors so variables added later still get a value
+                                let mut colors = builtin_colors(settings.0.peek().dark_mode);
+                                colors.extend(palette.colors.clone());
+                                draft.set(Some(PaletteDraft {
+                                    id: Some(palette.id),
+                                    name: palette.name.clone(),
+                                    colors,
+                                }));
+                            }
+                        },
+                        {tr.t("palettes-edit")}
+                    }
+                    button {
+                        key: "{palette.id}-copy",
+                        class: small_button_class,
+                        onclick: {
+                            let palette = palette.clone();
+                            move |_| copy_palette(palette.clone())
+                        },
+                        {tr.t("palettes-copy")}
+                    }
+                    button {
+                        key: "{palette.id}-delete",
+                        class: small_button_class,
+                        onclick: move |_| {
+                            let id = palette.id;
+                            if settings.0.peek().palette_id == Some(id) {
+                                settings.update(|settings| settings.palette_id = None);
+                            }
+                            spawn(async move {
+                                if let Err(e) = delete_palette(id).await {
+                                    message.set(Some(Err(format!("{:#}", e))));
+                                }
+                                reload();
+                            });
+                        },
+                        {tr.t("palettes-delete")}
+                    }
+                }
+            }
+
+            if let Some(current) = draft() {
+                div { class: "rounded-lg border border-[var(--outline-variant)] p-4 space-y-4",
+                    input {
+                        r#type: "text",
+                        class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                        placeholder: tr.t("palettes-name"),
+                        value: "{current.name}",
+                        oninput: move |e| {
+                            if let Some(draft) = draft.write().as_mut() {
+                                draft.name = e.value();
+                            }
+                        },
+                    }
+                    div { class: "grid grid-cols-2 sm:grid-cols-3 gap-2 text-xs",
+                        for (name, _, _) in PALETTE_VARIABLES.iter() {
+                            label { key: "{name}", class: "flex items-center gap-2 text-[var(--on-surface)]",
+                                input {
+                                    r#type: "color",
+                                    class: "h-6 w-10 cursor-pointer rounded border border-[var(--outline-variant)]",
+                                    value: current.colors.get(*name).cloned().unwrap_or_default(),
+                                    oninput: move |e| set_color(name.to_string(), e.value()),
+                                }
+                                span { class: "font-mono truncate", "--{name}" }
+                            }
+                        }
+                    }
+
+                    h3 { class: "text-sm font-semibold text-[var(--on-surface)]", {tr.t("palettes-contrast")} }
+                    div { class: "grid grid-cols-[auto_1fr_auto_auto] gap-x-3 gap-y-1 items-center text-xs",
+                        for (foreground, background) in contrast_pairs() {
+                            {
+                                let fg = current.colors.get(foreground).cloned().unwrap_or_default();
+                                let bg = current.colors.get(background).cloned().unwrap_or_default();
+                                let ratio = contrast_ratio(&fg, &bg).unwrap_or(1.0);
+                                let passes = ratio >= MIN_CONTRAST;
+                                rsx! {
+                                    span {
+                                        key: "{foreground}-sample",
+                                        class: "px-2 rounded font-semibold",
+                                        style: "color: {fg}; background-color: {bg};",
+                                        "Aa"
+                                    }
+                                    span { key: "{foreground}-pair", class: "font-mono text-[var(--on-surface)]",
+                                        {tr.t_args("palettes-contrast-pair", &[("foreground", &foreground), ("background", &background)])}
+                                    }
+                                    span { key: "{foreground}-ratio", class: "font-mono text-[var(--on-surface)]",
+                                        "{ratio:.2}:1"
+                                    }
+                                    span {
+                                        key: "{foreground}-verdict",
+                                        class: if passes { "text-[var(--primary)]" } else { "font-semibold text-[var(--error)]" },
+                                        if passes { "AA" } else { {tr.t("palettes-contrast-low")} }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "flex gap-2",
+                        button { class: button_class, onclick: save_draft, {tr.t("palettes-save")} }
+                        button {
+                            class: "px-3 py-2 rounded-lg border border-[var(--outline-variant)] text-[var(--on-surface)] hover:bg-[var(--surface-container-highest)] text-sm",
+                            onclick: move |_| {
+                                draft.set(None);
+                                restore_active();
+                            },
+                            {tr.t("cancel")}
+                        }
+                    }
+                }
+            }
+
+            match message() {
+                Some(Ok(msg)) => rsx! {
+                    div { class: "mt-4 text-sm text-[var(--primary)]", "{msg}" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "mt-4 p-2 rounded-lg bg-[var(--error)] text-[var(--on-error)] text-sm", "{err}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
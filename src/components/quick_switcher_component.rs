@@ -0,0 +1,140 @@
+use crate::data::{get_quick_switch_items, QuickSwitchItem};
+use crate::helpers::{fuzzy_score, use_tr};
+use crate::Route;
+use dioxus::prelude::*;
+use std::cmp::Reverse;
+
+const MAX_RESULTS: usize = 50;
+// Bonus for the most recently opened item, decreasing by one per older item
+const RECENCY_BONUS: i32 = 15;
+
+#[component]
+pub fn QuickSwitcherComponent(on_close: EventHandler<()>) -> Element {
+    let tr = use_tr();
+    let nav = navigator();
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+    let mut items = use_signal(Vec::<QuickSwitchItem>::new);
+
+    use_effect(move || {
+        spawn(async move {
+            match get_quick_switch_items().await {
+                Ok(loaded) => items.set(loaded),
+                Err(e) => log::error!("Failed to load quick switcher items: {}", e),
+            }
+        });
+    });
+
+    // Items arrive most recent first, so position doubles as recency rank
+    let matches = use_memo(move || {
+        let query = query();
+        let mut ranked: Vec<(i32, QuickSwitchItem)> = items
+            .read()
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, item)| {
+                let haystack = match item.note_id {
+                    Some(_) => format!("{} {}", item.label, item.folder_path),
+                    None => item.folder_path.clone(),
+                };
+                let score = fuzzy_score(&query, &item.label)
+                    .into_iter()
+                    .chain(fuzzy_score(&query, &haystack))
+                    .max()?;
+                let recency = match item.opened_at {
+                    Some(_) => (RECENCY_BONUS - rank as i32).max(0),
+                    None => 0,
+                };
+                Some((score + recency, item.clone()))
+            })
+            .collect();
+        ranked.sort_by_key(|(score, ..)| Reverse(*score));
+        ranked.into_iter().take(MAX_RESULTS).map(|(_, item)| item).collect::<Vec<_>>()
+    });
+
+    let open_item = move |item: QuickSwitchItem| {
+        nav.push(match item.note_id {
+            Some(note_id) => Route::NotePage { note_id },
+            None => Route::FolderPage { folder_id: item.folder_id },
+        });
+        on_close.call(());
+    };
+
+    let handle_key = move |e: KeyboardEvent| {
+        let count = matches.read().len();
+        match e.key() {
+            Key::ArrowDown if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + 1) % count);
+            }
+            Key::ArrowUp if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + count - 1) % count);
+            }
+            Key::Enter => {
+                let item = matches.read().get(selected()).cloned();
+                if let Some(item) = item {
+                    open_item(item);
+                }
+            }
+            Key::Escape => on_close.call(()),
+            _ => {}
+        }
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-start justify-center pt-24 z-50",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-[var(--surface-container-low)] rounded-xl shadow-lg w-[36rem] max-w-[90vw] overflow-hidden border border-[var(--outline-variant)]",
+                onclick: move |e| e.stop_propagation(),
+
+                input {
+                    r#type: "text",
+                    class: "w-full p-4 bg-[var(--surface-container-low)] text-[var(--on-surface)] outline-none border-b border-[var(--outline-variant)]",
+                    placeholder: tr.t("switcher-placeholder"),
+                    value: "{query}",
+                    onmounted: move |e| async move {
+                        let _ = e.set_focus(true).await;
+                    },
+                    oninput: move |e| {
+                        query.set(e.value());
+                        selected.set(0);
+                    },
+                    onkeydown: handle_key,
+                }
+
+                div { class: "max-h-96 overflow-y-auto py-2",
+                    if matches.read().is_empty() {
+                        div { class: "px-4 py-2 text-sm text-[var(--on-surface-variant)]", {tr.t("no-matches")} }
+                    }
+                    for (index, item) in matches.read().iter().cloned().enumerate() {
+                        button {
+                            key: "{item.folder_id}-{item.note_id:?}",
+                            class: "cursor-pointer w-full flex items-center gap-3 px-4 py-2 text-left",
+                            class: if index == selected() { "bg-[var(--secondary-container)]" } else { "hover:bg-[var(--surface-container-highest)]" },
+                            onclick: {
+                                let item = item.clone();
+                                move |_| open_item(item.clone())
+                            },
+                            span { class: "text-xs w-12 shrink-0 text-[var(--on-surface-variant)]",
+                                if item.note_id.is_some() { {tr.t("switcher-note")} } else { {tr.t("switcher-folder")} }
+                            }
+                            div { class: "flex flex-col min-w-0",
+                                span { class: "text-sm font-medium truncate text-[var(--on-surface)]", "{item.label}" }
+                                if item.note_id.is_some() {
+                                    span { class: "text-xs truncate text-[var(--on-surface-variant)]", "{item.folder_path}" }
+                                }
+                            }
+                            if let Some(opened_at) = &item.opened_at {
+                                span { class: "ml-auto text-xs text-[var(--on-surface-variant)]", {tr.date(opened_at)} }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
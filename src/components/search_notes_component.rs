@@ -0,0 +1,101 @@
+use crate::data::{search_notes, NoteSearchHit};
+use crate::helpers::use_tr;
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SearchNotesComponent(on_close: EventHandler<()>) -> Element {
+    let tr = use_tr();
+    let nav = navigator();
+    let mut query = use_signal(String::new);
+    let mut hits = use_signal(Vec::<NoteSearchHit>::new);
+    let mut selected = use_signal(|| 0usize);
+
+    use_effect(move || {
+        let query = query();
+        spawn(async move {
+            if query.trim().is_empty() {
+                hits.set(Vec::new());
+                return;
+            }
+            match search_notes(query).await {
+                Ok(found) => hits.set(found),
+                Err(e) => log::error!("Failed to search notes: {}", e),
+            }
+            selected.set(0);
+        });
+    });
+
+    let open_hit = move |hit: NoteSearchHit| {
+        nav.push(Route::NotePage { note_id: hit.note_id });
+        on_close.call(());
+    };
+
+    let handle_key = move |e: KeyboardEvent| {
+        let count = hits.read().len();
+        match e.key() {
+            Key::ArrowDown if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + 1) % count);
+            }
+            Key::ArrowUp if count > 0 => {
+                e.prevent_default();
+                selected.set((selected() + count - 1) % count);
+            }
+            Key::Enter => {
+                let hit = hits.read().get(selected()).cloned();
+                if let Some(hit) = hit {
+                    open_hit(hit);
+                }
+            }
+            Key::Escape => on_close.call(()),
+            _ => {}
+        }
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-start justify-center pt-24 z-50",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-[var(--surface-container-low)] rounded-xl shadow-lg w-[40rem] max-w-[90vw] overflow-hidden border border-[var(--outline-variant)]",
+                onclick: move |e| e.stop_propagation(),
+
+                input {
+                    r#type: "search",
+                    class: "w-full p-4 bg-[var(--surface-container-low)] text-[var(--on-surface)] outline-none border-b border-[var(--outline-variant)]",
+                    placeholder: tr.t("search-placeholder"),
+                    value: "{query}",
+                    onmounted: move |e| async move {
+                        let _ = e.set_focus(true).await;
+                    },
+                    oninput: move |e| query.set(e.value()),
+                    onkeydown: handle_key,
+                }
+
+                div { class: "max-h-96 overflow-y-auto py-2",
+                    if !query.read().trim().is_empty() && hits.read().is_empty() {
+                        div { class: "px-4 py-2 text-sm text-[var(--on-surface-variant)]", {tr.t("search-no-results")} }
+                    }
+                    for (index, hit) in hits.read().iter().cloned().enumerate() {
+                        button {
+                            key: "{hit.note_id}",
+                            class: "cursor-pointer w-full flex flex-col px-4 py-2 text-left",
+                            class: if index == selected() { "bg-[var(--secondary-container)]" } else { "hover:bg-[var(--surface-container-highest)]" },
+                            onclick: {
+                                let hit = hit.clone();
+                                move |_| open_hit(hit.clone())
+                            },
+                            span { class: "text-sm font-medium text-[var(--on-surface)]",
+                                {hit.title.clone().unwrap_or_else(|| tr.t_args("note-untitled", &[("id", &hit.note_id)]))}
+                            }
+                            span { class: "text-xs text-[var(--on-surface-variant)]", "{hit.folder_name}" }
+                            span { class: "text-xs truncate text-[var(--on-surface-variant)] font-mono", "{hit.snippet}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::syntax::theme_preview_html;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ThemeThumbnailComponent(
+    theme: ReadSignal<String>,
+    name: String,
+    on_click: EventHandler<String>,
+) -> Element {
+    let is_active = theme() == name;
+    let preview_html = use_memo({
+        let name = name.clone();
+        move || theme_preview_html(&name)
+    });
+
+    rsx! {
+        button {
+            class: format!(
+                "flex flex-col w-44 rounded-lg border-2 overflow-hidden text-left cursor-pointer transition-colors {}",
+                if is_active {
+                    "border-[var(--primary)]"
+                } else {
+                    "border-[var(--outline-variant)] hover:border-[var(--primary)]"
+                },
+            ),
+            title: "{name}",
+            onclick: move |_| on_click.call(name.clone()),
+            div {
+                class: "text-[10px] leading-tight overflow-hidden pointer-events-none [&_pre]:m-0 [&_pre]:p-2",
+                dangerous_inner_html: "{preview_html}",
+            }
+            span {
+                class: format!(
+                    "px-2 py-1 text-xs truncate {}",
+                    if is_active {
+                        "bg-[var(--primary)] text-[var(--on-primary)]"
+                    } else {
+                        "bg-[var(--surface-container-high)] text-[var(--on-surface)]"
+                    },
+                ),
+                "{name}"
+            }
+        }
+    }
+}
@@ -1,16 +1,38 @@
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::Duration;
 use anyhow::{Result, Context};
+use rusqlite::OptionalExtension;
+use crate::data::{
+    build_folder_tree, folder_paths, migrate_legacy_settings, note_label, quick_switch_items, search_snippet,
+    Folder, Note, NoteSearchHit, QuickSwitchItem,
+};
+use crate::syntax::{note_meta, note_title, NoteMeta};
 
 thread_local! {
-    static DELETE_COUNTER: RefCell<u32> = RefCell::new(0);
+    static DELETE_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+}
+
+/// Overrides the notes database location, e.g. for `runorna-cli --db`.
+pub const DATABASE_ENV: &str = "RUNORNA_DB";
+// The GUI and runorna-cli may write at the same time; wait this long for the lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn database_path() -> PathBuf {
+    std::env::var_os(DATABASE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("folder.db"))
 }
 
 thread_local! {
     pub static DB: rusqlite::Connection = {
-        let conn = rusqlite::Connection::open("folder.db").expect("Failed to open database");
+        let conn = rusqlite::Connection::open(database_path()).expect("Failed to open database");
+        conn.busy_timeout(BUSY_TIMEOUT).expect("Failed to set busy timeout");
         conn.execute_batch(
-            "PRAGMA foreign_keys = ON;
+            "PRAGMA journal_mode = WAL;
+            PRAGMA foreign_keys = ON;
             CREATE TABLE IF NOT EXISTS folder (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 name TEXT NOT NULL,
@@ -23,49 +45,137 @@ thread_local! {
                 content TEXT NOT NULL,
                 date_created DATETIME NOT NULL,
                 folder_id INTEGER NOT NULL,
+                title TEXT,
+                tags TEXT NOT NULL DEFAULT '',
+                language TEXT,
+                aliases TEXT NOT NULL DEFAULT '',
+                pinned_theme TEXT,
+                label TEXT,
                 FOREIGN KEY(folder_id) REFERENCES folder(id) ON DELETE CASCADE
             );
-            CREATE TABLE IF NOT EXISTS theme_preference (
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS palette (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                theme_name TEXT NOT NULL,
-                date_created DATETIME NOT NULL
+                name TEXT NOT NULL,
+                colors TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS keybinding (
+                command TEXT PRIMARY KEY,
+                combo TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS api_config (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                enabled BOOLEAN NOT NULL,
+                port INTEGER NOT NULL,
+                token TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS language_alias (
+                alias TEXT PRIMARY KEY,
+                lang TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS recent_open (
+                kind TEXT NOT NULL,
+                item_id INTEGER NOT NULL,
+                opened_at DATETIME NOT NULL,
+                PRIMARY KEY (kind, item_id)
             );"
 
         ).unwrap();
+
+        // Front matter columns, added to databases created before they existed
+        for (column, definition) in [
+            ("title", "TEXT"),
+            ("tags", "TEXT NOT NULL DEFAULT ''"),
+            ("language", "TEXT"),
+            ("aliases", "TEXT NOT NULL DEFAULT ''"),
+            ("pinned_theme", "TEXT"),
+            ("label", "TEXT"),
+        ] {
+            add_column_if_missing(&conn, "note", column, definition).unwrap();
+        }
+        if let Err(e) = fill_missing_labels(&conn) {
+            log::error!("Failed to fill note labels: {:#}", e);
+        }
+        if let Err(e) = migrate_legacy_settings(&conn) {
+            log::error!("Failed to migrate theme settings: {:#}", e);
+        }
         conn
     };
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Note {
-    pub id: i32,
-    pub content: String,
-    pub date_created: String,
-    pub folder_id: i32,
+fn add_column_if_missing(
+    conn: &rusqlite::Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let exists = conn
+        .prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1", table))?
+        .exists([column])?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .with_context(|| format!("Failed to add column {}.{}", table, column))?;
+    }
+    Ok(())
 }
 
+// Tags and aliases are stored as a JSON array in a single column. Notes saved before
+// that hold a comma-separated list.
+fn join_list(items: &[String]) -> String {
+    serde_json::to_string(items).unwrap_or_default()
+}
+
+fn split_list(value: String) -> Vec<String> {
+    serde_json::from_str(&value).unwrap_or_else(|_| {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+// Notes saved before the label column existed.
+fn fill_missing_labels(conn: &rusqlite::Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT id, title, content FROM note WHERE label IS NULL")?;
+    let labels = stmt
+        .query_map([], |row| {
+            let title: Option<String> = row.get(1)?;
+            let content: String = row.get(2)?;
+            Ok((row.get::<_, i32>(0)?, title.unwrap_or_else(|| note_title(&content))))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (id, label) in labels {
+        conn.execute("UPDATE note SET label = ?1 WHERE id = ?2", (label, id))?;
+    }
+    Ok(())
+}
+
+/// Settings of the opt-in local HTTP API.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Folder {
-    pub id: i32,
-    pub name: String,
-    pub date_created: String,
-    pub parent_id: Option<i32>,
-    pub children: Vec<Folder>,
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
 }
 
 pub async fn save_folder(
     name: String,
     date_created: String,
     parent_id: Option<i32>,
-) -> Result<()> {
+) -> Result<i32> {
     DB.with(|f| {
         f.execute(
             "INSERT INTO folder (name, date_created, parent_id) VALUES (?1, ?2, ?3)",
             (&name, &date_created, &parent_id),
         )
-            .context("Failed to save folder")
-    })?;
-    Ok(())
+            .context("Failed to save folder")?;
+        Ok(f.last_insert_rowid() as i32)
+    })
 }
 
 pub async fn update_folder_name(id: i32, new_name: String) -> Result<()> {
@@ -100,17 +210,6 @@ pub async fn delete_folder_recursive(id: i32) -> Result<()> {
     Ok(())
 }
 
-fn assign_children_recursively(folder: &mut Folder, children_map: &mut std::collections::HashMap<i32, Vec<Folder>>) {
-    if let Some(mut children) = children_map.remove(&folder.id) {
-        children.sort_by(|a, b| a.name.cmp(&b.name));
-
-        for mut child in children {
-            assign_children_recursively(&mut child, children_map);
-            folder.children.push(child);
-        }
-    }
-}
-
 pub async fn get_folders() -> Result<Vec<Folder>> {
     DB.with(|conn| {
         let mut stmt = conn.prepare("SELECT id, name, date_created, parent_id FROM folder ORDER BY name ASC")
@@ -129,70 +228,74 @@ pub async fn get_folders() -> Result<Vec<Folder>> {
             .collect::<Result<Vec<Folder>, _>>()
             .context("Failed to collect folder rows")?;
 
-        if folder_rows.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut folders_map: std::collections::HashMap<i32, Folder> = folder_rows
-            .into_iter()
-            .map(|f| (f.id, f))
-            .collect();
-
-        let mut root_folders: Vec<Folder> = Vec::new();
-        let mut temp_children_map: std::collections::HashMap<i32, Vec<Folder>> = std::collections::HashMap::new();
-
-        for (_, folder) in folders_map.drain() {
-            if let Some(parent_id) = folder.parent_id {
-                temp_children_map.entry(parent_id).or_insert_with(Vec::new).push(folder);
-            } else {
-                root_folders.push(folder);
-            }
-        }
-
-        root_folders.sort_by(|a, b| a.name.cmp(&b.name));
-        for folder in root_folders.iter_mut() {
-            assign_children_recursively(folder, &mut temp_children_map);
-        }
-
-        // Attach any orphaned children
-        for (_, orphaned) in temp_children_map {
-            root_folders.extend(orphaned.into_iter());
-        }
-        Ok(root_folders)
+        Ok(build_folder_tree(folder_rows))
     })
 }
 
+fn insert_note(
+    conn: &rusqlite::Connection,
+    content: &str,
+    date_created: &str,
+    folder_id: i32,
+) -> Result<i32> {
+    let meta = note_meta(content).unwrap_or_default();
+    conn.execute(
+        "INSERT INTO note (content, date_created, folder_id, title, tags, language, aliases, pinned_theme, label)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        (
+            content,
+            date_created,
+            &folder_id,
+            &meta.title,
+            join_list(&meta.tags),
+            &meta.language,
+            join_list(&meta.aliases),
+            &meta.theme,
+            note_label(Some(&meta), content),
+        ),
+    )
+        .context("Failed to save note")?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
 pub async fn save_note(
     content: String,
     date_created: String,
     folder_id: i32,
-) -> Result<()> {
-    DB.with(|conn| {
-        conn.execute(
-            "INSERT INTO note (content, date_created, folder_id) VALUES (?1, ?2, ?3)",
-            (&content, &date_created, &folder_id),
-        )
-            .context("Failed to save note")
-    })?;
-    Ok(())
+) -> Result<i32> {
+    DB.with(|conn| insert_note(conn, &content, &date_created, folder_id))
+}
+
+const NOTE_COLUMNS: &str =
+    "id, content, date_created, folder_id, title, tags, language, aliases, pinned_theme";
+
+fn note_from_row(row: &rusqlite::Row) -> rusqlite::Result<Note> {
+    Ok(Note {
+        id: row.get(0)?,
+        content: row.get(1)?,
+        date_created: row.get(2)?,
+        folder_id: row.get(3)?,
+        meta: NoteMeta {
+            title: row.get(4)?,
+            tags: split_list(row.get(5)?),
+            language: row.get(6)?,
+            aliases: split_list(row.get(7)?),
+            theme: row.get(8)?,
+            ..Default::default()
+        },
+    })
 }
 
 pub async fn get_notes(folder_id: i32) -> Result<Vec<Note>> {
     DB.with(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, content, date_created, folder_id FROM note WHERE folder_id = ?1 ORDER BY date_created DESC"
-        )
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note WHERE folder_id = ?1 ORDER BY date_created DESC",
+            NOTE_COLUMNS
+        ))
             .context("Failed to prepare notes query")?;
 
         let notes = stmt
-            .query_map([folder_id], |row| {
-                Ok(Note {
-                    id: row.get(0)?,
-                    content: row.get(1)?,
-                    date_created: row.get(2)?,
-                    folder_id: row.get(3)?,
-                })
-            })?
+            .query_map([folder_id], note_from_row)?
             .collect::<Result<Vec<Note>, _>>()
             .context("Failed to collect note rows")?;
 
@@ -200,21 +303,62 @@ pub async fn get_notes(folder_id: i32) -> Result<Vec<Note>> {
     })
 }
 
+pub async fn get_note(id: i32) -> Result<Note> {
+    DB.with(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM note WHERE id = ?1", NOTE_COLUMNS),
+            [id],
+            note_from_row,
+        )
+            .with_context(|| format!("Failed to get note {}", id))
+    })
+}
+
 pub async fn update_note(
     id: i32,
     content: String,
     date_updated: String,
 ) -> Result<()> {
+    // A front matter typo keeps the metadata stored from the last valid version
+    let Ok(meta) = note_meta(&content) else {
+        DB.with(|conn| {
+            conn.execute(
+                "UPDATE note SET content = ?1, date_created = ?2, label = COALESCE(title, ?3) WHERE id = ?4",
+                (&content, &date_updated, note_label(None, &content), &id),
+            )
+                .context("Failed to update note")
+        })?;
+        return Ok(());
+    };
     DB.with(|conn| {
         conn.execute(
-            "UPDATE note SET content = ?1, date_created = ?2 WHERE id = ?3",
-            (&content, &date_updated, &id),
+            "UPDATE note SET content = ?1, date_created = ?2, title = ?3, tags = ?4, language = ?5,
+             aliases = ?6, pinned_theme = ?7, label = ?8 WHERE id = ?9",
+            (
+                &content,
+                &date_updated,
+                &meta.title,
+                join_list(&meta.tags),
+                &meta.language,
+                join_list(&meta.aliases),
+                &meta.theme,
+                note_label(Some(&meta), &content),
+                &id,
+            ),
         )
             .context("Failed to update note")
     })?;
     Ok(())
 }
 
+pub async fn delete_note(id: i32) -> Result<()> {
+    DB.with(|conn| {
+        conn.execute("DELETE FROM note WHERE id = ?1", [id])
+            .context("Failed to delete note")
+    })?;
+    Ok(())
+}
+
 pub async fn get_folder_name(folder_id: i32) -> Result<String> {
     DB.with(|conn| {
         conn.query_row(
@@ -226,36 +370,258 @@ pub async fn get_folder_name(folder_id: i32) -> Result<String> {
     })
 }
 
-pub async fn save_theme_preference(theme_name: String) -> Result<()> {
-    let now = chrono::Local::now().to_rfc3339();
+pub async fn search_notes(query: String) -> Result<Vec<NoteSearchHit>> {
+    let pattern = format!(
+        "%{}%",
+        query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+    DB.with(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT note.id, note.folder_id, folder.name, note.title, note.content
+             FROM note JOIN folder ON folder.id = note.folder_id
+             WHERE note.content LIKE ?1 ESCAPE '\\'
+             ORDER BY note.date_created DESC LIMIT 50"
+        )
+            .context("Failed to prepare search query")?;
+
+        let hits = stmt
+            .query_map([&pattern], |row| {
+                let content: String = row.get(4)?;
+                Ok(NoteSearchHit {
+                    note_id: row.get(0)?,
+                    folder_id: row.get(1)?,
+                    folder_name: row.get(2)?,
+                    title: row.get(3)?,
+                    snippet: search_snippet(&content, &query),
+                })
+            })?
+            .collect::<Result<Vec<NoteSearchHit>, _>>()
+            .context("Failed to collect search results")?;
+
+        Ok(hits)
+    })
+}
+
+pub async fn load_keybindings() -> Result<HashMap<String, String>> {
+    DB.with(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT command, combo FROM keybinding")
+            .context("Failed to prepare keybinding query")?;
+        let bindings = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<String, String>, _>>()
+            .context("Failed to load keybindings")?;
+        Ok(bindings)
+    })
+}
+
+/// Stores a remapped binding; an empty combo leaves the command unbound.
+pub async fn save_keybinding(command: String, combo: String) -> Result<()> {
     DB.with(|conn| {
-        conn.execute("DELETE FROM theme_preference", [])
-            .context("Failed to clear existing theme preferences")?;
         conn.execute(
-            "INSERT INTO theme_preference (theme_name, date_created) VALUES (?1, ?2)",
-            (&theme_name, &now),
+            "INSERT INTO keybinding (command, combo) VALUES (?1, ?2)
+             ON CONFLICT(command) DO UPDATE SET combo = excluded.combo",
+            (&command, &combo),
         )
-            .context("Failed to save theme preference")
+            .context("Failed to save keybinding")
     })?;
     Ok(())
 }
 
-pub async fn load_theme_preference() -> Result<String> {
+pub async fn reset_keybinding(command: String) -> Result<()> {
     DB.with(|conn| {
-        match conn.query_row(
-            "SELECT theme_name FROM theme_preference ORDER BY date_created DESC LIMIT 1",
-            [],
-            |row| row.get(0),
-        ) {
-            Ok(theme) => Ok(theme),
-            Err(e) => {
-                if e.to_string().contains("no rows") {
-                    // Return default theme if no preference exists
-                    Ok("base16-eighties.dark".to_string())
-                } else {
-                    Err(anyhow::Error::new(e).context("Failed to load theme preference"))
+        conn.execute("DELETE FROM keybinding WHERE command = ?1", [&command])
+            .context("Failed to reset keybinding")
+    })?;
+    Ok(())
+}
+
+pub async fn record_opened(folder_id: i32, note_id: Option<i32>) -> Result<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    DB.with(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "INSERT INTO recent_open (kind, item_id, opened_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(kind, item_id) DO UPDATE SET opened_at = excluded.opened_at",
+            )
+            .context("Failed to prepare recent open statement")?;
+        stmt.execute(("folder", folder_id, &now))
+            .context("Failed to record opened folder")?;
+        if let Some(note_id) = note_id {
+            stmt.execute(("note", note_id, &now))
+                .context("Failed to record opened note")?;
+        }
+        Ok(())
+    })
+}
+
+fn load_folder_paths(conn: &rusqlite::Connection) -> Result<HashMap<i32, String>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, parent_id FROM folder")
+        .context("Failed to prepare folder path query")?;
+    let folders = stmt
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .collect::<Result<HashMap<i32, (String, Option<i32>)>, _>>()
+        .context("Failed to collect folders")?;
+    Ok(folder_paths(&folders))
+}
+
+/// Every folder and note, most recently opened first, then alphabetically.
+pub async fn get_quick_switch_items() -> Result<Vec<QuickSwitchItem>> {
+    DB.with(|conn| {
+        let paths = load_folder_paths(conn)?;
+        let recent = {
+            let mut stmt = conn
+                .prepare("SELECT kind, item_id, opened_at FROM recent_open")
+                .context("Failed to prepare recent open query")?;
+            let recent = stmt
+                .query_map([], |row| Ok(((row.get::<_, String>(0)?, row.get::<_, i32>(1)?), row.get(2)?)))?
+                .collect::<Result<HashMap<(String, i32), String>, _>>()
+                .context("Failed to load recently opened items")?;
+            recent
+        };
+
+        let mut stmt = conn
+            .prepare("SELECT id, folder_id, label FROM note")
+            .context("Failed to prepare quick switch notes query")?;
+        let notes = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect quick switch notes")?;
+
+        Ok(quick_switch_items(&paths, &recent, notes))
+    })
+}
+
+/// Folder whose path (as shown by the quick switcher, e.g. "Docker/Compose") matches.
+pub async fn find_folder_by_path(path: String) -> Result<Option<i32>> {
+    let path = path.trim_matches('/');
+    DB.with(|conn| {
+        Ok(load_folder_paths(conn)?
+            .into_iter()
+            .find(|(_, folder_path)| folder_path == path)
+            .map(|(id, _)| id))
+    })
+}
+
+/// Saves a note under `folder_path`, creating any missing folders along the way.
+/// Runs as one write transaction so a concurrently running GUI never sees half of it.
+pub async fn add_note_at_path(folder_path: String, content: String) -> Result<(i32, i32)> {
+    let now = chrono::Local::now().to_rfc3339();
+    DB.with(|conn| {
+        let tx = rusqlite::Transaction::new_unchecked(conn, rusqlite::TransactionBehavior::Immediate)
+            .context("Failed to lock the database")?;
+
+        let mut parent_id: Option<i32> = None;
+        for name in folder_path.split('/').map(str::trim).filter(|name| !name.is_empty()) {
+            let existing = tx
+                .query_row(
+                    "SELECT id FROM folder WHERE name = ?1 AND parent_id IS ?2 ORDER BY id LIMIT 1",
+                    (name, &parent_id),
+                    |row| row.get(0),
+                )
+                .optional()
+                .context("Failed to look up folder")?;
+            parent_id = Some(match existing {
+                Some(id) => id,
+                None => {
+                    tx.execute(
+                        "INSERT INTO folder (name, date_created, parent_id) VALUES (?1, ?2, ?3)",
+                        (name, &now, &parent_id),
+                    )
+                        .with_context(|| format!("Failed to create folder {}", name))?;
+                    tx.last_insert_rowid() as i32
                 }
-            }
+            });
         }
+        let folder_id = parent_id.context("Folder path is empty")?;
+
+        let note_id = insert_note(&tx, &content, &now, folder_id)?;
+        tx.commit().context("Failed to commit note")?;
+        Ok((folder_id, note_id))
     })
-}
\ No newline at end of file
+}
+
+pub const DEFAULT_API_PORT: u16 = 7878;
+
+/// Random 32-byte hex token for the local API.
+pub fn new_api_token() -> String {
+    rand::random::<[u8; 32]>().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The API configuration, created disabled with a fresh token on first use.
+pub async fn load_api_config() -> Result<ApiConfig> {
+    let stored = DB.with(|conn| {
+        conn.query_row(
+            "SELECT enabled, port, token FROM api_config WHERE id = 1",
+            [],
+            |row| {
+                Ok(ApiConfig {
+                    enabled: row.get(0)?,
+                    port: row.get(1)?,
+                    token: row.get(2)?,
+                })
+            },
+        )
+            .optional()
+            .context("Failed to load API config")
+    })?;
+    match stored {
+        Some(config) => Ok(config),
+        None => {
+            let config = ApiConfig {
+                enabled: false,
+                port: DEFAULT_API_PORT,
+                token: new_api_token(),
+            };
+            save_api_config(config.clone()).await?;
+            Ok(config)
+        }
+    }
+}
+
+pub async fn save_api_config(config: ApiConfig) -> Result<()> {
+    DB.with(|conn| {
+        conn.execute(
+            "INSERT INTO api_config (id, enabled, port, token) VALUES (1, ?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET enabled = excluded.enabled, port = excluded.port,
+             token = excluded.token",
+            (&config.enabled, &config.port, &config.token),
+        )
+            .context("Failed to save API config")
+    })?;
+    Ok(())
+}
+
+pub async fn load_language_aliases() -> Result<BTreeMap<String, String>> {
+    DB.with(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT alias, lang FROM language_alias")
+            .context("Failed to prepare language alias query")?;
+        let aliases = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<BTreeMap<String, String>, _>>()
+            .context("Failed to load language aliases")?;
+        Ok(aliases)
+    })
+}
+
+pub async fn save_language_alias(alias: String, lang: String) -> Result<()> {
+    DB.with(|conn| {
+        conn.execute(
+            "INSERT INTO language_alias (alias, lang) VALUES (?1, ?2)
+             ON CONFLICT(alias) DO UPDATE SET lang = excluded.lang",
+            (&alias, &lang),
+        )
+            .context("Failed to save language alias")
+    })?;
+    Ok(())
+}
+
+pub async fn delete_language_alias(alias: String) -> Result<()> {
+    DB.with(|conn| {
+        conn.execute("DELETE FROM language_alias WHERE alias = ?1", [&alias])
+            .context("Failed to delete language alias")
+    })?;
+    Ok(())
+}
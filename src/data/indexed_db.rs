@@ -0,0 +1,727 @@
+use crate::data::{
+    build_folder_tree, folder_paths, note_label, quick_switch_items, search_snippet, settings_from_stored,
+    settings_map, Folder, Note, NoteSearchHit, Palette, QuickSwitchItem, Settings,
+};
+use crate::syntax::{note_meta, NoteMeta};
+use anyhow::{anyhow, Context, Result};
+use futures_util::future::{FutureExt, LocalBoxFuture, Shared};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbDatabase, IdbObjectStore, IdbObjectStoreParameters, IdbRequest, IdbTransaction, IdbTransactionMode,
+};
+
+const DATABASE_NAME: &str = "runorna";
+const DATABASE_VERSION: u32 = 2;
+// Version 1 kept every table as one JSON value in this store
+const LEGACY_TABLES_STORE: &str = "tables";
+
+// One object store per table of the SQLite build
+const FOLDERS: &str = "folder";
+const NOTES: &str = "note";
+// What the quick switcher lists, so listing notes never reads their content
+const NOTE_LABELS: &str = "note_label";
+const PALETTES: &str = "palette";
+const SETTINGS: &str = "setting";
+const KEYBINDINGS: &str = "keybinding";
+const LANGUAGE_ALIASES: &str = "language_alias";
+const RECENT_OPENS: &str = "recent_open";
+
+#[derive(Serialize, Deserialize)]
+struct NoteLabel {
+    id: i32,
+    folder_id: i32,
+    label: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SettingRecord {
+    key: String,
+    value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeybindingRecord {
+    command: String,
+    combo: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LanguageAliasRecord {
+    alias: String,
+    lang: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RecentOpen {
+    kind: String,
+    item_id: i32,
+    opened_at: String,
+}
+
+/// The rows of a version 1 table, stored next to its AUTOINCREMENT counter.
+#[derive(Deserialize)]
+struct LegacyRows<T> {
+    rows: Vec<T>,
+}
+
+thread_local! {
+    // Opened by whichever call comes first, the others wait for it
+    static DATABASE: OnceCell<Shared<LocalBoxFuture<'static, Result<IdbDatabase, String>>>> =
+        const { OnceCell::new() };
+}
+
+fn js_error(value: JsValue) -> anyhow::Error {
+    match value.dyn_ref::<js_sys::Error>() {
+        Some(error) => anyhow!(String::from(error.message())),
+        None => anyhow!("{:?}", value),
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue> {
+    js_sys::JSON::parse(&serde_json::to_string(value)?).map_err(js_error)
+}
+
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T> {
+    let json = js_sys::JSON::stringify(&value).map_err(js_error)?;
+    Ok(serde_json::from_str(&String::from(json))?)
+}
+
+// Resolves with the request's result once its success event fires.
+async fn request_done(request: &IdbRequest) -> Result<JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    if JsFuture::from(promise).await.is_err() {
+        return Err(match request.error() {
+            Ok(Some(error)) => anyhow!(error.message()),
+            _ => anyhow!("IndexedDB request failed"),
+        });
+    }
+    request.result().map_err(js_error)
+}
+
+async fn transaction_done(transaction: &IdbTransaction) -> Result<()> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        transaction.set_oncomplete(Some(&resolve));
+        transaction.set_onerror(Some(&reject));
+        transaction.set_onabort(Some(&reject));
+    });
+    if JsFuture::from(promise).await.is_err() {
+        return Err(match transaction.error() {
+            Some(error) => anyhow!(error.message()),
+            None => anyhow!("IndexedDB transaction failed"),
+        });
+    }
+    Ok(())
+}
+
+fn create_stores(database: &IdbDatabase) -> Result<(), JsValue> {
+    let recent_key = js_sys::Array::of2(&JsValue::from_str("kind"), &JsValue::from_str("item_id"));
+    let stores = [
+        (FOLDERS, JsValue::from_str("id"), true),
+        (NOTES, JsValue::from_str("id"), true),
+        (NOTE_LABELS, JsValue::from_str("id"), false),
+        (PALETTES, JsValue::from_str("id"), true),
+        (SETTINGS, JsValue::from_str("key"), false),
+        (KEYBINDINGS, JsValue::from_str("command"), false),
+        (LANGUAGE_ALIASES, JsValue::from_str("alias"), false),
+        (RECENT_OPENS, recent_key.into(), false),
+    ];
+    let existing = database.object_store_names();
+    for (name, key_path, auto_increment) in stores {
+        if existing.contains(name) {
+            continue;
+        }
+        let parameters = IdbObjectStoreParameters::new();
+        parameters.set_key_path(&key_path);
+        parameters.set_auto_increment(auto_increment);
+        let store = database.create_object_store_with_optional_parameters(name, &parameters)?;
+        match name {
+            FOLDERS => drop(store.create_index_with_str("parent_id", "parent_id")?),
+            NOTES => drop(store.create_index_with_str("folder_id", "folder_id")?),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+async fn legacy_table<T: DeserializeOwned>(legacy: &IdbObjectStore, key: &str) -> Result<Option<T>> {
+    let request = legacy.get(&JsValue::from_str(key)).map_err(js_error)?;
+    match request_done(&request).await?.as_string() {
+        Some(json) => serde_json::from_str(&json)
+            .map(Some)
+            .with_context(|| format!("Failed to read the {} table", key)),
+        None => Ok(None),
+    }
+}
+
+// Copies the version 1 tables into their own stores, keeping every id.
+async fn migrate_legacy_tables(transaction: &IdbTransaction) -> Result<()> {
+    let legacy = store(transaction, LEGACY_TABLES_STORE)?;
+
+    let folders: Option<LegacyRows<Folder>> = legacy_table(&legacy, FOLDERS).await?;
+    for folder in folders.map_or_else(Vec::new, |folders| folders.rows) {
+        put(&store(transaction, FOLDERS)?, &folder).await?;
+    }
+    let notes: Option<LegacyRows<Note>> = legacy_table(&legacy, NOTES).await?;
+    for note in notes.map_or_else(Vec::new, |notes| notes.rows) {
+        put(&store(transaction, NOTE_LABELS)?, &label_of(&note)).await?;
+        put(&store(transaction, NOTES)?, &note).await?;
+    }
+    let palettes: Option<LegacyRows<Palette>> = legacy_table(&legacy, PALETTES).await?;
+    for palette in palettes.map_or_else(Vec::new, |palettes| palettes.rows) {
+        put(&store(transaction, PALETTES)?, &palette).await?;
+    }
+    let settings: Option<BTreeMap<String, String>> = legacy_table(&legacy, "settings").await?;
+    for (key, value) in settings.unwrap_or_default() {
+        put(&store(transaction, SETTINGS)?, &SettingRecord { key, value }).await?;
+    }
+    let keybindings: Option<HashMap<String, String>> = legacy_table(&legacy, KEYBINDINGS).await?;
+    for (command, combo) in keybindings.unwrap_or_default() {
+        put(&store(transaction, KEYBINDINGS)?, &KeybindingRecord { command, combo }).await?;
+    }
+    let aliases: Option<BTreeMap<String, String>> = legacy_table(&legacy, LANGUAGE_ALIASES).await?;
+    for (alias, lang) in aliases.unwrap_or_default() {
+        put(&store(transaction, LANGUAGE_ALIASES)?, &LanguageAliasRecord { alias, lang }).await?;
+    }
+    let recent: Option<Vec<RecentOpen>> = legacy_table(&legacy, RECENT_OPENS).await?;
+    for recent in recent.unwrap_or_default() {
+        put(&store(transaction, RECENT_OPENS)?, &recent).await?;
+    }
+
+    transaction.db().delete_object_store(LEGACY_TABLES_STORE).map_err(js_error)
+}
+
+async fn open_database() -> Result<IdbDatabase> {
+    let factory = web_sys::window()
+        .context("No browser window")?
+        .indexed_db()
+        .map_err(js_error)?
+        .context("IndexedDB is not available")?;
+    let open = factory
+        .open_with_u32(DATABASE_NAME, DATABASE_VERSION)
+        .map_err(js_error)?;
+    let upgrade_request = open.clone();
+    let on_upgrade = Closure::once_into_js(move |_: web_sys::Event| {
+        let (Ok(database), Some(transaction)) = (upgrade_request.result(), upgrade_request.transaction()) else {
+            return;
+        };
+        let database: IdbDatabase = database.unchecked_into();
+        if let Err(e) = create_stores(&database) {
+            log::error!("Failed to create IndexedDB stores: {:#}", js_error(e));
+            let _ = transaction.abort();
+            return;
+        }
+        if database.object_store_names().contains(LEGACY_TABLES_STORE) {
+            // The upgrade, and with it the open request, only completes once this is done
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = migrate_legacy_tables(&transaction).await {
+                    log::error!("Failed to migrate IndexedDB tables: {:#}", e);
+                    let _ = transaction.abort();
+                }
+            });
+        }
+    });
+    open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+    let database: IdbDatabase = request_done(&open).await?.unchecked_into();
+
+    // A tab opening a newer version waits until every other tab lets go of this one
+    let closing = database.clone();
+    let on_version_change = Closure::once_into_js(move |_: web_sys::Event| closing.close());
+    database.set_onversionchange(Some(on_version_change.unchecked_ref()));
+    Ok(database)
+}
+
+async fn database() -> Result<IdbDatabase> {
+    let opening = DATABASE.with(|database| {
+        database
+            .get_or_init(|| {
+                async { open_database().await.map_err(|e| format!("{:#}", e)) }
+                    .boxed_local()
+                    .shared()
+            })
+            .clone()
+    });
+    opening
+        .await
+        .map_err(|e| anyhow!(e).context("Failed to open database"))
+}
+
+// Every read and write of one call goes through a single transaction, so other tabs
+// see either all of its changes or none.
+async fn transaction(stores: &[&str], mode: IdbTransactionMode) -> Result<IdbTransaction> {
+    let names: js_sys::Array = stores.iter().map(|name| JsValue::from_str(name)).collect();
+    database()
+        .await?
+        .transaction_with_str_sequence_and_mode(&names, mode)
+        .map_err(js_error)
+}
+
+// Waits for a read-write transaction to commit, or rolls it back when `result` failed.
+async fn finish<R>(transaction: IdbTransaction, result: Result<R>) -> Result<R> {
+    match result {
+        Ok(value) => transaction_done(&transaction).await.map(|()| value),
+        Err(e) => {
+            let _ = transaction.abort();
+            Err(e)
+        }
+    }
+}
+
+fn store(transaction: &IdbTransaction, name: &str) -> Result<IdbObjectStore> {
+    transaction.object_store(name).map_err(js_error)
+}
+
+async fn get<T: DeserializeOwned>(store: &IdbObjectStore, key: impl Into<JsValue>) -> Result<Option<T>> {
+    let value = request_done(&store.get(&key.into()).map_err(js_error)?).await?;
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    from_js(value).map(Some)
+}
+
+async fn get_all<T: DeserializeOwned>(request: Result<IdbRequest, JsValue>) -> Result<Vec<T>> {
+    let values: js_sys::Array = request_done(&request.map_err(js_error)?).await?.unchecked_into();
+    values.iter().map(from_js).collect()
+}
+
+async fn get_all_ids(request: Result<IdbRequest, JsValue>) -> Result<Vec<i32>> {
+    let keys: js_sys::Array = request_done(&request.map_err(js_error)?).await?.unchecked_into();
+    keys.iter()
+        .map(|key| key.as_f64().map(|id| id as i32).context("Expected a numeric key"))
+        .collect()
+}
+
+async fn put<T: Serialize>(store: &IdbObjectStore, record: &T) -> Result<()> {
+    request_done(&store.put(&to_js(record)?).map_err(js_error)?).await?;
+    Ok(())
+}
+
+// Adds a record under the next id of the store's key generator, ignoring its own `id`.
+async fn add<T: Serialize>(store: &IdbObjectStore, record: &T) -> Result<i32> {
+    let record = to_js(record)?;
+    js_sys::Reflect::delete_property(record.unchecked_ref(), &JsValue::from_str("id")).map_err(js_error)?;
+    let key = request_done(&store.add(&record).map_err(js_error)?).await?;
+    key.as_f64().map(|id| id as i32).context("Expected a numeric key")
+}
+
+async fn delete(store: &IdbObjectStore, key: impl Into<JsValue>) -> Result<()> {
+    request_done(&store.delete(&key.into()).map_err(js_error)?).await?;
+    Ok(())
+}
+
+// The SQLite build keeps only these front matter fields, in their own columns.
+fn stored_meta(content: &str) -> NoteMeta {
+    let meta = note_meta(content).unwrap_or_default();
+    stored_fields(meta)
+}
+
+fn stored_fields(meta: NoteMeta) -> NoteMeta {
+    NoteMeta {
+        title: meta.title,
+        tags: meta.tags,
+        language: meta.language,
+        aliases: meta.aliases,
+        theme: meta.theme,
+        ..Default::default()
+    }
+}
+
+fn label_of(note: &Note) -> NoteLabel {
+    NoteLabel {
+        id: note.id,
+        folder_id: note.folder_id,
+        label: note_label(Some(&note.meta), &note.content),
+    }
+}
+
+async fn insert_note(transaction: &IdbTransaction, mut note: Note) -> Result<i32> {
+    note.id = add(&store(transaction, NOTES)?, &note).await?;
+    put(&store(transaction, NOTE_LABELS)?, &label_of(&note)).await?;
+    Ok(note.id)
+}
+
+pub async fn save_folder(
+    name: String,
+    date_created: String,
+    parent_id: Option<i32>,
+) -> Result<i32> {
+    let transaction = transaction(&[FOLDERS], IdbTransactionMode::Readwrite).await?;
+    let folder = Folder {
+        id: 0,
+        name,
+        date_created,
+        parent_id,
+        children: Vec::new(),
+    };
+    let result = add(&store(&transaction, FOLDERS)?, &folder).await;
+    finish(transaction, result).await.context("Failed to save folder")
+}
+
+pub async fn update_folder_name(id: i32, new_name: String) -> Result<()> {
+    let transaction = transaction(&[FOLDERS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let folders = store(&transaction, FOLDERS)?;
+        if let Some(mut folder) = get::<Folder>(&folders, id).await? {
+            folder.name = new_name;
+            put(&folders, &folder).await?;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to update folder name")
+}
+
+/// Deletes the folder with its subfolders and their notes.
+pub async fn delete_folder_recursive(id: i32) -> Result<()> {
+    let transaction = transaction(&[FOLDERS, NOTES, NOTE_LABELS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let folders = store(&transaction, FOLDERS)?;
+        let notes = store(&transaction, NOTES)?;
+        let labels = store(&transaction, NOTE_LABELS)?;
+        let by_parent = folders.index("parent_id").map_err(js_error)?;
+        let by_folder = notes.index("folder_id").map_err(js_error)?;
+
+        let mut removed = vec![id];
+        let mut index = 0;
+        while index < removed.len() {
+            let folder_id = removed[index];
+            let children = get_all_ids(by_parent.get_all_keys_with_key(&folder_id.into())).await?;
+            removed.extend(children.into_iter().filter(|child| !removed.contains(child)).collect::<Vec<_>>());
+            for note_id in get_all_ids(by_folder.get_all_keys_with_key(&folder_id.into())).await? {
+                delete(&notes, note_id).await?;
+                delete(&labels, note_id).await?;
+            }
+            delete(&folders, folder_id).await?;
+            index += 1;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to delete folder")
+}
+
+pub async fn get_folders() -> Result<Vec<Folder>> {
+    let transaction = transaction(&[FOLDERS], IdbTransactionMode::Readonly).await?;
+    let folders = get_all(store(&transaction, FOLDERS)?.get_all()).await?;
+    Ok(build_folder_tree(folders))
+}
+
+pub async fn save_note(
+    content: String,
+    date_created: String,
+    folder_id: i32,
+) -> Result<i32> {
+    let meta = stored_meta(&content);
+    let transaction = transaction(&[NOTES, NOTE_LABELS], IdbTransactionMode::Readwrite).await?;
+    let note = Note {
+        id: 0,
+        content,
+        date_created,
+        folder_id,
+        meta,
+    };
+    let result = insert_note(&transaction, note).await;
+    finish(transaction, result).await.context("Failed to save note")
+}
+
+pub async fn get_notes(folder_id: i32) -> Result<Vec<Note>> {
+    let transaction = transaction(&[NOTES], IdbTransactionMode::Readonly).await?;
+    let by_folder = store(&transaction, NOTES)?.index("folder_id").map_err(js_error)?;
+    let mut notes: Vec<Note> = get_all(by_folder.get_all_with_key(&folder_id.into())).await?;
+    notes.sort_by(|a, b| b.date_created.cmp(&a.date_created));
+    Ok(notes)
+}
+
+pub async fn get_note(id: i32) -> Result<Note> {
+    let transaction = transaction(&[NOTES], IdbTransactionMode::Readonly).await?;
+    get(&store(&transaction, NOTES)?, id)
+        .await?
+        .with_context(|| format!("Failed to get note {}", id))
+}
+
+pub async fn update_note(
+    id: i32,
+    content: String,
+    date_updated: String,
+) -> Result<()> {
+    // A front matter typo keeps the metadata stored from the last valid version
+    let meta = note_meta(&content).ok().map(stored_fields);
+    let transaction = transaction(&[NOTES, NOTE_LABELS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let notes = store(&transaction, NOTES)?;
+        if let Some(mut note) = get::<Note>(&notes, id).await? {
+            note.content = content;
+            note.date_created = date_updated;
+            if let Some(meta) = meta {
+                note.meta = meta;
+            }
+            put(&notes, &note).await?;
+            put(&store(&transaction, NOTE_LABELS)?, &label_of(&note)).await?;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to update note")
+}
+
+pub async fn delete_note(id: i32) -> Result<()> {
+    let transaction = transaction(&[NOTES, NOTE_LABELS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        delete(&store(&transaction, NOTES)?, id).await?;
+        delete(&store(&transaction, NOTE_LABELS)?, id).await
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to delete note")
+}
+
+pub async fn get_folder_name(folder_id: i32) -> Result<String> {
+    let transaction = transaction(&[FOLDERS], IdbTransactionMode::Readonly).await?;
+    let folder: Option<Folder> = get(&store(&transaction, FOLDERS)?, folder_id).await?;
+    folder.map(|folder| folder.name).context("Failed to get folder name")
+}
+
+/// Notes containing `query`, ignoring ASCII case like SQLite's `LIKE`.
+pub async fn search_notes(query: String) -> Result<Vec<NoteSearchHit>> {
+    let needle = query.to_ascii_lowercase();
+    let transaction = transaction(&[NOTES, FOLDERS], IdbTransactionMode::Readonly).await?;
+    let folders: Vec<Folder> = get_all(store(&transaction, FOLDERS)?.get_all()).await?;
+    let notes: Vec<Note> = get_all(store(&transaction, NOTES)?.get_all()).await?;
+
+    let mut matches: Vec<&Note> = notes
+        .iter()
+        .filter(|note| note.content.to_ascii_lowercase().contains(&needle))
+        .collect();
+    matches.sort_by(|a, b| b.date_created.cmp(&a.date_created));
+    Ok(matches
+        .into_iter()
+        .filter_map(|note| {
+            let folder = folders.iter().find(|folder| folder.id == note.folder_id)?;
+            Some(NoteSearchHit {
+                note_id: note.id,
+                folder_id: note.folder_id,
+                folder_name: folder.name.clone(),
+                title: note.meta.title.clone(),
+                snippet: search_snippet(&note.content, &query),
+            })
+        })
+        .take(50)
+        .collect())
+}
+
+pub async fn load_keybindings() -> Result<HashMap<String, String>> {
+    let transaction = transaction(&[KEYBINDINGS], IdbTransactionMode::Readonly).await?;
+    let records: Vec<KeybindingRecord> = get_all(store(&transaction, KEYBINDINGS)?.get_all()).await?;
+    Ok(records.into_iter().map(|record| (record.command, record.combo)).collect())
+}
+
+/// Stores a remapped binding; an empty combo leaves the command unbound.
+pub async fn save_keybinding(command: String, combo: String) -> Result<()> {
+    let transaction = transaction(&[KEYBINDINGS], IdbTransactionMode::Readwrite).await?;
+    let result = put(&store(&transaction, KEYBINDINGS)?, &KeybindingRecord { command, combo }).await;
+    finish(transaction, result).await.context("Failed to save keybinding")
+}
+
+pub async fn reset_keybinding(command: String) -> Result<()> {
+    let transaction = transaction(&[KEYBINDINGS], IdbTransactionMode::Readwrite).await?;
+    let result = delete(&store(&transaction, KEYBINDINGS)?, command).await;
+    finish(transaction, result).await.context("Failed to reset keybinding")
+}
+
+pub async fn record_opened(folder_id: i32, note_id: Option<i32>) -> Result<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    let transaction = transaction(&[RECENT_OPENS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let recent_opens = store(&transaction, RECENT_OPENS)?;
+        let opened = std::iter::once(("folder", folder_id)).chain(note_id.map(|id| ("note", id)));
+        for (kind, item_id) in opened {
+            let recent = RecentOpen {
+                kind: kind.to_string(),
+                item_id,
+                opened_at: now.clone(),
+            };
+            put(&recent_opens, &recent).await?;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to record opened note")
+}
+
+fn load_folder_paths(folders: &[Folder]) -> HashMap<i32, String> {
+    let folders = folders
+        .iter()
+        .map(|folder| (folder.id, (folder.name.clone(), folder.parent_id)))
+        .collect();
+    folder_paths(&folders)
+}
+
+/// Every folder and note, most recently opened first, then alphabetically.
+pub async fn get_quick_switch_items() -> Result<Vec<QuickSwitchItem>> {
+    let transaction = transaction(&[FOLDERS, NOTE_LABELS, RECENT_OPENS], IdbTransactionMode::Readonly).await?;
+    let folders: Vec<Folder> = get_all(store(&transaction, FOLDERS)?.get_all()).await?;
+    let labels: Vec<NoteLabel> = get_all(store(&transaction, NOTE_LABELS)?.get_all()).await?;
+    let recent_opens: Vec<RecentOpen> = get_all(store(&transaction, RECENT_OPENS)?.get_all()).await?;
+
+    let paths = load_folder_paths(&folders);
+    let recent = recent_opens
+        .into_iter()
+        .map(|recent| ((recent.kind, recent.item_id), recent.opened_at))
+        .collect();
+    let notes = labels.into_iter().map(|note| (note.id, note.folder_id, note.label));
+    Ok(quick_switch_items(&paths, &recent, notes))
+}
+
+/// Folder whose path (as shown by the quick switcher, e.g. "Docker/Compose") matches.
+pub async fn find_folder_by_path(path: String) -> Result<Option<i32>> {
+    let path = path.trim_matches('/');
+    let transaction = transaction(&[FOLDERS], IdbTransactionMode::Readonly).await?;
+    let folders: Vec<Folder> = get_all(store(&transaction, FOLDERS)?.get_all()).await?;
+    Ok(load_folder_paths(&folders)
+        .into_iter()
+        .find(|(_, folder_path)| folder_path == path)
+        .map(|(id, _)| id))
+}
+
+/// Saves a note under `folder_path`, creating any missing folders along the way.
+pub async fn add_note_at_path(folder_path: String, content: String) -> Result<(i32, i32)> {
+    let now = chrono::Local::now().to_rfc3339();
+    let meta = stored_meta(&content);
+    let names: Vec<String> = folder_path
+        .split('/')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    if names.is_empty() {
+        return Err(anyhow!("Folder path is empty"));
+    }
+
+    let transaction = transaction(&[FOLDERS, NOTES, NOTE_LABELS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let folders_store = store(&transaction, FOLDERS)?;
+        let mut folders: Vec<Folder> = get_all(folders_store.get_all()).await?;
+        let mut parent_id: Option<i32> = None;
+        for name in names {
+            let existing = folders
+                .iter()
+                .filter(|folder| folder.name == name && folder.parent_id == parent_id)
+                .map(|folder| folder.id)
+                .min();
+            let folder_id = match existing {
+                Some(id) => id,
+                None => {
+                    let mut folder = Folder {
+                        id: 0,
+                        name,
+                        date_created: now.clone(),
+                        parent_id,
+                        children: Vec::new(),
+                    };
+                    folder.id = add(&folders_store, &folder).await?;
+                    let id = folder.id;
+                    folders.push(folder);
+                    id
+                }
+            };
+            parent_id = Some(folder_id);
+        }
+        let folder_id = parent_id.context("Folder path is empty")?;
+
+        let note = Note {
+            id: 0,
+            content,
+            date_created: now,
+            folder_id,
+            meta,
+        };
+        Ok((folder_id, insert_note(&transaction, note).await?))
+    }
+    .await;
+    finish(transaction, result).await
+}
+
+pub async fn load_language_aliases() -> Result<BTreeMap<String, String>> {
+    let transaction = transaction(&[LANGUAGE_ALIASES], IdbTransactionMode::Readonly).await?;
+    let records: Vec<LanguageAliasRecord> = get_all(store(&transaction, LANGUAGE_ALIASES)?.get_all()).await?;
+    Ok(records.into_iter().map(|record| (record.alias, record.lang)).collect())
+}
+
+pub async fn save_language_alias(alias: String, lang: String) -> Result<()> {
+    let transaction = transaction(&[LANGUAGE_ALIASES], IdbTransactionMode::Readwrite).await?;
+    let result = put(&store(&transaction, LANGUAGE_ALIASES)?, &LanguageAliasRecord { alias, lang }).await;
+    finish(transaction, result).await.context("Failed to save language alias")
+}
+
+pub async fn delete_language_alias(alias: String) -> Result<()> {
+    let transaction = transaction(&[LANGUAGE_ALIASES], IdbTransactionMode::Readwrite).await?;
+    let result = delete(&store(&transaction, LANGUAGE_ALIASES)?, alias).await;
+    finish(transaction, result).await.context("Failed to delete language alias")
+}
+
+/// Stored settings over the defaults. A value that no longer parses falls back to its default.
+pub async fn load_settings() -> Result<Settings> {
+    let transaction = transaction(&[SETTINGS], IdbTransactionMode::Readonly).await?;
+    let records: Vec<SettingRecord> = get_all(store(&transaction, SETTINGS)?.get_all()).await?;
+    settings_from_stored(records.into_iter().map(|record| (record.key, record.value)).collect())
+}
+
+pub async fn save_settings(settings: Settings) -> Result<()> {
+    let values = settings_map(&settings)?;
+    let transaction = transaction(&[SETTINGS], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let store = store(&transaction, SETTINGS)?;
+        for (key, value) in values {
+            put(&store, &SettingRecord { key, value: value.to_string() }).await?;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to save settings")
+}
+
+pub async fn get_palettes() -> Result<Vec<Palette>> {
+    let transaction = transaction(&[PALETTES], IdbTransactionMode::Readonly).await?;
+    let mut palettes: Vec<Palette> = get_all(store(&transaction, PALETTES)?.get_all()).await?;
+    palettes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(palettes)
+}
+
+pub async fn get_palette(id: i32) -> Result<Palette> {
+    let transaction = transaction(&[PALETTES], IdbTransactionMode::Readonly).await?;
+    get(&store(&transaction, PALETTES)?, id)
+        .await?
+        .context("Failed to load palette")
+}
+
+pub async fn save_palette(name: String, colors: BTreeMap<String, String>) -> Result<i32> {
+    let transaction = transaction(&[PALETTES], IdbTransactionMode::Readwrite).await?;
+    let result = add(&store(&transaction, PALETTES)?, &Palette { id: 0, name, colors }).await;
+    finish(transaction, result).await.context("Failed to save palette")
+}
+
+pub async fn update_palette(palette: Palette) -> Result<()> {
+    let transaction = transaction(&[PALETTES], IdbTransactionMode::Readwrite).await?;
+    let result = async {
+        let palettes = store(&transaction, PALETTES)?;
+        if get::<Palette>(&palettes, palette.id).await?.is_some() {
+            put(&palettes, &palette).await?;
+        }
+        Ok(())
+    }
+    .await;
+    finish(transaction, result).await.context("Failed to update palette")
+}
+
+pub async fn delete_palette(id: i32) -> Result<()> {
+    let transaction = transaction(&[PALETTES], IdbTransactionMode::Readwrite).await?;
+    let result = delete(&store(&transaction, PALETTES)?, id).await;
+    finish(transaction, result).await.context("Failed to delete palette")
+}
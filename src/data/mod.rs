@@ -1,5 +1,19 @@
-mod theme_db;
+mod models;
+mod note_export;
+mod palette;
+#[cfg(not(feature = "web"))]
 mod folder_sqlite_db;
+#[cfg(not(feature = "web"))]
+mod settings_db;
+#[cfg(feature = "web")]
+mod indexed_db;
 
-pub use theme_db::*;
-pub use folder_sqlite_db::*;
\ No newline at end of file
+pub use models::*;
+pub use note_export::*;
+pub use palette::*;
+#[cfg(not(feature = "web"))]
+pub use folder_sqlite_db::*;
+#[cfg(not(feature = "web"))]
+pub use settings_db::*;
+#[cfg(feature = "web")]
+pub use indexed_db::*;
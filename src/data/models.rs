@@ -0,0 +1,291 @@
+use crate::i18n::Locale;
+use crate::syntax::{note_title, NoteMeta, SanitizerPolicy, DEFAULT_LIGHT_THEME, DEFAULT_THEME};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Note {
+    pub id: i32,
+    pub content: String,
+    pub date_created: String,
+    pub folder_id: i32,
+    pub meta: NoteMeta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NoteSearchHit {
+    pub note_id: i32,
+    pub folder_id: i32,
+    pub folder_name: String,
+    pub title: Option<String>,
+    pub snippet: String,
+}
+
+/// A folder or note offered by the quick switcher.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuickSwitchItem {
+    pub folder_id: i32,
+    pub note_id: Option<i32>,
+    pub label: String,
+    pub folder_path: String,
+    pub opened_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Folder {
+    pub id: i32,
+    pub name: String,
+    pub date_created: String,
+    pub parent_id: Option<i32>,
+    pub children: Vec<Folder>,
+}
+
+fn assign_children_recursively(folder: &mut Folder, children_map: &mut HashMap<i32, Vec<Folder>>) {
+    if let Some(mut children) = children_map.remove(&folder.id) {
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for mut child in children {
+            assign_children_recursively(&mut child, children_map);
+            folder.children.push(child);
+        }
+    }
+}
+
+// Nests flat folder rows under their parents, each level sorted by name.
+pub(crate) fn build_folder_tree(folder_rows: Vec<Folder>) -> Vec<Folder> {
+    let mut root_folders: Vec<Folder> = Vec::new();
+    let mut temp_children_map: HashMap<i32, Vec<Folder>> = HashMap::new();
+
+    for folder in folder_rows {
+        if let Some(parent_id) = folder.parent_id {
+            temp_children_map.entry(parent_id).or_default().push(folder);
+        } else {
+            root_folders.push(folder);
+        }
+    }
+
+    root_folders.sort_by(|a, b| a.name.cmp(&b.name));
+    for folder in root_folders.iter_mut() {
+        assign_children_recursively(folder, &mut temp_children_map);
+    }
+
+    // Attach any orphaned children
+    for (_, orphaned) in temp_children_map {
+        root_folders.extend(orphaned);
+    }
+    root_folders
+}
+
+// Folder paths such as "Work/Projects/Runorna", from (name, parent id) keyed by folder id.
+pub(crate) fn folder_paths(folders: &HashMap<i32, (String, Option<i32>)>) -> HashMap<i32, String> {
+    folders
+        .iter()
+        .map(|(&id, (name, parent_id))| {
+            let mut path = name.clone();
+            let mut parent_id = *parent_id;
+            // Bounded so a corrupted parent cycle can't loop forever
+            for _ in 0..folders.len() {
+                let Some((parent_name, grandparent_id)) = parent_id.and_then(|p| folders.get(&p)) else {
+                    break;
+                };
+                path = format!("{}/{}", parent_name, path);
+                parent_id = *grandparent_id;
+            }
+            (id, path)
+        })
+        .collect()
+}
+
+/// A note as the quick switcher needs it: id, folder id and label.
+pub(crate) type QuickSwitchNote = (i32, i32, String);
+
+// What the quick switcher lists a note as, stored so listing notes never loads their content.
+pub(crate) fn note_label(meta: Option<&NoteMeta>, content: &str) -> String {
+    meta.and_then(|meta| meta.title.clone())
+        .unwrap_or_else(|| note_title(content))
+}
+
+// Folders and notes, most recently opened first, then alphabetically.
+pub(crate) fn quick_switch_items(
+    paths: &HashMap<i32, String>,
+    recent: &HashMap<(String, i32), String>,
+    notes: impl IntoIterator<Item = QuickSwitchNote>,
+) -> Vec<QuickSwitchItem> {
+    let mut items: Vec<QuickSwitchItem> = paths
+        .iter()
+        .map(|(&folder_id, path)| QuickSwitchItem {
+            folder_id,
+            note_id: None,
+            label: path.clone(),
+            folder_path: path.clone(),
+            opened_at: recent.get(&("folder".to_string(), folder_id)).cloned(),
+        })
+        .collect();
+
+    items.extend(notes.into_iter().map(|(note_id, folder_id, label)| QuickSwitchItem {
+        folder_id,
+        note_id: Some(note_id),
+        label,
+        folder_path: paths.get(&folder_id).cloned().unwrap_or_default(),
+        opened_at: recent.get(&("note".to_string(), note_id)).cloned(),
+    }));
+
+    items.sort_by(|a, b| b.opened_at.cmp(&a.opened_at).then_with(|| a.label.cmp(&b.label)));
+    items
+}
+
+// The line around the first case-insensitive match of `query`, cut to a window on long lines.
+pub(crate) fn search_snippet(content: &str, query: &str) -> String {
+    let position = content
+        .to_ascii_lowercase()
+        .find(&query.to_ascii_lowercase())
+        .unwrap_or_default();
+    let line_start = content[..position].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[position..].find('\n').map_or(content.len(), |i| position + i);
+    let line = &content[line_start..line_end];
+
+    let offset = position - line_start;
+    let start = (0..=offset.saturating_sub(60))
+        .rev()
+        .find(|&i| line.is_char_boundary(i))
+        .unwrap_or(0);
+    let end = ((offset + 100).min(line.len())..=line.len())
+        .find(|&i| line.is_char_boundary(i))
+        .unwrap_or(line.len());
+    line[start..end].trim().to_string()
+}
+
+/// Everything the Settings page configures, stored one field per row in the `settings` table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub locale: Locale,
+    pub dark_mode: bool,
+    /// Syntax theme for code blocks in light mode.
+    pub light_syntax_theme: String,
+    /// Syntax theme for code blocks in dark mode.
+    pub dark_syntax_theme: String,
+    pub editor_font: String,
+    pub editor_font_size: u32,
+    pub tab_width: u32,
+    pub word_wrap: bool,
+    /// Seconds between automatic saves of a changed note, 0 turns autosave off.
+    pub autosave_interval: u32,
+    /// Custom color palette replacing the built-in light and dark colors.
+    pub palette_id: Option<i32>,
+    /// How much raw HTML in notes is kept in the preview and exports.
+    pub sanitizer_policy: SanitizerPolicy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            locale: Locale::from_system(),
+            dark_mode: false,
+            light_syntax_theme: DEFAULT_LIGHT_THEME.to_string(),
+            dark_syntax_theme: DEFAULT_THEME.to_string(),
+            editor_font: "monospace".to_string(),
+            editor_font_size: 14,
+            tab_width: 4,
+            word_wrap: true,
+            autosave_interval: 0,
+            palette_id: None,
+            sanitizer_policy: SanitizerPolicy::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// The syntax theme paired with the current light/dark mode.
+    pub fn syntax_theme(&self) -> &str {
+        if self.dark_mode {
+            &self.dark_syntax_theme
+        } else {
+            &self.light_syntax_theme
+        }
+    }
+
+    /// Sets the syntax theme for the current mode only.
+    pub fn set_syntax_theme(&mut self, name: String) {
+        if self.dark_mode {
+            self.dark_syntax_theme = name;
+        } else {
+            self.light_syntax_theme = name;
+        }
+    }
+
+    /// `editor_font` as a CSS `font-family` value: generic families stay keywords, every
+    /// other name becomes a quoted string, so the setting can't add declarations to a style.
+    pub fn editor_font_family(&self) -> String {
+        let families: Vec<String> = self
+            .editor_font
+            .split(',')
+            .map(|family| family.trim().trim_matches(['"', '\'']).trim())
+            .filter(|family| !family.is_empty())
+            .map(|family| {
+                if GENERIC_FONT_FAMILIES.contains(&family.to_ascii_lowercase().as_str()) {
+                    family.to_ascii_lowercase()
+                } else {
+                    let escaped: String = family
+                        .chars()
+                        .filter(|c| !c.is_control())
+                        .flat_map(|c| match c {
+                            '"' | '\\' => vec!['\\', c],
+                            c => vec![c],
+                        })
+                        .collect();
+                    format!("\"{}\"", escaped)
+                }
+            })
+            .collect();
+        if families.is_empty() {
+            "monospace".to_string()
+        } else {
+            families.join(", ")
+        }
+    }
+}
+
+const GENERIC_FONT_FAMILIES: &[&str] = &[
+    "monospace", "serif", "sans-serif", "system-ui", "cursive", "fantasy", "ui-monospace",
+    "ui-serif", "ui-sans-serif", "ui-rounded", "math", "emoji",
+];
+
+pub(crate) fn settings_map(settings: &Settings) -> Result<Map<String, Value>> {
+    match serde_json::to_value(settings).context("Failed to serialize settings")? {
+        Value::Object(map) => Ok(map),
+        _ => unreachable!("Settings serializes to an object"),
+    }
+}
+
+// Stored key/JSON value rows over the defaults. A value that no longer parses falls back
+// to its default.
+pub(crate) fn settings_from_stored(stored: Vec<(String, String)>) -> Result<Settings> {
+    // Before light and dark got their own theme there was a single one, picked
+    // against the dark code block background by default
+    let has_dark_theme = stored.iter().any(|(key, _)| key == "dark_syntax_theme");
+    let mut map = settings_map(&Settings::default())?;
+    for (mut key, value) in stored {
+        if key == "syntax_theme" && !has_dark_theme {
+            key = "dark_syntax_theme".to_string();
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&value) else {
+            continue;
+        };
+        let mut candidate = map.clone();
+        candidate.insert(key, value);
+        if serde_json::from_value::<Settings>(Value::Object(candidate.clone())).is_ok() {
+            map = candidate;
+        }
+    }
+    serde_json::from_value(Value::Object(map)).context("Failed to read settings")
+}
+
+/// A user-defined set of values for the CSS color variables, keyed without the leading `--`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub id: i32,
+    pub name: String,
+    pub colors: BTreeMap<String, String>,
+}
@@ -0,0 +1,80 @@
+use crate::data::{get_folder_name, get_folders, get_notes, Folder};
+use anyhow::{Context, Result};
+use crate::syntax::{folder_index_html, note_title, note_to_standalone_html, slugify, SanitizerPolicy};
+use std::path::{Path, PathBuf};
+
+pub const EXPORTS_DIR: &str = "exports";
+
+fn find_folder(folders: &[Folder], folder_id: i32) -> Option<&Folder> {
+    folders.iter().find_map(|folder| {
+        if folder.id == folder_id {
+            Some(folder)
+        } else {
+            find_folder(&folder.children, folder_id)
+        }
+    })
+}
+
+// The folder itself followed by its subfolders, depth first.
+fn collect_folders(folder: &Folder, out: &mut Vec<(i32, String)>) {
+    out.push((folder.id, folder.name.clone()));
+    for child in &folder.children {
+        collect_folders(child, out);
+    }
+}
+
+/// Writes the note as a standalone `.html` file into `EXPORTS_DIR` and returns its path.
+/// The file is named after the title and the id, as in folder exports, so notes sharing a
+/// title don't overwrite each other; a note that was never saved has no id yet.
+pub async fn export_note_html(
+    markdown: String,
+    note_id: Option<i32>,
+    theme_name: String,
+    policy: SanitizerPolicy,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(EXPORTS_DIR).context("Failed to create exports directory")?;
+    let suffix = note_id.map_or_else(|| "draft".to_string(), |id| id.to_string());
+    let file_name = format!("{}-{}.html", slugify(&note_title(&markdown)), suffix);
+    let path = Path::new(EXPORTS_DIR).join(file_name);
+    std::fs::write(&path, note_to_standalone_html(&markdown, &theme_name, policy))
+        .context("Failed to write exported note")?;
+    Ok(path)
+}
+
+/// Exports every note of the folder and its subfolders, plus an `index.html`
+/// linking to them, into a directory of `EXPORTS_DIR`. Returns the index path.
+pub async fn export_folder_html(
+    folder_id: i32,
+    theme_name: String,
+    policy: SanitizerPolicy,
+) -> Result<PathBuf> {
+    let folder_name = get_folder_name(folder_id).await?;
+    let folders = get_folders().await?;
+    let mut export_folders = Vec::new();
+    match find_folder(&folders, folder_id) {
+        Some(folder) => collect_folders(folder, &mut export_folders),
+        None => export_folders.push((folder_id, folder_name.clone())),
+    }
+
+    let dir = Path::new(EXPORTS_DIR).join(slugify(&folder_name));
+    std::fs::create_dir_all(&dir).context("Failed to create folder export directory")?;
+
+    let mut sections = Vec::new();
+    for (id, name) in export_folders {
+        let mut entries = Vec::new();
+        for note in get_notes(id).await? {
+            let title = note_title(&note.content);
+            let file_name = format!("{}-{}.html", slugify(&title), note.id);
+            let html = note_to_standalone_html(&note.content, &theme_name, policy);
+            std::fs::write(dir.join(&file_name), html)
+                .with_context(|| format!("Failed to write exported note {}", note.id))?;
+            entries.push((title, file_name));
+        }
+        sections.push((name, entries));
+    }
+
+    let index = dir.join("index.html");
+    std::fs::write(&index, folder_index_html(&folder_name, &sections))
+        .context("Failed to write export index")?;
+    Ok(index)
+}
@@ -0,0 +1,151 @@
+use crate::data::Palette;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Every CSS variable a palette assigns, with its value in main.css and dark.css.
+pub const PALETTE_VARIABLES: &[(&str, &str, &str)] = &[
+    ("primary", "#4c5c92", "#b6c4ff"),
+    ("surface-tint", "#4c5c92", "#b6c4ff"),
+    ("on-primary", "#ffffff", "#1d2d61"),
+    ("primary-container", "#dce1ff", "#344479"),
+    ("on-primary-container", "#344479", "#dce1ff"),
+    ("secondary", "#445e91", "#adc6ff"),
+    ("on-secondary", "#ffffff", "#112f60"),
+    ("secondary-container", "#d8e2ff", "#2b4678"),
+    ("on-secondary-container", "#2b4678", "#d8e2ff"),
+    ("tertiary", "#34618d", "#9fcafc"),
+    ("on-tertiary", "#ffffff", "#003257"),
+    ("tertiary-container", "#d0e4ff", "#164974"),
+    ("on-tertiary-container", "#164974", "#d0e4ff"),
+    ("error", "#904a43", "#ffb4ab"),
+    ("on-error", "#ffffff", "#561e19"),
+    ("error-container", "#ffdad6", "#73332d"),
+    ("on-error-container", "#73332d", "#ffdad6"),
+    ("background", "#faf8ff", "#121318"),
+    ("on-background", "#1a1b21", "#e3e1e9"),
+    ("surface", "#faf8ff", "#121318"),
+    ("on-surface", "#1a1b21", "#e3e2e9"),
+    ("surface-variant", "#e2e1ec", "#45464f"),
+    ("on-surface-variant", "#45464f", "#c6c6d0"),
+    ("outline", "#767680", "#8f909a"),
+    ("outline-variant", "#c6c6d0", "#45464f"),
+    ("shadow", "#000000", "#000000"),
+    ("scrim", "#000000", "#000000"),
+    ("inverse-surface", "#2f3036", "#e3e2e9"),
+    ("inverse-on-surface", "#f1f0f7", "#2f3036"),
+    ("inverse-primary", "#b6c4ff", "#4c5c92"),
+    ("primary-fixed", "#dce1ff", "#dce1ff"),
+    ("on-primary-fixed", "#03174b", "#03174b"),
+    ("primary-fixed-dim", "#b6c4ff", "#b6c4ff"),
+    ("on-primary-fixed-variant", "#344479", "#344479"),
+    ("secondary-fixed", "#d8e2ff", "#d8e2ff"),
+    ("on-secondary-fixed", "#001a42", "#001a42"),
+    ("secondary-fixed-dim", "#adc6ff", "#adc6ff"),
+    ("on-secondary-fixed-variant", "#2b4678", "#2b4678"),
+    ("tertiary-fixed", "#d0e4ff", "#d0e4ff"),
+    ("on-tertiary-fixed", "#001d35", "#001d35"),
+    ("tertiary-fixed-dim", "#9fcafc", "#9fcafc"),
+    ("on-tertiary-fixed-variant", "#164974", "#164974"),
+    ("surface-dim", "#dad9e0", "#121318"),
+    ("surface-bright", "#faf8ff", "#38393f"),
+    ("surface-container-lowest", "#ffffff", "#0d0e13"),
+    ("surface-container-low", "#f4f3fa", "#1a1b21"),
+    ("surface-container", "#eeedf4", "#1e1f25"),
+    ("surface-container-high", "#e8e7ef", "#292a2f"),
+    ("surface-container-highest", "#e3e2e9", "#33343a"),
+];
+
+/// Text/background pairs the contrast checker measures, e.g. `on-primary` over `primary`.
+pub fn contrast_pairs() -> impl Iterator<Item = (&'static str, &'static str)> {
+    PALETTE_VARIABLES.iter().filter_map(|(name, _, _)| {
+        let background = name.strip_prefix("on-")?;
+        PALETTE_VARIABLES
+            .iter()
+            .find(|(candidate, _, _)| *candidate == background)
+            .map(|(background, _, _)| (*name, *background))
+    })
+}
+
+// WCAG AA for body text
+pub const MIN_CONTRAST: f64 = 4.5;
+
+/// Colors of the built-in light or dark palette, the starting point for a new one.
+pub fn builtin_colors(dark: bool) -> BTreeMap<String, String> {
+    PALETTE_VARIABLES
+        .iter()
+        .map(|(name, light, dark_value)| {
+            (name.to_string(), if dark { dark_value } else { light }.to_string())
+        })
+        .collect()
+}
+
+pub fn parse_hex_color(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |channel: u8| {
+        let c = channel as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG contrast ratio between two `#rrggbb` colors, from 1 to 21.
+pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f64> {
+    let a = relative_luminance(parse_hex_color(foreground)?);
+    let b = relative_luminance(parse_hex_color(background)?);
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+    Some((light + 0.05) / (dark + 0.05))
+}
+
+/// Stylesheet overriding the variables of main.css and dark.css.
+pub fn palette_stylesheet(colors: &BTreeMap<String, String>) -> String {
+    // More specific than their `:root`, so it wins regardless of link order
+    let mut css = String::from("html:root {\n");
+    for (name, _, _) in PALETTE_VARIABLES {
+        if let Some(value) = colors.get(*name).filter(|value| parse_hex_color(value).is_some()) {
+            css.push_str(&format!("    --{}: {};\n", name, value));
+        }
+    }
+    css.push('}');
+    css
+}
+
+/// The JSON a palette is shared as.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedPalette {
+    pub name: String,
+    pub colors: BTreeMap<String, String>,
+}
+
+impl From<&Palette> for SharedPalette {
+    fn from(palette: &Palette) -> Self {
+        SharedPalette {
+            name: palette.name.clone(),
+            colors: palette.colors.clone(),
+        }
+    }
+}
+
+/// Reads a shared palette, keeping only known variables with valid colors.
+pub fn parse_shared_palette(json: &str) -> anyhow::Result<SharedPalette> {
+    let mut shared: SharedPalette = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("Not a Runorna palette: {}", e))?;
+    shared.colors.retain(|name, value| {
+        PALETTE_VARIABLES.iter().any(|(known, _, _)| known == name) && parse_hex_color(value).is_some()
+    });
+    if shared.name.trim().is_empty() || shared.colors.is_empty() {
+        anyhow::bail!("The palette has no name or no valid colors");
+    }
+    Ok(shared)
+}
@@ -0,0 +1,131 @@
+use crate::data::{database_path, settings_from_stored, settings_map, Palette, Settings, DB};
+use anyhow::{Context, Result};
+use rusqlite::OptionalExtension;
+use std::collections::BTreeMap;
+
+// Moves the appearance state kept by older versions (theme.db and the
+// theme_preference table) into the settings table, once.
+pub(crate) fn migrate_legacy_settings(conn: &rusqlite::Connection) -> Result<()> {
+    let has_settings = conn.prepare("SELECT 1 FROM settings")?.exists([])?;
+    let has_preference = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'theme_preference'")?
+        .exists([])?;
+    let theme_db = database_path().with_file_name("theme.db");
+    if has_settings || (!has_preference && !theme_db.exists()) {
+        return Ok(());
+    }
+
+    let mut settings = Settings::default();
+    if has_preference {
+        let theme: Option<String> = conn
+            .query_row(
+                "SELECT theme_name FROM theme_preference ORDER BY date_created DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(theme) = theme {
+            settings.dark_syntax_theme = theme;
+        }
+    }
+    if theme_db.exists() {
+        let legacy = rusqlite::Connection::open(&theme_db).context("Failed to open theme.db")?;
+        let dark_mode: Option<bool> = legacy
+            .query_row("SELECT is_dark_mode FROM theme ORDER BY id DESC LIMIT 1", [], |row| row.get(0))
+            .optional()
+            .unwrap_or(None);
+        settings.dark_mode = dark_mode.unwrap_or(false);
+    }
+
+    write_settings(conn, &settings)?;
+    conn.execute_batch("DROP TABLE IF EXISTS theme_preference")
+        .context("Failed to drop theme_preference")?;
+    if theme_db.exists() {
+        std::fs::remove_file(&theme_db).context("Failed to remove theme.db")?;
+    }
+    Ok(())
+}
+
+fn write_settings(conn: &rusqlite::Connection, settings: &Settings) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )?;
+    for (key, value) in settings_map(settings)? {
+        stmt.execute((&key, value.to_string()))
+            .with_context(|| format!("Failed to save setting {}", key))?;
+    }
+    Ok(())
+}
+
+/// Stored settings over the defaults. A value that no longer parses falls back to its default.
+pub async fn load_settings() -> Result<Settings> {
+    let stored = DB.with(|conn| {
+        let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>();
+        rows.context("Failed to load settings")
+    })?;
+    settings_from_stored(stored)
+}
+
+pub async fn save_settings(settings: Settings) -> Result<()> {
+    DB.with(|conn| write_settings(conn, &settings))
+}
+
+fn palette_from_row(row: &rusqlite::Row) -> rusqlite::Result<Palette> {
+    let colors: String = row.get(2)?;
+    Ok(Palette {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        colors: serde_json::from_str(&colors).unwrap_or_default(),
+    })
+}
+
+pub async fn get_palettes() -> Result<Vec<Palette>> {
+    DB.with(|conn| {
+        let mut stmt = conn.prepare("SELECT id, name, colors FROM palette ORDER BY name")?;
+        let palettes = stmt
+            .query_map([], palette_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load palettes");
+        palettes
+    })
+}
+
+pub async fn get_palette(id: i32) -> Result<Palette> {
+    DB.with(|conn| {
+        conn.query_row("SELECT id, name, colors FROM palette WHERE id = ?1", [id], palette_from_row)
+            .context("Failed to load palette")
+    })
+}
+
+pub async fn save_palette(name: String, colors: BTreeMap<String, String>) -> Result<i32> {
+    let colors = serde_json::to_string(&colors)?;
+    DB.with(|conn| {
+        conn.execute("INSERT INTO palette (name, colors) VALUES (?1, ?2)", (&name, &colors))
+            .context("Failed to save palette")?;
+        Ok(conn.last_insert_rowid() as i32)
+    })
+}
+
+pub async fn update_palette(palette: Palette) -> Result<()> {
+    let colors = serde_json::to_string(&palette.colors)?;
+    DB.with(|conn| {
+        conn.execute(
+            "UPDATE palette SET name = ?1, colors = ?2 WHERE id = ?3",
+            (&palette.name, &colors, &palette.id),
+        )
+            .context("Failed to update palette")
+    })?;
+    Ok(())
+}
+
+pub async fn delete_palette(id: i32) -> Result<()> {
+    DB.with(|conn| {
+        conn.execute("DELETE FROM palette WHERE id = ?1", [id])
+            .context("Failed to delete palette")
+    })?;
+    Ok(())
+}
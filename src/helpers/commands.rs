@@ -0,0 +1,189 @@
+use crate::helpers::Tr;
+use dioxus::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Actions reachable from keyboard shortcuts and the command palette.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppCommand {
+    CommandPalette,
+    QuickSwitcher,
+    SaveNote,
+    NewNote,
+    NewFolder,
+    SearchNotes,
+    SwitchSyntaxTheme,
+    ToggleDarkMode,
+    ToggleOutline,
+    ExportNote,
+    GoHome,
+    GoBack,
+    GoForward,
+    OpenSettings,
+    OpenFaq,
+}
+
+impl AppCommand {
+    pub const ALL: [AppCommand; 15] = [
+        AppCommand::CommandPalette,
+        AppCommand::QuickSwitcher,
+        AppCommand::SaveNote,
+        AppCommand::NewNote,
+        AppCommand::NewFolder,
+        AppCommand::SearchNotes,
+        AppCommand::SwitchSyntaxTheme,
+        AppCommand::ToggleDarkMode,
+        AppCommand::ToggleOutline,
+        AppCommand::ExportNote,
+        AppCommand::GoHome,
+        AppCommand::GoBack,
+        AppCommand::GoForward,
+        AppCommand::OpenSettings,
+        AppCommand::OpenFaq,
+    ];
+
+    /// Stable identifier used to store remapped bindings.
+    pub fn id(&self) -> &'static str {
+        match self {
+            AppCommand::CommandPalette => "command_palette",
+            AppCommand::QuickSwitcher => "quick_switcher",
+            AppCommand::SaveNote => "save_note",
+            AppCommand::NewNote => "new_note",
+            AppCommand::NewFolder => "new_folder",
+            AppCommand::SearchNotes => "search_notes",
+            AppCommand::SwitchSyntaxTheme => "switch_syntax_theme",
+            AppCommand::ToggleDarkMode => "toggle_dark_mode",
+            AppCommand::ToggleOutline => "toggle_outline",
+            AppCommand::ExportNote => "export_note",
+            AppCommand::GoHome => "go_home",
+            AppCommand::GoBack => "go_back",
+            AppCommand::GoForward => "go_forward",
+            AppCommand::OpenSettings => "open_settings",
+            AppCommand::OpenFaq => "open_faq",
+        }
+    }
+
+    /// Whether this build can carry the command out; the browser has no exports directory.
+    pub fn is_available(&self) -> bool {
+        cfg!(not(feature = "web")) || *self != AppCommand::ExportNote
+    }
+
+    pub fn label(&self, tr: Tr) -> String {
+        tr.t(&format!("command-{}", self.id().replace('_', "-")))
+    }
+
+    pub fn default_binding(&self) -> Option<&'static str> {
+        match self {
+            AppCommand::CommandPalette => Some("Ctrl+K"),
+            AppCommand::QuickSwitcher => Some("Ctrl+P"),
+            AppCommand::SaveNote => Some("Ctrl+S"),
+            AppCommand::NewNote => Some("Ctrl+N"),
+            AppCommand::SearchNotes => Some("Ctrl+Shift+F"),
+            AppCommand::ToggleDarkMode => Some("Ctrl+Shift+D"),
+            AppCommand::GoBack => Some("Alt+ArrowLeft"),
+            AppCommand::GoForward => Some("Alt+ArrowRight"),
+            AppCommand::OpenSettings => Some("Ctrl+,"),
+            _ => None,
+        }
+    }
+}
+
+/// Canonical combo string such as `Ctrl+Shift+F`, shared with the JS key listener.
+/// Letters and digits come from the physical key code so Shift doesn't change them.
+/// Returns `None` while only modifiers are held.
+pub fn key_combo(ctrl: bool, alt: bool, shift: bool, code: &str, key: &str) -> Option<String> {
+    if matches!(key, "Control" | "Alt" | "Shift" | "Meta" | "AltGraph") {
+        return None;
+    }
+    let key = if let Some(letter) = code.strip_prefix("Key").filter(|l| l.len() == 1) {
+        letter.to_string()
+    } else if let Some(digit) = code.strip_prefix("Digit").filter(|d| d.len() == 1) {
+        digit.to_string()
+    } else if key.chars().count() == 1 {
+        key.to_uppercase()
+    } else {
+        key.to_string()
+    };
+
+    let mut combo = String::new();
+    for (held, name) in [(ctrl, "Ctrl+"), (alt, "Alt+"), (shift, "Shift+")] {
+        if held {
+            combo.push_str(name);
+        }
+    }
+    combo.push_str(&key);
+    Some(combo)
+}
+
+/// Defaults with the user's remapped bindings applied; an empty override unbinds.
+pub fn effective_bindings(overrides: &HashMap<String, String>) -> HashMap<AppCommand, String> {
+    AppCommand::ALL
+        .into_iter()
+        .filter(AppCommand::is_available)
+        .filter_map(|command| {
+            let combo = match overrides.get(command.id()) {
+                Some(combo) => combo.clone(),
+                None => command.default_binding()?.to_string(),
+            };
+            (!combo.is_empty()).then_some((command, combo))
+        })
+        .collect()
+}
+
+/// Current key bindings, provided by `App` and edited in `SettingsPage`.
+#[derive(Clone, Copy)]
+pub struct Keybindings(pub Signal<HashMap<AppCommand, String>>);
+
+/// Folder open in the editor, provided by `HomePage` and set by its folder and note routes.
+#[derive(Clone, Copy)]
+pub struct SelectedFolder(pub Signal<Option<i32>>);
+
+// Commands kept for handlers whose effects haven't run yet; far more than one tick dispatches.
+const RECENT_COMMANDS: usize = 32;
+
+/// Broadcasts commands to whichever mounted components handle them.
+#[derive(Clone, Copy)]
+pub struct CommandBus {
+    recent: Signal<VecDeque<(u64, AppCommand)>>,
+}
+
+impl CommandBus {
+    pub fn new() -> Self {
+        CommandBus { recent: Signal::new(VecDeque::new()) }
+    }
+
+    pub fn dispatch(mut self, command: AppCommand) {
+        let mut recent = self.recent.write();
+        let sequence = recent.back().map_or(0, |(sequence, _)| sequence + 1);
+        recent.push_back((sequence, command));
+        if recent.len() > RECENT_COMMANDS {
+            recent.pop_front();
+        }
+    }
+
+    fn last_sequence(&self) -> Option<u64> {
+        self.recent.peek().back().map(|(sequence, _)| *sequence)
+    }
+}
+
+/// Calls `handler` for every command dispatched while the component is mounted, in order,
+/// including several dispatched in the same tick.
+pub fn use_command_handler(mut handler: impl FnMut(AppCommand) + 'static) {
+    let bus = use_context::<CommandBus>();
+    // Commands dispatched before mounting belong to someone else
+    let mut handled = use_hook(|| CopyValue::new(bus.last_sequence()));
+
+    use_effect(move || {
+        let pending: Vec<(u64, AppCommand)> = bus
+            .recent
+            .read()
+            .iter()
+            .filter(|(sequence, _)| handled.cloned().is_none_or(|handled| *sequence > handled))
+            .cloned()
+            .collect();
+        // The handler may dispatch more commands, so the queue is no longer borrowed here
+        for (sequence, command) in pending {
+            handled.set(Some(sequence));
+            handler(command);
+        }
+    });
+}
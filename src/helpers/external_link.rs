@@ -0,0 +1,19 @@
+use crate::syntax::external_link;
+
+/// Opens an `http(s)` or `mailto` link from a note outside the app. Other schemes are ignored.
+pub fn open_external_link(href: &str) {
+    let Some(url) = external_link(href) else {
+        log::warn!("Blocked link to {}", href);
+        return;
+    };
+    #[cfg(not(feature = "web"))]
+    if let Err(e) = webbrowser::open(url) {
+        log::error!("Failed to open {}: {}", url, e);
+    }
+    #[cfg(feature = "web")]
+    if let Some(window) = web_sys::window() {
+        if let Err(e) = window.open_with_url_and_target_and_features(url, "_blank", "noopener") {
+            log::error!("Failed to open {}: {:?}", url, e);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+/// Case-insensitive subsequence match of `query` in `candidate`. Higher scores mean
+/// tighter matches: consecutive characters and word starts are rewarded, gaps cost.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut last_match: Option<usize> = None;
+    let mut previous = ' ';
+    for (index, c) in candidate.chars().enumerate() {
+        if matched < query.len() && c.to_lowercase().eq(std::iter::once(query[matched])) {
+            score += 1;
+            if last_match.is_some_and(|last| last + 1 == index) {
+                score += 5;
+            } else if let Some(last) = last_match {
+                score -= (index - last - 1).min(5) as i32;
+            }
+            if !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase()) {
+                score += 8;
+            }
+            last_match = Some(index);
+            matched += 1;
+        }
+        previous = c;
+    }
+
+    (matched == query.len()).then_some(score)
+}
@@ -0,0 +1,30 @@
+use crate::helpers::use_settings;
+use crate::i18n::{format_date, translate, Locale};
+use dioxus::prelude::*;
+use std::fmt::Display;
+
+/// Translates UI strings into the locale chosen in Settings.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Tr(pub Locale);
+
+impl Tr {
+    pub fn t(self, key: &str) -> String {
+        translate(self.0, key, &[])
+    }
+
+    pub fn t_args(self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        translate(self.0, key, args)
+    }
+
+    /// A stored timestamp as a date, or the raw value if it doesn't parse.
+    pub fn date(self, timestamp: &str) -> String {
+        format_date(self.0, timestamp).unwrap_or_else(|| timestamp.to_string())
+    }
+}
+
+/// Re-renders the calling component when the locale changes.
+pub fn use_tr() -> Tr {
+    let settings = use_settings();
+    let locale = use_memo(move || settings.0.read().locale);
+    Tr(locale())
+}
@@ -1,2 +1,15 @@
 mod structs_enums_helpers;
-pub use structs_enums_helpers::*;
\ No newline at end of file
+mod commands;
+mod external_link;
+mod fuzzy;
+mod i18n;
+mod settings;
+mod timer;
+
+pub use structs_enums_helpers::*;
+pub use commands::*;
+pub use external_link::*;
+pub use fuzzy::*;
+pub use i18n::*;
+pub use settings::*;
+pub use timer::*;
@@ -0,0 +1,30 @@
+use crate::data::{save_settings, Palette, Settings};
+use dioxus::prelude::*;
+
+/// App-wide settings, provided by `App`. Pages read the signal and re-render on change.
+#[derive(Clone, Copy)]
+pub struct AppSettings(pub Signal<Settings>);
+
+impl AppSettings {
+    /// Applies `change` and persists the result.
+    pub fn update(mut self, change: impl FnOnce(&mut Settings)) {
+        let updated = {
+            let mut settings = self.0.write();
+            change(&mut settings);
+            settings.clone()
+        };
+        spawn(async move {
+            if let Err(e) = save_settings(updated).await {
+                log::error!("Failed to save settings: {:#}", e);
+            }
+        });
+    }
+}
+
+/// The palette chosen in `Settings::palette_id`, kept loaded by `App`.
+#[derive(Clone, Copy)]
+pub struct ActivePalette(pub Signal<Option<Palette>>);
+
+pub fn use_settings() -> AppSettings {
+    use_context::<AppSettings>()
+}
@@ -1,3 +1,4 @@
+use crate::helpers::Tr;
 use dioxus::prelude::*;
 
 #[derive(PartialEq, Clone, Copy)]
@@ -7,6 +8,24 @@ pub enum DialogMode {
     CreateSubfolder,
 }
 
+impl DialogMode {
+    pub fn title(self, tr: Tr) -> String {
+        match self {
+            DialogMode::Create => tr.t("dialog-create-folder"),
+            DialogMode::Update => tr.t("dialog-update-folder"),
+            DialogMode::CreateSubfolder => tr.t("dialog-create-subfolder"),
+        }
+    }
+
+    pub fn submit_label(self, tr: Tr) -> String {
+        match self {
+            DialogMode::Create => tr.t("dialog-create"),
+            DialogMode::Update => tr.t("dialog-update"),
+            DialogMode::CreateSubfolder => tr.t("folder-create-subfolder"),
+        }
+    }
+}
+
 #[derive(Props, PartialEq, Clone)]
 pub struct AccordionProps{
     pub accordion_title: Option<String>,
@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+/// Waits without blocking the UI, on tokio's timer or on the browser's.
+pub async fn sleep(duration: Duration) {
+    #[cfg(not(feature = "web"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(feature = "web")]
+    gloo_timers::future::sleep(duration).await;
+}
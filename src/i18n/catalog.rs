@@ -0,0 +1,225 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::sync::{LazyLock, RwLock};
+
+/// A language the UI ships a message catalog for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Fr];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+
+    /// The language's name in itself, for the Settings picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Fr => "Français",
+        }
+    }
+
+    /// Parses codes such as `fr`, `fr-CA` or `fr_FR.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_', '.']).next()?.to_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    /// The locale from the usual environment variables, English if none is supported.
+    #[cfg(not(feature = "web"))]
+    pub fn from_system() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_code(&value))
+            .unwrap_or_default()
+    }
+
+    /// The browser's preferred language, English if it isn't supported.
+    #[cfg(feature = "web")]
+    pub fn from_system() -> Locale {
+        web_sys::window()
+            .and_then(|window| window.navigator().language())
+            .and_then(|language| Locale::from_code(&language))
+            .unwrap_or_default()
+    }
+
+    pub fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("en.ftl"),
+            Locale::Fr => include_str!("fr.ftl"),
+        }
+    }
+}
+
+static CATALOGS: LazyLock<HashMap<Locale, BTreeMap<String, String>>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let messages = parse_catalog(locale.source())
+                .unwrap_or_else(|e| panic!("Invalid {} catalog: {:#}", locale.code(), e));
+            (locale, messages)
+        })
+        .collect()
+});
+
+// For HTML rendered outside components, such as the code block buttons in the preview
+static UI_LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+pub fn set_ui_locale(locale: Locale) {
+    *UI_LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+pub fn ui_locale() -> Locale {
+    *UI_LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Parses the subset of Fluent syntax the catalogs use: `key = value` messages,
+/// indented continuation lines, `#` comments, `{ $name }` placeables and
+/// `{ $name -> [one] … *[other] … }` selects on a plural category or exact value.
+pub fn parse_catalog(source: &str) -> Result<BTreeMap<String, String>> {
+    let mut messages = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for (number, line) in source.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            current = None;
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let Some(key) = &current else {
+                bail!("line {}: continuation without a message", number + 1);
+            };
+            let value: &mut String = messages.get_mut(key).expect("current message exists");
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(line.trim());
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("line {}: expected `key = value`", number + 1);
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            bail!("line {}: invalid message key {:?}", number + 1, key);
+        }
+        if messages.insert(key.to_string(), value.trim().to_string()).is_some() {
+            bail!("line {}: duplicate message {}", number + 1, key);
+        }
+        current = Some(key.to_string());
+    }
+    Ok(messages)
+}
+
+/// All messages of a locale's catalog.
+pub fn catalog(locale: Locale) -> &'static BTreeMap<String, String> {
+    &CATALOGS[&locale]
+}
+
+/// Names of the `{ $name }` placeables and select arguments in a message, each listed once.
+pub fn placeables(message: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = message;
+    while let Some(start) = rest.find("{ $") {
+        rest = &rest[start + 3..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        if !names.contains(&&rest[..end]) {
+            names.push(&rest[..end]);
+        }
+        rest = &rest[end..];
+    }
+    names
+}
+
+/// The CLDR plural category of a number, `other` for anything that isn't one.
+pub fn plural_category(locale: Locale, value: &str) -> &'static str {
+    let Ok(number) = value.parse::<f64>() else {
+        return "other";
+    };
+    let one = match locale {
+        Locale::En => number == 1.0 && !value.contains('.'),
+        Locale::Fr => (0.0..2.0).contains(&number),
+    };
+    if one { "one" } else { "other" }
+}
+
+// Replaces each select with the variant whose key is the argument itself or its plural
+// category, falling back to the `*` default variant.
+fn resolve_selects(locale: Locale, message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::new();
+    let mut rest = message;
+    while let Some(start) = rest.find("{ $") {
+        let select = rest[start + 3..]
+            .split_once("->")
+            .filter(|(name, _)| !name.contains('}'))
+            .and_then(|(name, body)| body.split_once("\n}").map(|(variants, after)| (name.trim(), variants, after)));
+        let Some((name, variants, after)) = select else {
+            text.push_str(&rest[..start + 3]);
+            rest = &rest[start + 3..];
+            continue;
+        };
+        let value = args
+            .iter()
+            .find(|(arg, _)| *arg == name)
+            .map(|(_, value)| value.to_string())
+            .unwrap_or_default();
+        let category = plural_category(locale, &value);
+        let mut default = "";
+        let mut chosen = None;
+        for line in variants.lines().map(str::trim) {
+            let (is_default, line) = match line.strip_prefix('*') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let Some((key, variant)) = line.strip_prefix('[').and_then(|line| line.split_once(']')) else {
+                continue;
+            };
+            if is_default {
+                default = variant.trim();
+            }
+            if chosen.is_none() && (key.trim() == value || key.trim() == category) {
+                chosen = Some(variant.trim());
+            }
+        }
+        text.push_str(&rest[..start]);
+        text.push_str(chosen.unwrap_or(default));
+        rest = after;
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Looks `key` up in the locale's catalog, falling back to English and then to the key itself.
+pub fn translate(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let found = [locale, Locale::En]
+        .into_iter()
+        .find_map(|locale| catalog(locale).get(key).map(|message| (locale, message)));
+    let Some((locale, message)) = found else {
+        return key.to_string();
+    };
+    format_message(locale, message, args)
+}
+
+/// A catalog message with its selects resolved and its placeables filled in.
+pub fn format_message(locale: Locale, message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = resolve_selects(locale, message, args);
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    text
+}
@@ -0,0 +1,14 @@
+use crate::i18n::{translate, Locale};
+use chrono::{DateTime, Datelike};
+
+/// Formats an RFC 3339 timestamp, as stored in the database, as a date in the
+/// locale's order with its month names, e.g. "Oct 19, 2026" or "19 oct. 2026".
+pub fn format_date(locale: Locale, timestamp: &str) -> Option<String> {
+    let date = DateTime::parse_from_rfc3339(timestamp).ok()?;
+    let month = translate(locale, &format!("month-short-{}", date.month()), &[]);
+    Some(translate(
+        locale,
+        "date-format",
+        &[("day", &date.day()), ("month", &month), ("year", &date.year())],
+    ))
+}
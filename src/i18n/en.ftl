@@ -0,0 +1,286 @@
+# English messages, the reference catalog every other locale must match.
+
+## Dates
+date-format = { $month } { $day }, { $year }
+month-short-1 = Jan
+month-short-2 = Feb
+month-short-3 = Mar
+month-short-4 = Apr
+month-short-5 = May
+month-short-6 = Jun
+month-short-7 = Jul
+month-short-8 = Aug
+month-short-9 = Sep
+month-short-10 = Oct
+month-short-11 = Nov
+month-short-12 = Dec
+
+## Commands
+command-command-palette = Open command palette
+command-quick-switcher = Go to note or folder
+command-save-note = Save note
+command-new-note = New note
+command-new-folder = New folder
+command-search-notes = Search notes
+command-switch-syntax-theme = Switch syntax theme
+command-toggle-dark-mode = Toggle dark mode
+command-toggle-outline = Toggle outline
+command-export-note = Export note to HTML
+command-go-home = Go to home
+command-go-back = Go back
+command-go-forward = Go forward
+command-open-settings = Open settings
+command-open-faq = Open FAQ
+
+## Command palette
+palette-pick-theme = Pick a syntax theme...
+palette-type-command = Type a command...
+no-matches = No matches
+
+## Keyboard shortcuts
+shortcuts-title = Keyboard Shortcuts
+shortcuts-description = Click a shortcut and press the new key combination. Escape cancels.
+shortcuts-conflict = { $combo } is already used by "{ $command }"
+shortcuts-press-keys = Press keys...
+shortcuts-unassigned = Unassigned
+shortcuts-clear = Clear
+shortcuts-clear-title = Remove shortcut
+shortcuts-reset = Reset
+shortcuts-reset-title = Restore default shortcut
+
+## Folder tree
+folder-created = Created { $date }
+folder-create-subfolder = Create Subfolder
+folder-rename = Rename Folder
+folder-rename-subfolder = Rename Subfolder
+folder-export = Export to HTML
+folder-delete = Delete Folder
+folder-delete-subfolder = Delete Subfolder
+folder-actions = Actions for { $name }
+folder-delete-confirm-title = Delete this folder?
+folder-delete-confirm-warning = “{ $name }”, its subfolders and all their notes will be permanently deleted.
+folder-delete-confirm = Delete
+
+## Home page
+cancel = Cancel
+exported-to = Exported to { $path }
+export-failed = Export failed: { $error }
+export-untitled-note = Untitled note
+folders-title = Folders
+folders-new = + New
+folders-loading = Loading folders...
+folders-load-failed = Error loading folders: { $error }
+folders-empty = No folders yet. Click '+ New' to create one.
+folder-save-failed = Failed to save folder: { $error }
+folder-update-failed = Failed to update folder: { $error }
+folder-name-required = You need to enter a valid value
+home-welcome = Welcome to Runorna
+home-select-folder = Select a folder from the sidebar to view or create notes
+home-learn-more = Learn more about Runorna
+dialog-create-folder = Create New Folder
+dialog-update-folder = Update Folder Name
+dialog-create-subfolder = Create New Subfolder
+dialog-folder-name = Folder name...
+dialog-create = Create
+dialog-update = Update
+
+## Code blocks in the preview
+code-unknown-language = Unknown language: add an alias in Settings
+code-guess-detected = Detected automatically
+code-guess-insert = Insert language tag
+code-guess-insert-title = Write the language tag into the note
+code-guess-dismiss = Dismiss guess
+code-run = Run
+code-run-title = Run locally
+code-copy = Copy
+code-copy-title = Copy to clipboard
+code-copied = Copied
+code-output-save = Save output to note
+code-output-running = Running…
+
+## Front matter in the preview
+note-meta-error = Front matter error: { $error }
+note-meta-language = Language: { $language }
+note-meta-aliases = Aliases: { $aliases }
+note-meta-theme = Theme: { $theme }
+note-meta-run-enabled = Code execution enabled
+
+## SQL playground results
+sql-error = Error: { $error }
+sql-stopped = { $seconds ->
+        [one] Query stopped after { $seconds } second
+       *[other] Query stopped after { $seconds } seconds
+    }
+sql-rows-changed = { $count ->
+        [one] { $count } row changed
+       *[other] { $count } rows changed
+    }
+sql-rows-shown = Showing { $shown } of { $total } rows
+sql-no-rows = No rows
+sql-unavailable = SQL results are only shown in the desktop app
+sql-blob = { $bytes ->
+        [one] BLOB ({ $bytes } byte)
+       *[other] BLOB ({ $bytes } bytes)
+    }
+
+## Diagrams in the preview
+diagram-error = Diagram error: { $error }
+
+## Editor
+editor-editing = Editing: { $folder }
+editor-outline = Outline
+editor-theme-pinned = Theme: { $theme } (pinned)
+editor-theme-dark = Theme: { $theme } (dark)
+editor-theme-light = Theme: { $theme } (light)
+editor-export = Export HTML
+editor-editor = Editor
+editor-save-note = Save Note
+editor-placeholder = Write your markdown here...
+editor-preview = Preview
+editor-live-rendering = Live rendering
+editor-note-saved = Note saved successfully!
+editor-note-saved-meta-kept = Note saved, but its front matter was not applied: { $error }
+editor-characters = { $count ->
+        [one] { $count } character
+       *[other] { $count } characters
+    }
+
+## Running code blocks
+run-confirm-title = Run code on this computer?
+run-confirm-warning = This { $lang } block will run with your user's permissions. Only run code you trust.
+run-cwd = Working directory: { $cwd }
+run-default-cwd = application directory
+run-timeout = { $seconds ->
+        [one] Timeout: { $seconds } second
+       *[other] Timeout: { $seconds } seconds
+    }
+run-env = Environment: { $names }
+run-timed-out = Timed out
+run-finished = Finished
+run-exited = Exited with code { $code }
+run-terminated = Terminated
+run-failed = Failed: { $error }
+
+## Search and quick switcher
+search-placeholder = Search in all notes...
+search-no-results = No notes found
+note-open-failed = Could not open this note: { $error }
+note-untitled = Note { $id }
+switcher-placeholder = Go to note or folder...
+switcher-note = Note
+switcher-folder = Folder
+
+## Local API
+api-title = Local API
+api-toggle = Toggle local API
+api-listening = Listening on http://{ $address }
+api-description = Serves folders, notes and search as JSON on 127.0.0.1 only, for editor extensions and scripts. Requests must send the header
+api-openapi = The API is described at /api/openapi.json.
+api-port = Port
+api-port-invalid = Port must be between 1024 and 65535
+api-token = Token
+api-regenerate = Regenerate
+api-regenerate-title = Invalidates the current token
+
+## Language aliases
+aliases-title = Language Aliases
+aliases-description = Fence tags listed here are highlighted as another language, e.g. ```tsx as javascript. Your entries override the built-in ones.
+aliases-invalid-tag = The tag must be a single word, e.g. tf
+aliases-unknown-language = "{ $lang }" is not a language the highlighter knows
+aliases-tag = Tag
+aliases-language = Language
+aliases-add = Add
+aliases-remove = Remove
+aliases-built-in = built-in
+
+## Color palettes
+file-read-failed = Could not read { $file }
+palettes-title = Color Palettes
+palettes-description = A palette replaces the light and dark colors of the whole app. Share one by copying it as JSON, then paste it or import it from a .json file.
+palettes-active = Active
+palettes-built-in = Built-in light / dark
+palettes-new = New palette
+palettes-paste = Paste JSON
+palettes-import = Import .json
+palettes-edit = Edit
+palettes-copy = Copy JSON
+palettes-delete = Delete
+palettes-name = Palette name
+palettes-save = Save palette
+palettes-contrast = Contrast
+palettes-contrast-pair = --{ $foreground } on --{ $background }
+palettes-contrast-low = Too low
+palette-name-required = Give the palette a name
+palette-saved = Saved { $name }
+palette-clipboard-failed = Could not read the clipboard
+palette-imported = Imported { $name }
+palette-imported-files = Palette imported
+palette-copied = Copied { $name } as JSON
+
+## Navigation
+nav-home = Home
+nav-settings = Settings
+nav-faq = FAQ
+nav-menu = Menu
+nav-label = Main navigation
+
+## Settings page
+settings-title = Settings
+settings-dark-mode = Dark Mode
+settings-light-mode = Light Mode
+settings-toggle-dark-mode = Toggle dark mode
+settings-appearance = Appearance
+settings-light = Light
+settings-dark = Dark
+language-title = Language
+themes-title = Syntax Themes
+themes-description-before = Code blocks switch between these with light and dark mode. A note can still pin its own theme with
+themes-description-after = in its front matter.
+themes-light = Light mode
+themes-dark = Dark mode
+themes-import-description = Import .tmTheme files below, or drop them into the "{ $dir }" folder next to the database. Imported themes appear in the editor's theme picker.
+themes-import = Import .tmTheme
+themes-imported = Imported: { $names }
+editor-settings-title = Editor
+editor-font = Font
+editor-font-size = Font size
+editor-tab-width = Tab width
+editor-autosave = Autosave
+editor-word-wrap = Word wrap
+editor-toggle-word-wrap = Toggle word wrap
+autosave-off = Off
+autosave-10s = 10 seconds
+autosave-30s = 30 seconds
+autosave-1m = 1 minute
+autosave-5m = 5 minutes
+html-policy-title = HTML in notes
+html-policy-description = Raw HTML written in a note is filtered before it is shown or exported. Scripts, event handlers and javascript: or file: links are always removed.
+html-policy-strict = Strict: no inline styles
+html-policy-relaxed = Relaxed: keep text and table styles
+
+## FAQ
+faq-title = Frequently Asked Questions
+faq-what-title = What is Runorna?
+faq-what-intro = Runorna (from Old Norse "Rún" meaning "rune", "wisdom" or "secret") is a lightweight application designed to help developers store and organize their code snippets with syntax highlighting using markdown syntax.
+faq-what-built = Built with Rust and the Dioxus framework, Runorna focuses on simplicity, performance, and a clean user experience.
+faq-data-title = Where is my data stored?
+faq-data-intro = All your data is stored locally in a SQLite database. This means:
+faq-data-no-cloud = No data is collected or sent to any cloud services
+faq-data-private = Your snippets remain private on your machine
+faq-data-control = You maintain full control over your data
+faq-data-backup = The database file can be easily backed up or migrated
+faq-data-location = The database is typically located in your application data directory.
+faq-languages-title = What programming languages are supported by the markdown?
+faq-languages-intro = Here is a small list with their usage:
+faq-languages-aliases = Common variants such as ts, tsx, jsonc, yml, zsh or console are highlighted as the closest supported language, and you can add your own under Settings > Language Aliases.
+faq-languages-unsupported = Languages like Kotlin or Swift are not supported. A fence whose language can't be found is marked with a warning in the preview.
+faq-contribute-title = How can I contribute or suggest features?
+faq-contribute-intro = Runorna is open source and welcomes community contributions:
+faq-contribute-note = Please be aware that Runorna as it is now will not change a lot in the future, since I created it for my purposes only. But still feel free to make suggestions.
+faq-different-title = What makes Runorna different from other snippet managers?
+faq-different-intro = Runorna stands out by:
+faq-different-local = Being completely local and private by design
+faq-different-rust = Using Rust for performance and reliability and powered by Dioxus
+faq-different-markdown = Supporting markdown with syntax highlighting
+faq-different-fast = Being lightweight and fast
+faq-different-simple = Easy and simple design
@@ -0,0 +1,286 @@
+# Messages en français. Chaque clé du catalogue anglais doit avoir sa traduction ici.
+
+## Dates
+date-format = { $day } { $month } { $year }
+month-short-1 = janv.
+month-short-2 = févr.
+month-short-3 = mars
+month-short-4 = avr.
+month-short-5 = mai
+month-short-6 = juin
+month-short-7 = juil.
+month-short-8 = août
+month-short-9 = sept.
+month-short-10 = oct.
+month-short-11 = nov.
+month-short-12 = déc.
+
+## Commands
+command-command-palette = Ouvrir la palette de commandes
+command-quick-switcher = Aller à une note ou un dossier
+command-save-note = Enregistrer la note
+command-new-note = Nouvelle note
+command-new-folder = Nouveau dossier
+command-search-notes = Rechercher dans les notes
+command-switch-syntax-theme = Changer de thème de coloration
+command-toggle-dark-mode = Basculer le mode sombre
+command-toggle-outline = Afficher ou masquer le plan
+command-export-note = Exporter la note en HTML
+command-go-home = Aller à l'accueil
+command-go-back = Revenir en arrière
+command-go-forward = Aller en avant
+command-open-settings = Ouvrir les paramètres
+command-open-faq = Ouvrir la FAQ
+
+## Command palette
+palette-pick-theme = Choisir un thème de coloration...
+palette-type-command = Tapez une commande...
+no-matches = Aucun résultat
+
+## Keyboard shortcuts
+shortcuts-title = Raccourcis clavier
+shortcuts-description = Cliquez sur un raccourci puis appuyez sur la nouvelle combinaison de touches. Échap annule.
+shortcuts-conflict = { $combo } est déjà utilisé par « { $command } »
+shortcuts-press-keys = Appuyez sur des touches...
+shortcuts-unassigned = Non attribué
+shortcuts-clear = Effacer
+shortcuts-clear-title = Supprimer le raccourci
+shortcuts-reset = Réinitialiser
+shortcuts-reset-title = Rétablir le raccourci par défaut
+
+## Folder tree
+folder-created = Créé le { $date }
+folder-create-subfolder = Créer un sous-dossier
+folder-rename = Renommer le dossier
+folder-rename-subfolder = Renommer le sous-dossier
+folder-export = Exporter en HTML
+folder-delete = Supprimer le dossier
+folder-delete-subfolder = Supprimer le sous-dossier
+folder-actions = Actions pour { $name }
+folder-delete-confirm-title = Supprimer ce dossier ?
+folder-delete-confirm-warning = « { $name } », ses sous-dossiers et toutes leurs notes seront définitivement supprimés.
+folder-delete-confirm = Supprimer
+
+## Home page
+cancel = Annuler
+exported-to = Exporté vers { $path }
+export-failed = Échec de l'export : { $error }
+export-untitled-note = Note sans titre
+folders-title = Dossiers
+folders-new = + Nouveau
+folders-loading = Chargement des dossiers...
+folders-load-failed = Erreur lors du chargement des dossiers : { $error }
+folders-empty = Aucun dossier pour l'instant. Cliquez sur « + Nouveau » pour en créer un.
+folder-save-failed = Impossible d'enregistrer le dossier : { $error }
+folder-update-failed = Impossible de modifier le dossier : { $error }
+folder-name-required = Vous devez saisir une valeur valide
+home-welcome = Bienvenue dans Runorna
+home-select-folder = Sélectionnez un dossier dans la barre latérale pour afficher ou créer des notes
+home-learn-more = En savoir plus sur Runorna
+dialog-create-folder = Créer un dossier
+dialog-update-folder = Renommer le dossier
+dialog-create-subfolder = Créer un sous-dossier
+dialog-folder-name = Nom du dossier...
+dialog-create = Créer
+dialog-update = Modifier
+
+## Code blocks in the preview
+code-unknown-language = Langage inconnu : ajoutez un alias dans les paramètres
+code-guess-detected = Détecté automatiquement
+code-guess-insert = Insérer le langage
+code-guess-insert-title = Écrire le langage dans la note
+code-guess-dismiss = Ignorer la suggestion
+code-run = Exécuter
+code-run-title = Exécuter localement
+code-copy = Copier
+code-copy-title = Copier dans le presse-papiers
+code-copied = Copié
+code-output-save = Enregistrer la sortie dans la note
+code-output-running = Exécution…
+
+## Front matter in the preview
+note-meta-error = Erreur dans le front matter : { $error }
+note-meta-language = Langue : { $language }
+note-meta-aliases = Alias : { $aliases }
+note-meta-theme = Thème : { $theme }
+note-meta-run-enabled = Exécution du code activée
+
+## SQL playground results
+sql-error = Erreur : { $error }
+sql-stopped = { $seconds ->
+        [one] Requête arrêtée après { $seconds } seconde
+       *[other] Requête arrêtée après { $seconds } secondes
+    }
+sql-rows-changed = { $count ->
+        [one] { $count } ligne modifiée
+       *[other] { $count } lignes modifiées
+    }
+sql-rows-shown = { $shown } lignes affichées sur { $total }
+sql-no-rows = Aucune ligne
+sql-unavailable = Les résultats SQL ne s'affichent que dans l'application de bureau
+sql-blob = { $bytes ->
+        [one] BLOB ({ $bytes } octet)
+       *[other] BLOB ({ $bytes } octets)
+    }
+
+## Diagrams in the preview
+diagram-error = Erreur de diagramme : { $error }
+
+## Editor
+editor-editing = Modification : { $folder }
+editor-outline = Plan
+editor-theme-pinned = Thème : { $theme } (épinglé)
+editor-theme-dark = Thème : { $theme } (sombre)
+editor-theme-light = Thème : { $theme } (clair)
+editor-export = Exporter en HTML
+editor-editor = Éditeur
+editor-save-note = Enregistrer la note
+editor-placeholder = Écrivez votre markdown ici...
+editor-preview = Aperçu
+editor-live-rendering = Rendu en direct
+editor-note-saved = Note enregistrée !
+editor-note-saved-meta-kept = Note enregistrée, mais son front matter n'a pas été appliqué : { $error }
+editor-characters = { $count ->
+        [one] { $count } caractère
+       *[other] { $count } caractères
+    }
+
+## Running code blocks
+run-confirm-title = Exécuter du code sur cet ordinateur ?
+run-confirm-warning = Ce bloc { $lang } s'exécutera avec les droits de votre utilisateur. N'exécutez que du code de confiance.
+run-cwd = Répertoire de travail : { $cwd }
+run-default-cwd = répertoire de l'application
+run-timeout = { $seconds ->
+        [one] Délai maximal : { $seconds } seconde
+       *[other] Délai maximal : { $seconds } secondes
+    }
+run-env = Environnement : { $names }
+run-timed-out = Délai dépassé
+run-finished = Terminé
+run-exited = Terminé avec le code { $code }
+run-terminated = Interrompu
+run-failed = Échec : { $error }
+
+## Search and quick switcher
+search-placeholder = Rechercher dans toutes les notes...
+search-no-results = Aucune note trouvée
+note-open-failed = Impossible d'ouvrir cette note : { $error }
+note-untitled = Note { $id }
+switcher-placeholder = Aller à une note ou un dossier...
+switcher-note = Note
+switcher-folder = Dossier
+
+## Local API
+api-title = API locale
+api-toggle = Activer ou désactiver l'API locale
+api-listening = À l'écoute sur http://{ $address }
+api-description = Expose les dossiers, les notes et la recherche en JSON sur 127.0.0.1 uniquement, pour les extensions d'éditeur et les scripts. Les requêtes doivent envoyer l'en-tête
+api-openapi = L'API est décrite à l'adresse /api/openapi.json.
+api-port = Port
+api-port-invalid = Le port doit être compris entre 1024 et 65535
+api-token = Jeton
+api-regenerate = Régénérer
+api-regenerate-title = Invalide le jeton actuel
+
+## Language aliases
+aliases-title = Alias de langages
+aliases-description = Les étiquettes de bloc listées ici sont colorées comme un autre langage, par exemple ```tsx comme javascript. Vos entrées remplacent celles par défaut.
+aliases-invalid-tag = L'étiquette doit être un seul mot, par exemple tf
+aliases-unknown-language = « { $lang } » n'est pas un langage connu du surligneur
+aliases-tag = Étiquette
+aliases-language = Langage
+aliases-add = Ajouter
+aliases-remove = Supprimer
+aliases-built-in = par défaut
+
+## Color palettes
+file-read-failed = Impossible de lire { $file }
+palettes-title = Palettes de couleurs
+palettes-description = Une palette remplace les couleurs claires et sombres de toute l'application. Partagez-en une en la copiant en JSON, puis collez-la ou importez-la depuis un fichier .json.
+palettes-active = Active
+palettes-built-in = Clair / sombre par défaut
+palettes-new = Nouvelle palette
+palettes-paste = Coller le JSON
+palettes-import = Importer un .json
+palettes-edit = Modifier
+palettes-copy = Copier le JSON
+palettes-delete = Supprimer
+palettes-name = Nom de la palette
+palettes-save = Enregistrer la palette
+palettes-contrast = Contraste
+palettes-contrast-pair = --{ $foreground } sur --{ $background }
+palettes-contrast-low = Insuffisant
+palette-name-required = Donnez un nom à la palette
+palette-saved = { $name } enregistrée
+palette-clipboard-failed = Impossible de lire le presse-papiers
+palette-imported = { $name } importée
+palette-imported-files = Palette importée
+palette-copied = { $name } copiée en JSON
+
+## Navigation
+nav-home = Accueil
+nav-settings = Paramètres
+nav-faq = FAQ
+nav-menu = Menu
+nav-label = Navigation principale
+
+## Settings page
+settings-title = Paramètres
+settings-dark-mode = Mode sombre
+settings-light-mode = Mode clair
+settings-toggle-dark-mode = Basculer le mode sombre
+settings-appearance = Apparence
+settings-light = Clair
+settings-dark = Sombre
+language-title = Langue
+themes-title = Thèmes de coloration
+themes-description-before = Les blocs de code passent de l'un à l'autre avec le mode clair ou sombre. Une note peut toujours épingler son propre thème avec
+themes-description-after = dans son en-tête.
+themes-light = Mode clair
+themes-dark = Mode sombre
+themes-import-description = Importez des fichiers .tmTheme ci-dessous, ou déposez-les dans le dossier « { $dir } » à côté de la base de données. Les thèmes importés apparaissent dans le sélecteur de thème de l'éditeur.
+themes-import = Importer un .tmTheme
+themes-imported = Importés : { $names }
+editor-settings-title = Éditeur
+editor-font = Police
+editor-font-size = Taille de police
+editor-tab-width = Largeur de tabulation
+editor-autosave = Enregistrement automatique
+editor-word-wrap = Retour à la ligne
+editor-toggle-word-wrap = Activer ou désactiver le retour à la ligne
+autosave-off = Désactivé
+autosave-10s = 10 secondes
+autosave-30s = 30 secondes
+autosave-1m = 1 minute
+autosave-5m = 5 minutes
+html-policy-title = HTML dans les notes
+html-policy-description = Le HTML écrit dans une note est filtré avant d'être affiché ou exporté. Les scripts, les gestionnaires d'événements et les liens javascript: ou file: sont toujours supprimés.
+html-policy-strict = Strict : aucun style en ligne
+html-policy-relaxed = Souple : garder les styles de texte et de tableau
+
+## FAQ
+faq-title = Questions fréquentes
+faq-what-title = Qu'est-ce que Runorna ?
+faq-what-intro = Runorna (du vieux norrois « Rún », qui signifie « rune », « sagesse » ou « secret ») est une application légère qui aide les développeurs à conserver et organiser leurs extraits de code, avec coloration syntaxique, en markdown.
+faq-what-built = Conçu avec Rust et le framework Dioxus, Runorna mise sur la simplicité, la performance et une interface épurée.
+faq-data-title = Où sont stockées mes données ?
+faq-data-intro = Toutes vos données sont stockées localement dans une base SQLite. Cela signifie que :
+faq-data-no-cloud = Aucune donnée n'est collectée ni envoyée à un service cloud
+faq-data-private = Vos extraits restent privés sur votre machine
+faq-data-control = Vous gardez le contrôle total de vos données
+faq-data-backup = Le fichier de base de données se sauvegarde ou se déplace facilement
+faq-data-location = La base de données se trouve en général dans le répertoire de données de l'application.
+faq-languages-title = Quels langages de programmation le markdown prend-il en charge ?
+faq-languages-intro = En voici une courte liste avec leur syntaxe :
+faq-languages-aliases = Les variantes courantes comme ts, tsx, jsonc, yml, zsh ou console sont colorées comme le langage pris en charge le plus proche, et vous pouvez ajouter les vôtres dans Paramètres > Alias de langages.
+faq-languages-unsupported = Les langages comme Kotlin ou Swift ne sont pas pris en charge. Un bloc dont le langage est introuvable est signalé par un avertissement dans l'aperçu.
+faq-contribute-title = Comment contribuer ou proposer des fonctionnalités ?
+faq-contribute-intro = Runorna est open source et accueille les contributions de la communauté :
+faq-contribute-note = Runorna ne devrait pas beaucoup évoluer, car je l'ai créé pour mon propre usage. N'hésitez pas pour autant à faire des suggestions.
+faq-different-title = Qu'est-ce qui distingue Runorna des autres gestionnaires d'extraits ?
+faq-different-intro = Runorna se distingue par :
+faq-different-local = Un fonctionnement entièrement local et privé
+faq-different-rust = Rust pour la performance et la fiabilité, propulsé par Dioxus
+faq-different-markdown = La prise en charge du markdown avec coloration syntaxique
+faq-different-fast = Sa légèreté et sa rapidité
+faq-different-simple = Un design simple et clair
@@ -0,0 +1,5 @@
+mod catalog;
+mod dates;
+
+pub use catalog::*;
+pub use dates::*;
@@ -0,0 +1,5 @@
+#[cfg(not(feature = "web"))]
+pub mod api;
+pub mod data;
+pub mod i18n;
+pub mod syntax;
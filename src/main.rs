@@ -1,22 +1,38 @@
 mod components;
-mod data;
 mod helpers;
 mod pages;
-mod syntax;
+mod runner;
 
 use crate::components::NavbarComponent;
-use crate::pages::{FaqPage, HomePage};
+use crate::pages::{FaqPage, FolderPage, HomePage, NotePage, WelcomePage};
 use crate::pages::SettingsPage;
 use dioxus::document::eval;
 use dioxus::prelude::*;
-use crate::data::load_latest_theme;
+#[cfg(not(feature = "web"))]
+use crate::api::apply_api_config;
+use crate::data::{get_palette, load_keybindings, load_language_aliases, load_settings, palette_stylesheet};
+use crate::helpers::{effective_bindings, ActivePalette, AppSettings, CommandBus, Keybindings};
+use std::collections::HashMap;
+#[cfg(not(feature = "web"))]
+use dioxus::history::{History, MemoryHistory};
+#[cfg(not(feature = "web"))]
+use std::rc::Rc;
+use runorna::{data, i18n, syntax};
+#[cfg(not(feature = "web"))]
+use runorna::api;
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
     #[layout(NavbarComponent)]
+    #[layout(HomePage)]
     #[route("/")]
     Home {},
+    #[route("/folder/:folder_id")]
+    FolderPage { folder_id: i32 },
+    #[route("/note/:note_id")]
+    NotePage { note_id: i32 },
+    #[end_layout]
     #[route("/settings")]
     SettingsPage {},
     #[route("/faq")]
@@ -34,25 +50,78 @@ fn main() {
 
 #[component]
 fn App() -> Element {
-    let dark_mode = use_signal(|| false);
+    let mut settings = use_context_provider(|| AppSettings(Signal::new(data::Settings::default())));
+    let mut active_palette = use_context_provider(|| ActivePalette(Signal::new(None)));
+    use_context_provider(CommandBus::new);
+    let mut keybindings =
+        use_context_provider(|| Keybindings(Signal::new(effective_bindings(&HashMap::new()))));
 
-    use_effect({
-        to_owned![dark_mode];
-        move || {
-            spawn(async move {
-                if let Ok((_mode, is_dark)) = load_latest_theme().await {
-                    dark_mode.set(is_dark);
-                    let js = if is_dark {
-                        r#"document.getElementById("dark-css").disabled = false;"#
-                    } else {
-                        r#"document.getElementById("dark-css").disabled = true;"#
-                    };
-                    eval(js);
-                }
-            });
+    // Launched with a `runorna://note/ID` link, such as by the system URL handler
+    #[cfg(not(feature = "web"))]
+    use_hook(|| {
+        if let Some(note_id) = std::env::args().skip(1).find_map(|arg| syntax::note_link(&arg)) {
+            provide_context::<Rc<dyn History>>(Rc::new(MemoryHistory::with_initial_path(
+                Route::NotePage { note_id },
+            )));
         }
     });
 
+    use_effect(move || {
+        spawn(async move {
+            match load_settings().await {
+                Ok(loaded) => settings.0.set(loaded),
+                Err(e) => log::error!("Failed to load settings: {:#}", e),
+            }
+            match load_keybindings().await {
+                Ok(overrides) => keybindings.0.set(effective_bindings(&overrides)),
+                Err(e) => log::error!("Failed to load keybindings: {}", e),
+            }
+            match load_language_aliases().await {
+                Ok(aliases) => syntax::set_custom_language_aliases(&aliases),
+                Err(e) => log::error!("Failed to load language aliases: {}", e),
+            }
+            #[cfg(not(feature = "web"))]
+            {
+                let api_started = match data::load_api_config().await {
+                    Ok(config) => apply_api_config(&config),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = api_started {
+                    log::error!("Failed to start the local API: {:#}", e);
+                }
+            }
+        });
+    });
+
+    let dark_mode = use_memo(move || settings.0.read().dark_mode);
+    use_effect(move || {
+        eval(&format!(r#"document.getElementById("dark-css").disabled = {};"#, !dark_mode()));
+    });
+
+    // Code block buttons are rendered into the preview HTML, outside any component
+    let locale = use_memo(move || settings.0.read().locale);
+    use_effect(move || {
+        i18n::set_ui_locale(locale());
+        syntax::clear_highlight_cache();
+    });
+
+    let palette_id = use_memo(move || settings.0.read().palette_id);
+    use_effect(move || {
+        let Some(id) = palette_id() else {
+            active_palette.0.set(None);
+            return;
+        };
+        spawn(async move {
+            match get_palette(id).await {
+                Ok(palette) => active_palette.0.set(Some(palette)),
+                Err(e) => log::error!("Failed to load palette: {:#}", e),
+            }
+        });
+    });
+    let palette_css = use_memo(move || {
+        active_palette.0.read().as_ref().map(|palette| palette_stylesheet(&palette.colors))
+    });
+
     rsx! {
         // document::Link { rel: "icon", href: ICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
@@ -63,6 +132,9 @@ fn App() -> Element {
             disabled: Some(true),
         }
         document::Link { rel: "stylesheet", href: TAILWIND_CSS }
+        if let Some(css) = palette_css() {
+            document::Style { "{css}" }
+        }
 
         Router::<Route> {}
     }
@@ -71,7 +143,7 @@ fn App() -> Element {
 #[component]
 fn Home() -> Element {
     rsx! {
-        HomePage {}
+        WelcomePage {}
     }
 }
 
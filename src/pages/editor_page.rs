@@ -1,37 +1,201 @@
-use std::thread::sleep;
-use crate::components::ButtonComponent;
-use crate::data::{get_folder_name, get_notes, load_theme_preference, save_note, save_theme_preference, update_note, Note};
-use crate::syntax::markdown_to_html;
-use ammonia::{Builder, UrlRelative};
+use crate::components::{OutlineComponent, ThemeThumbnailComponent};
+use crate::data::{export_note_html, get_folder_name, get_notes, record_opened, save_note, update_note, Note};
+use crate::helpers::{open_external_link, sleep, use_command_handler, use_settings, use_tr, AppCommand};
+use crate::Route;
+use crate::runner::{run_code_block, run_fingerprint, run_timeout, CodeBlockAction, RunEvent};
+use crate::syntax::{
+    dismiss_language_guess, fenced_code_blocks, insert_output_block, language_guess, note_link, note_meta,
+    render_markdown, set_fence_lang, theme_names, theme_stylesheet, HeadingEntry, RunSettings,
+};
+use dioxus::document::eval;
 use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(150);
+
+// Forwards clicks on the Run and Save output buttons and on links of the preview to `EditorPage`.
+const CODE_ACTIONS_JS: &str = r##"
+    if (window.__runornaPreviewLinks) {
+        document.removeEventListener("click", window.__runornaPreviewLinks, true);
+    }
+    // Captured ahead of the webview's own link handling, which would follow any href
+    window.__runornaPreviewLinks = (event) => {
+        const link = event.target.closest("#note-preview a[href]");
+        if (!link) return;
+        event.preventDefault();
+        event.stopPropagation();
+        const href = link.getAttribute("href");
+        if (href.startsWith("#")) {
+            document.getElementById(decodeURIComponent(href.slice(1)))?.scrollIntoView({ behavior: "smooth" });
+        } else {
+            dioxus.send({ action: "open", href });
+        }
+    };
+    document.addEventListener("click", window.__runornaPreviewLinks, true);
+    if (window.__runornaCodeActions) {
+        document.removeEventListener("click", window.__runornaCodeActions);
+    }
+    window.__runornaCodeActions = (event) => {
+        const run = event.target.closest(".code-run");
+        const save = event.target.closest(".code-output-save");
+        const tag = event.target.closest(".code-insert-lang");
+        const dismiss = event.target.closest(".code-dismiss-guess");
+        // The index `render_fenced_code` wrote, which is the block's position in `fenced_code_blocks`
+        const send = (action, element) => {
+            const index = Number.parseInt(element?.dataset.block, 10);
+            if (Number.isInteger(index) && index >= 0) dioxus.send({ action, index });
+        };
+        if (tag) {
+            send("tag", tag.closest(".code-block"));
+        } else if (dismiss) {
+            send("dismiss", dismiss.closest(".code-block"));
+        } else if (run) {
+            send("run", run.closest(".code-block"));
+        } else if (save) {
+            send("save", save.closest(".code-output"));
+        }
+    };
+    document.addEventListener("click", window.__runornaCodeActions);
+"##;
+
+// Creates or clears the output panel under a code block, then appends streamed lines.
+const RUN_OUTPUT_JS: &str = r#"
+    const [index, saveLabel, runningLabel] = await dioxus.recv();
+    const block = document.querySelector(`.code-block[data-block="${index}"]`);
+    if (block) {
+        let panel = block.nextElementSibling;
+        if (!panel || !panel.classList.contains("code-output")) {
+            panel = document.createElement("div");
+            panel.className = "code-output";
+            panel.innerHTML = `
+                <div class="code-output-header">
+                    <span class="code-output-status"></span>
+                    <button type="button" class="code-output-save"></button>
+                </div>
+                <pre class="code-output-body"></pre>`;
+            block.after(panel);
+        }
+        panel.dataset.block = index;
+        const status = panel.querySelector(".code-output-status");
+        const body = panel.querySelector(".code-output-body");
+        const save = panel.querySelector(".code-output-save");
+        save.textContent = saveLabel;
+        status.textContent = runningLabel;
+        body.textContent = "";
+        save.hidden = true;
+
+        while (true) {
+            const [kind, text] = await dioxus.recv();
+            if (kind === "done") {
+                status.textContent = text;
+                save.hidden = false;
+                break;
+            }
+            const line = document.createElement("span");
+            line.className = kind;
+            line.textContent = text + "\n";
+            body.appendChild(line);
+        }
+    }
+"#;
 
 #[component]
-pub fn EditorPage(folder_id: i32) -> Element {
-    let mut user_input_markdown = use_signal(|| String::new());
-    let mut theme = use_signal(|| String::from("base16-eighties.dark"));
-    let mut notes = use_signal(|| Vec::<Note>::new());
+pub fn EditorPage(folder_id: i32, note_id: Option<i32>) -> Element {
+    let settings = use_settings();
+    let tr = use_tr();
+    let nav = navigator();
+    let mut user_input_markdown = use_signal(String::new);
+    let theme = use_memo(move || settings.0.read().syntax_theme().to_string());
+    let mut notes = use_signal(Vec::<Note>::new);
     let mut current_note_id = use_signal(|| None::<i32>);
-    let mut folder_name = use_signal(|| String::new());
+    let mut folder_name = use_signal(String::new);
     let mut is_saved_note = use_signal(|| false);
+    // Front matter that failed to parse on the last save, so the stored metadata was kept
+    let mut saved_meta_error = use_signal(|| None::<String>);
+    // Content as last loaded or saved, so autosave only writes real changes
+    let mut saved_markdown = use_signal(String::new);
+    let mut show_theme_picker = use_signal(|| false);
+    let available_themes = use_hook(theme_names);
+    let mut sanitized_html = use_signal(String::new);
+    let mut render_generation = use_signal(|| 0u64);
+    let mut outline = use_signal(Vec::<HeadingEntry>::new);
+    let mut show_outline = use_signal(|| true);
+    let mut pinned_theme = use_signal(|| None::<String>);
+    let mut run_settings = use_signal(RunSettings::default);
+    let mut run_outputs = use_signal(HashMap::<usize, String>::new);
+    let mut pending_run = use_signal(|| None::<(usize, String, String)>);
+    // Fingerprints of the runs confirmed this session
+    let mut confirmed_runs = use_signal(HashSet::<u64>::new);
+    let mut export_message = use_signal(|| None::<String>);
 
-    use_effect(move || {
-        spawn(async move {
-            if let Ok(saved_theme) = load_theme_preference().await {
-                theme.set(saved_theme);
+    // The preview is plain sanitized HTML, so copy buttons are wired with one delegated listener.
+    use_hook(|| {
+        let _ = eval(
+            r#"
+            if (!window.__runornaCodeCopy) {
+                window.__runornaCodeCopy = true;
+                document.addEventListener("click", async (event) => {
+                    const button = event.target.closest(".code-copy");
+                    if (!button) return;
+                    const block = button.closest(".code-block");
+                    const lines = block ? block.querySelectorAll(".code-line") : [];
+                    const text = Array.from(lines, (line) => line.textContent).join("\n");
+                    try {
+                        await navigator.clipboard.writeText(text);
+                    } catch {
+                        const scratch = document.createElement("textarea");
+                        scratch.value = text;
+                        document.body.appendChild(scratch);
+                        scratch.select();
+                        document.execCommand("copy");
+                        scratch.remove();
+                    }
+                    const label = button.textContent;
+                    button.textContent = button.dataset.copied;
+                    setTimeout(() => { button.textContent = label; }, 1500);
+                });
             }
-        });
+            "#,
+        );
     });
 
     let handle_theme_change = move |new_theme: String| {
-        theme.set(new_theme.clone());
+        settings.update(|settings| settings.set_syntax_theme(new_theme));
+    };
+
+    // Themes only restyle the preview, so switching one never re-renders the markdown.
+    let highlight_css = use_memo(move || theme_stylesheet(&pinned_theme().unwrap_or_else(|| theme.cloned())));
+
+    // Re-render the preview only once typing pauses for PREVIEW_DEBOUNCE.
+    use_effect(move || {
+        let markdown = user_input_markdown();
+        let policy = settings.0.read().sanitizer_policy;
+        let generation = *render_generation.peek() + 1;
+        render_generation.set(generation);
+
         spawn(async move {
-            let _ = save_theme_preference(new_theme).await;
+            sleep(PREVIEW_DEBOUNCE).await;
+            if *render_generation.peek() == generation {
+                let meta = note_meta(&markdown).unwrap_or_default();
+                // A theme pinned in the note's front matter wins over the global preference
+                let note_theme = meta.theme.filter(|name| theme_names().contains(name));
+                pinned_theme.set(note_theme);
+                run_settings.set(meta.run);
+
+                let rendered = render_markdown(&markdown);
+                sanitized_html.set(policy.clean(&rendered.html));
+                // Output panels are dropped with the old preview, block indices may have shifted
+                run_outputs.write().clear();
+                outline.set(rendered.outline);
+            }
         });
-    };
+    });
 
     use_effect(move || {
         spawn(async move {
             user_input_markdown.set("".to_string());
+            saved_markdown.set(String::new());
             current_note_id.set(None);
 
             if let Ok(name) = get_folder_name(folder_id).await {
@@ -40,11 +204,22 @@ pub fn EditorPage(folder_id: i32) -> Element {
 
             if let Ok(loaded_notes) = get_notes(folder_id).await {
                 notes.set(loaded_notes);
-                if let Some(latest_note) = notes.read().first() {
-                    user_input_markdown.set(latest_note.content.clone());
-                    current_note_id.set(Some(latest_note.id));
+                let notes = notes.read();
+                let opened = notes
+                    .iter()
+                    .find(|note| Some(note.id) == note_id)
+                    .or_else(|| notes.first());
+                if let Some(note) = opened {
+                    user_input_markdown.set(note.content.clone());
+                    saved_markdown.set(note.content.clone());
+                    current_note_id.set(Some(note.id));
                 }
             }
+
+            // Quick switcher ranks by this history
+            if let Err(e) = record_opened(folder_id, *current_note_id.peek()).await {
+                log::error!("Failed to record opened note: {}", e);
+            }
         });
     });
 
@@ -54,24 +229,29 @@ pub fn EditorPage(folder_id: i32) -> Element {
 
         spawn(async move {
             let result = if let Some(note_id) = current_note_id() {
-                update_note(note_id, content.clone(), now).await
+                update_note(note_id, content.clone(), now).await.map(|_| note_id)
             } else {
                 save_note(content.clone(), now, folder_id).await
             };
 
             match result {
-                Ok(_) => {
+                Ok(saved_id) => {
+                    current_note_id.set(Some(saved_id));
+                    saved_meta_error.set(note_meta(&content).err());
+                    saved_markdown.set(content);
                     is_saved_note.set(true);
                     spawn(async move {
-                        sleep(std::time::Duration::from_secs(3));
+                        sleep(Duration::from_secs(3)).await;
                         is_saved_note.set(false);
                     });
 
                     if let Ok(loaded_notes) = get_notes(folder_id).await {
                         notes.set(loaded_notes);
-                        if let Some(latest_note) = notes.read().first() {
-                            current_note_id.set(Some(latest_note.id));
-                        }
+                    }
+
+                    // The route reopens the editor, so wait until nothing typed since is unsaved
+                    if Some(saved_id) != note_id && *user_input_markdown.peek() == *saved_markdown.peek() {
+                        nav.replace(Route::NotePage { note_id: saved_id });
                     }
                 }
                 Err(e) => {
@@ -81,106 +261,261 @@ pub fn EditorPage(folder_id: i32) -> Element {
         });
     };
 
-    let custom_html = markdown_to_html(&user_input_markdown(), &theme());
+    // Restarted whenever the interval setting changes; 0 turns autosave off.
+    let _autosave = use_resource(move || async move {
+        let interval = settings.0.read().autosave_interval;
+        if interval == 0 {
+            return;
+        }
+        loop {
+            sleep(Duration::from_secs(interval.into())).await;
+            let changed = *user_input_markdown.peek() != *saved_markdown.peek();
+            if changed && !user_input_markdown.peek().trim().is_empty() {
+                save_note();
+            }
+        }
+    });
+
+    let execute_run = move |index: usize, lang: String, code: String| {
+        let settings = run_settings.peek().clone();
+        spawn(async move {
+            let panel = eval(RUN_OUTPUT_JS);
+            let _ = panel.send((index, tr.t("code-output-save"), tr.t("code-output-running")));
+            let result = run_code_block(&lang, &code, &settings, |event| {
+                let _ = match event {
+                    RunEvent::Stdout(line) => panel.send(("stdout", line)),
+                    RunEvent::Stderr(line) => panel.send(("stderr", line)),
+                };
+            })
+            .await;
+
+            let status = match result {
+                Ok(output) => {
+                    run_outputs.write().insert(index, output.output.clone());
+                    output.status(tr.0)
+                }
+                Err(e) => {
+                    log::error!("Failed to run code block: {}", e);
+                    tr.t_args("run-failed", &[("error", &e)])
+                }
+            };
+            let _ = panel.send(("done", status));
+        });
+    };
+
+    use_future(move || async move {
+        let mut actions = eval(CODE_ACTIONS_JS);
+        while let Ok(action) = actions.recv::<CodeBlockAction>().await {
+            match action {
+                CodeBlockAction::Run { index } => {
+                    if !run_settings.peek().enabled {
+                        continue;
+                    }
+                    let block = fenced_code_blocks(&user_input_markdown.peek()).into_iter().nth(index);
+                    let Some(block) = block.filter(|block| block.fence.is_runnable()) else {
+                        continue;
+                    };
+                    let (lang, code) = (block.fence.lang, block.code);
+                    let fingerprint = run_fingerprint(&lang, &code, &run_settings.peek());
+                    if confirmed_runs.peek().contains(&fingerprint) {
+                        execute_run(index, lang, code);
+                    } else {
+                        pending_run.set(Some((index, lang, code)));
+                    }
+                }
+                CodeBlockAction::Save { index } => {
+                    let Some(output) = run_outputs.peek().get(&index).cloned() else {
+                        continue;
+                    };
+                    let updated = insert_output_block(&user_input_markdown.peek(), index, &output);
+                    if let Some(updated) = updated {
+                        user_input_markdown.set(updated);
+                    }
+                }
+                CodeBlockAction::Tag { index } => {
+                    let block = fenced_code_blocks(&user_input_markdown.peek()).into_iter().nth(index);
+                    let Some(guess) = block.and_then(|block| language_guess(&block.fence.lang, &block.code)) else {
+                        continue;
+                    };
+                    let updated = set_fence_lang(&user_input_markdown.peek(), index, &guess.tag);
+                    if let Some(updated) = updated {
+                        user_input_markdown.set(updated);
+                    }
+                }
+                CodeBlockAction::Dismiss { index } => {
+                    let block = fenced_code_blocks(&user_input_markdown.peek()).into_iter().nth(index);
+                    if let Some(block) = block {
+                        dismiss_language_guess(&block.code);
+                        // Same text, but the preview has to re-render without the guess
+                        user_input_markdown.write();
+                    }
+                }
+                CodeBlockAction::Open { href } => match note_link(&href) {
+                    Some(note_id) => {
+                        nav.push(Route::NotePage { note_id });
+                    }
+                    None => open_external_link(&href),
+                },
+            }
+        }
+    });
+
+    let export_note = move || {
+        let markdown = user_input_markdown();
+        let note_id = current_note_id();
+        let theme_name = theme();
+        let policy = settings.0.peek().sanitizer_policy;
+        spawn(async move {
+            let message = match export_note_html(markdown, note_id, theme_name, policy).await {
+                Ok(path) => tr.t_args("exported-to", &[("path", &path.display())]),
+                Err(e) => {
+                    log::error!("Failed to export note: {}", e);
+                    tr.t_args("export-failed", &[("error", &e)])
+                }
+            };
+            export_message.set(Some(message));
+            sleep(Duration::from_secs(4)).await;
+            export_message.set(None);
+        });
+    };
+
+    use_command_handler(move |command| match command {
+        AppCommand::SaveNote => save_note(),
+        AppCommand::NewNote => {
+            user_input_markdown.set(String::new());
+            current_note_id.set(None);
+        }
+        AppCommand::ToggleOutline => show_outline.set(!show_outline()),
+        AppCommand::ExportNote => export_note(),
+        _ => {}
+    });
+
+    // Scrolls the preview to the heading and moves the textarea caret to its source line.
+    let jump_to_heading = move |heading: HeadingEntry| {
+        let markdown = user_input_markdown.peek();
+        let before = markdown.get(..heading.offset).unwrap_or_default();
+        let caret = before.encode_utf16().count();
+        let line = before.matches('\n').count();
+
+        let jump = eval(
+            r#"
+            const [id, caret, line] = await dioxus.recv();
+            document.getElementById(id)?.scrollIntoView({ behavior: "smooth", block: "start" });
+            const editor = document.getElementById("editor-textarea");
+            if (editor) {
+                editor.focus({ preventScroll: true });
+                editor.setSelectionRange(caret, caret);
+                const lineHeight = parseFloat(getComputedStyle(editor).lineHeight) || 24;
+                editor.scrollTop = Math.max(0, line * lineHeight - lineHeight);
+            }
+            "#,
+        );
+        let _ = jump.send((heading.id, caret, line));
+    };
 
-    let sanitized_html = Builder::default()
-        .add_tags([
-            "pre", "code", "span", "input", "label", "div", "section", "article", "table", "thead",
-            "tbody", "tfoot", "tr", "th", "td", "del", "ins", "mark", "sup", "sub", "details",
-            "summary", "math", "mrow", "mi", "mo", "mn", "msup", "msub", "msubsup", "mfrac",
-        ])
-        .add_tag_attributes("input", ["type", "checked", "disabled"].into_iter())
-        .add_generic_attributes(["class", "style", "id", "aria-hidden", "data-*"].into_iter())
-        .url_relative(UrlRelative::PassThrough)
-        .clean(&custom_html)
-        .to_string();
+    let editor_style = use_memo(move || {
+        let settings = settings.0.read();
+        format!(
+            "font-family: {}; font-size: {}px; tab-size: {};",
+            settings.editor_font_family(), settings.editor_font_size, settings.tab_width
+        )
+    });
 
     rsx! {
+        document::Style { "{highlight_css}" }
+
         div { class: "select-text min-h-screen bg-[var(--surface-container-lowest)] text-[var(--on-surface)] flex flex-col",
             header { class: "w-[90%] rounded-lg mt-5 mb-auto ml-auto mr-auto shadow-md bg-[var(--surface-container-high)] border-b border-[var(--outline-variant)] px-4 sm:px-8 py-4 sticky top-0 z-10",
                 div { class: "max-w-7xl mx-auto flex flex-col sm:flex-row justify-between items-center gap-4",
                     h1 { class: "text-xl font-semibold text-[var(--primary)]",
-                        "Editing: {folder_name()}"
+                        {tr.t_args("editor-editing", &[("folder", &folder_name())])}
                     }
 
-                    div { class: "flex flex-wrap gap-2 justify-center",
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "base16-ocean.dark".to_string(),
-                            text: "Ocean".to_string(),
-                            on_click: handle_theme_change,
-                        }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "base16-eighties.dark".to_string(),
-                            text: "Eighties".to_string(),
-                            on_click: handle_theme_change,
-                        }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "base16-mocha.dark".to_string(),
-                            text: "Mocha dark".to_string(),
-                            on_click: handle_theme_change,
+                    div { class: "flex items-center gap-2",
+                        button {
+                            class: "cursor-pointer px-3 py-1 rounded-md border border-[var(--outline-variant)] text-sm hover:bg-[var(--surface-container-highest)]",
+                            class: if show_outline() { "bg-[var(--surface-container-highest)]" } else { "" },
+                            onclick: move |_| show_outline.set(!show_outline()),
+                            {tr.t("editor-outline")}
                         }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "InspiredGitHub".to_string(),
-                            text: "GitHub".to_string(),
-                            on_click: handle_theme_change,
-                        }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "base16-ocean.light".to_string(),
-                            text: "Light".to_string(),
-                            on_click: handle_theme_change,
+                        button {
+                            class: "cursor-pointer px-3 py-1 rounded-md border border-[var(--outline-variant)] text-sm hover:bg-[var(--surface-container-highest)]",
+                            onclick: move |_| show_theme_picker.set(!show_theme_picker()),
+                            if let Some(pinned) = pinned_theme() {
+                                {tr.t_args("editor-theme-pinned", &[("theme", &pinned)])}
+                            } else {
+                                if settings.0.read().dark_mode {
+                                    {tr.t_args("editor-theme-dark", &[("theme", &theme())])}
+                                } else {
+                                    {tr.t_args("editor-theme-light", &[("theme", &theme())])}
+                                }
+                            }
                         }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "Solarized (dark)".to_string(),
-                            text: "Solarized dark".to_string(),
-                            on_click: handle_theme_change,
+                        // Exports are files, which the browser build can't write
+                        if cfg!(not(feature = "web")) {
+                            button {
+                                class: "cursor-pointer px-3 py-1 rounded-md border border-[var(--outline-variant)] text-sm hover:bg-[var(--surface-container-highest)]",
+                                onclick: move |_| export_note(),
+                                {tr.t("editor-export")}
+                            }
                         }
-                        ButtonComponent {
-                            theme: theme.clone(),
-                            name: "Solarized (light)".to_string(),
-                            text: "Solarized light".to_string(),
-                            on_click: handle_theme_change,
+                    }
+                }
+
+                if show_theme_picker() {
+                    div { class: "max-w-7xl mx-auto mt-4 flex flex-wrap gap-3 justify-center max-h-80 overflow-y-auto",
+                        for name in available_themes.iter() {
+                            ThemeThumbnailComponent {
+                                key: "{name}",
+                                theme,
+                                name: name.clone(),
+                                on_click: handle_theme_change,
+                            }
                         }
                     }
                 }
             }
 
-            main { class: "flex-1 p-4 sm:p-8 w-full",
-                div { class: "grid grid-cols-1 lg:grid-cols-2 gap-6 h-full max-w-7xl mx-auto min-h-[calc(100vh-8rem)]",
+            main { class: "flex-1 p-4 sm:p-8 w-full flex gap-6 max-w-7xl mx-auto",
+                if show_outline() && !outline.read().is_empty() {
+                    aside { class: "hidden lg:block w-56 flex-shrink-0 sticky top-32 self-start h-[calc(100vh-10rem)]",
+                        OutlineComponent { outline: outline(), on_select: jump_to_heading }
+                    }
+                }
+
+                div { class: "flex-1 min-w-0 grid grid-cols-1 lg:grid-cols-2 gap-6 h-full min-h-[calc(100vh-8rem)]",
                     // Editor Panel
                     div { class: "flex flex-col h-full",
                         div { class: "flex items-center justify-between mb-2",
                             h2 { class: "text-lg font-medium text-[var(--on-surface-variant)]",
-                                "Editor"
+                                {tr.t("editor-editor")}
                             }
                             div { class: "flex items-center gap-2",
                                 div { class: "text-xs text-[var(--on-surface-variant)]",
-                                    "{user_input_markdown().chars().count()} characters"
+                                    {tr.t_args("editor-characters", &[("count", &user_input_markdown().chars().count())])}
                                 }
                                 button {
                                     class: "cursor-pointer px-3 py-1 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--tertiary)] hover:text-[var(--on-tertiary)] text-sm",
                                     onclick: move |_| save_note(),
-                                    "Save Note"
+                                    {tr.t("editor-save-note")}
                                 }
                             }
                         }
                         div { class: "flex-1 flex flex-col border border-[var(--outline-variant)] rounded-xl overflow-hidden",
                             textarea {
+                                id: "editor-textarea",
                                 class: "
                                     flex-1 w-full p-4 sm:p-6
                                     bg-[var(--surface-container-high)] text-[var(--on-surface)]
-                                    text-base font-mono resize-none outline-none
+                                    resize-none outline-none
                                     [scrollbar-width:none] [-webkit-scrollbar:none]
                                 ",
+                                style: "{editor_style}",
+                                wrap: if settings.0.read().word_wrap { "soft" } else { "off" },
                                 spellcheck: "false",
                                 value: "{user_input_markdown()}",
                                 oninput: move |e| user_input_markdown.set(e.value().clone()),
-                                placeholder: "Write your markdown here...",
+                                placeholder: tr.t("editor-placeholder"),
                             }
                         }
                     }
@@ -189,22 +524,24 @@ pub fn EditorPage(folder_id: i32) -> Element {
                     div { class: "flex flex-col h-full",
                         div { class: "flex items-center justify-between mb-2",
                             h2 { class: "text-lg font-medium text-[var(--on-surface-variant)]",
-                                "Preview"
+                                {tr.t("editor-preview")}
                             }
                             div { class: "text-xs text-[var(--on-surface-variant)]",
-                                "Live rendering"
+                                {tr.t("editor-live-rendering")}
                             }
                         }
                         div { class: "flex-1 flex flex-col border border-[var(--outline-variant)] rounded-xl overflow-hidden",
                             div {
+                                id: "note-preview",
                                 class: "
                                     flex-1 w-full p-4 sm:p-6 overflow-auto
                                     bg-[var(--surface-container-high)]
                                     prose prose-sm max-w-none
                                     [&_*]:text-[var(--on-surface)]
-                                    [&_pre]:bg-[var(--surface-container-low)]
+                                    [&_pre:not(.code-block-body)]:bg-[var(--surface-container-low)]
                                     [scrollbar-width:none] [-webkit-scrollbar:none]
                                 ",
+                                class: if run_settings.read().enabled { "run-enabled" } else { "" },
                                 dangerous_inner_html: "{sanitized_html}",
                             }
                         }
@@ -213,6 +550,75 @@ pub fn EditorPage(folder_id: i32) -> Element {
             }
         }
 
+        if let Some((index, lang, code)) = pending_run() {
+            div {
+                class: "fixed inset-0 bg-black/50 backdrop-blur-lg flex items-center justify-center z-50",
+                onclick: move |_| pending_run.set(None),
+
+                div {
+                    class: "bg-[var(--surface-container-low)] rounded-xl p-6 shadow-lg w-[32rem] max-w-[90vw]",
+                    onclick: move |e| e.stop_propagation(),
+
+                    h2 { class: "text-xl font-semibold mb-2 text-[var(--on-surface)]",
+                        {tr.t("run-confirm-title")}
+                    }
+                    p { class: "text-sm mb-3 text-[var(--on-surface-variant)]",
+                        {tr.t_args("run-confirm-warning", &[("lang", &lang)])}
+                    }
+                    ul { class: "text-sm mb-3 text-[var(--on-surface-variant)]",
+                        li {
+                            {
+                                let cwd = run_settings.read().cwd.clone().unwrap_or_else(|| tr.t("run-default-cwd"));
+                                tr.t_args("run-cwd", &[("cwd", &cwd)])
+                            }
+                        }
+                        li { {tr.t_args("run-timeout", &[("seconds", &run_timeout(&run_settings.read()).as_secs())])} }
+                        if !run_settings.read().env.is_empty() {
+                            li {
+                                {
+                                    let names = run_settings.read().env.keys().cloned().collect::<Vec<_>>().join(", ");
+                                    tr.t_args("run-env", &[("names", &names)])
+                                }
+                            }
+                        }
+                    }
+                    pre { class: "mb-4 p-3 rounded-lg max-h-60 overflow-auto text-sm bg-[var(--surface-container-high)] text-[var(--on-surface)]",
+                        "{code}"
+                    }
+
+                    div { class: "flex justify-end gap-2",
+                        button {
+                            class: "px-4 py-2 rounded-lg text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-high)]",
+                            onclick: move |_| pending_run.set(None),
+                            {tr.t("cancel")}
+                        }
+                        button {
+                            class: "px-4 py-2 rounded-lg bg-[var(--error)] text-[var(--on-error)]",
+                            onclick: move |_| {
+                                // Running the same code the same way again skips this dialog
+                                confirmed_runs.write().insert(run_fingerprint(&lang, &code, &run_settings.peek()));
+                                pending_run.set(None);
+                                execute_run(index, lang.clone(), code.clone());
+                            },
+                            {tr.t("code-run")}
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(message) = export_message() {
+            div { class: "fixed bottom-4 left-4 z-50",
+                div { class: "
+                        bg-[var(--inverse-surface)] text-[var(--inverse-on-surface)]
+                        px-4 py-2 rounded-lg shadow-lg
+                        animate-fade-in
+                    ",
+                    "{message}"
+                }
+            }
+        }
+
         if is_saved_note() {
             div { class: "fixed bottom-4 right-4 z-50",
                 div { class: "
@@ -228,7 +634,14 @@ pub fn EditorPage(folder_id: i32) -> Element {
                         fill: "currentColor",
                         path { d: "M9 16.17L4.83 12l-1.42 1.41L9 19 21 7l-1.41-1.41L9 16.17z" }
                     }
-                    span { "Note saved successfully!" }
+                    match saved_meta_error() {
+                        Some(error) => rsx! {
+                            span { {tr.t_args("editor-note-saved-meta-kept", &[("error", &error)])} }
+                        },
+                        None => rsx! {
+                            span { {tr.t("editor-note-saved")} }
+                        },
+                    }
                 }
             }
         }
@@ -1,22 +1,25 @@
 use crate::components::AccordionComponent;
+use crate::helpers::use_tr;
 use dioxus::prelude::*;
 
 #[component]
 pub fn FaqPage() -> Element {
+    let tr = use_tr();
+
     rsx! {
         div { class: "max-w-3xl mx-auto p-6",
-            h1 { class: "text-2xl font-bold text-[var(--primary)] mb-6", "Frequently Asked Questions" }
+            h1 { class: "text-2xl font-bold text-[var(--primary)] mb-6", {tr.t("faq-title")} }
 
             // FAQ Item 1
             AccordionComponent {
-                accordion_title: "What is Runorna?",
+                accordion_title: tr.t("faq-what-title"),
                 accordion_description: rsx! {
                     div {
                         p { class: "mb-2",
-                            "Runorna (from Old Norse \"Rún\" meaning \"rune\", \"wisdom\" or \"secret\" is a lightweight application designed to help developers store and organize their code snippets with syntax highlighting using markdown syntax."
+                            {tr.t("faq-what-intro")}
                         }
                         p {
-                            "Built with Rust and the Dioxus framework, Runorna focuses on simplicity, performance, and a clean user experience."
+                            {tr.t("faq-what-built")}
                         }
                     }
                 },
@@ -24,27 +27,27 @@ pub fn FaqPage() -> Element {
 
             // FAQ Item 2
             AccordionComponent {
-                accordion_title: "Where is my data stored?",
+                accordion_title: tr.t("faq-data-title"),
                 accordion_description: rsx! {
                     div {
-                        p { class: "mb-2", "All your data is stored locally in a SQLite database. This means:" }
+                        p { class: "mb-2", {tr.t("faq-data-intro")} }
                         ul { class: "list-disc pl-5 space-y-1",
-                            li { "No data is collected or sent to any cloud services" }
-                            li { "Your snippets remain private on your machine" }
-                            li { "You maintain full control over your data" }
-                            li { "The database file can be easily backed up or migrated" }
+                            li { {tr.t("faq-data-no-cloud")} }
+                            li { {tr.t("faq-data-private")} }
+                            li { {tr.t("faq-data-control")} }
+                            li { {tr.t("faq-data-backup")} }
                         }
-                        p { class: "mt-2", "The database is typically located in your application data directory." }
+                        p { class: "mt-2", {tr.t("faq-data-location")} }
                     }
                 },
             }
 
             // FAQ Item 3
             AccordionComponent {
-                accordion_title: "What programming languages are supported by the markdown?",
+                accordion_title: tr.t("faq-languages-title"),
                 accordion_description: rsx! {
                     div {
-                        p { class: "mb-2", "Here is a small list with their usage:" }
+                        p { class: "mb-2", {tr.t("faq-languages-intro")} }
                         ul { class: "list-disc pl-5 space-y-1",
                             li { "JavaScript: ```js" }
                             li { "C#: ```c#" }
@@ -59,7 +62,8 @@ pub fn FaqPage() -> Element {
                             li { "Ruby: ```ruby" }
                             li { "C: ```c" }
                         }
-                        p { class: "mt-2", "Languages like Kotlin, swift or TypeScript are not supported." }
+                        p { class: "mt-2", {tr.t("faq-languages-aliases")} }
+                        p { class: "mt-2", {tr.t("faq-languages-unsupported")} }
                     }
                 },
             }
@@ -81,10 +85,10 @@ pub fn FaqPage() -> Element {
 
             // FAQ Item 5
             AccordionComponent {
-                accordion_title: "How can I contribute or suggest features?",
+                accordion_title: tr.t("faq-contribute-title"),
                 accordion_description: rsx! {
                     div {
-                        p { class: "mb-2", "Runorna is open source and welcomes community contributions:" }
+                        p { class: "mb-2", {tr.t("faq-contribute-intro")} }
                         ul { class: "list-disc pl-5 space-y-1",
                             li {
                                 strong { "GitHub: " }
@@ -96,7 +100,7 @@ pub fn FaqPage() -> Element {
                             }
                         }
                         p { class: "mt-2",
-                            "Please be aware that Runorna as it is now will not change a lot in the future, since I created it for my purposes only. But still feel free to make suggestions."
+                            {tr.t("faq-contribute-note")}
                         }
                     }
                 },
@@ -104,16 +108,16 @@ pub fn FaqPage() -> Element {
 
             // FAQ Item 6
             AccordionComponent {
-                accordion_title: "What makes Runorna different from other snippet managers?",
+                accordion_title: tr.t("faq-different-title"),
                 accordion_description: rsx! {
                     div {
-                        p { class: "mb-2", "Runorna stands out by:" }
+                        p { class: "mb-2", {tr.t("faq-different-intro")} }
                         ul { class: "list-disc pl-5 space-y-1",
-                            li { "Being completely local and private by design" }
-                            li { "Using Rust for performance and reliability and powered by Dioxus" }
-                            li { "Supporting markdown with syntax highlighting" }
-                            li { "Being lightweight and fast" }
-                            li { "Easy and simple design" }
+                            li { {tr.t("faq-different-local")} }
+                            li { {tr.t("faq-different-rust")} }
+                            li { {tr.t("faq-different-markdown")} }
+                            li { {tr.t("faq-different-fast")} }
+                            li { {tr.t("faq-different-simple")} }
                         }
                     }
                 },
@@ -1,36 +1,62 @@
 use std::collections::HashSet;
 use crate::components::FolderItem;
 use crate::data::{
-    delete_folder_recursive, get_folder_name, get_folders, get_notes, save_folder,
+    delete_folder_recursive, export_folder_html, get_folders, get_note, get_notes, save_folder,
     update_folder_name, Folder,
 };
-use crate::helpers::DialogMode;
+use crate::helpers::{
+    sleep, use_command_handler, use_settings, use_tr, AppCommand, CommandBus, DialogMode,
+    SelectedFolder,
+};
 use crate::pages::EditorPage;
 use chrono::Local;
 use dioxus::prelude::*;
 use crate::Route;
 
+fn folder_exists(folders: &[Folder], id: i32) -> bool {
+    folders
+        .iter()
+        .any(|folder| folder.id == id || folder_exists(&folder.children, id))
+}
+
+// Ids of the folders above `id`, outermost first, or `None` if it isn't in the tree.
+fn folder_ancestors(folders: &[Folder], id: i32) -> Option<Vec<i32>> {
+    folders.iter().find_map(|folder| {
+        if folder.id == id {
+            return Some(Vec::new());
+        }
+        let mut ancestors = folder_ancestors(&folder.children, id)?;
+        ancestors.insert(0, folder.id);
+        Some(ancestors)
+    })
+}
+
+/// Layout of `/`, `/folder/:folder_id` and `/note/:note_id`: the folder tree beside the
+/// routed page, so the tree keeps its state while moving between folders and notes.
 #[component]
 pub fn HomePage() -> Element {
     let mut show_dialog: Signal<bool> = use_signal(|| false);
-    let mut error_message: Signal<String> = use_signal(|| String::new());
-    let mut folders: Signal<Vec<Folder>> = use_signal(|| Vec::<Folder>::new());
-    let mut new_folder_name: Signal<String> = use_signal(|| String::new());
+    let mut error_message: Signal<String> = use_signal(String::new);
+    let mut folders: Signal<Vec<Folder>> = use_signal(Vec::<Folder>::new);
+    let mut new_folder_name: Signal<String> = use_signal(String::new);
     let mut loading_error: Signal<Option<String>> = use_signal(|| None);
     let mut is_loading: Signal<bool> = use_signal(|| true);
-    let mut selected_subfolder: Signal<Option<i32>> = use_signal(|| None::<i32>);
-    let expanded_folders = use_signal(|| HashSet::<i32>::new());
+    let selected_folder = use_context_provider(|| SelectedFolder(Signal::new(None)));
+    let nav = navigator();
+    let settings = use_settings();
+    let tr = use_tr();
+    let mut expanded_folders = use_signal(HashSet::<i32>::new);
+    let mut focused_folder = use_signal(|| None::<i32>);
 
     let show_dropdown_for_folder = use_signal(|| Option::<i32>::None);
     let mut dialog_mode = use_signal(|| DialogMode::Create);
     let mut current_folder_id = use_signal(|| Option::<i32>::None);
+    let mut export_message = use_signal(|| None::<String>);
+    // Folder awaiting confirmation before it is deleted with everything in it
+    let mut pending_delete = use_signal(|| None::<(i32, String)>);
 
     let handle_select_subfolder = move |folder_id: i32| {
-        selected_subfolder.set(Some(folder_id));
-        spawn(async move {
-            let _ = get_folder_name(folder_id).await;
-            let _ = get_notes(folder_id).await;
-        });
+        nav.push(Route::FolderPage { folder_id });
     };
 
     let fetch_folders = move || {
@@ -38,6 +64,12 @@ pub fn HomePage() -> Element {
             is_loading.set(true);
             match get_folders().await {
                 Ok(folders_from_db) => {
+                    // Keep the tree's tab stop on a folder that still exists
+                    let focused_exists = focused_folder()
+                        .is_some_and(|id| folder_exists(&folders_from_db, id));
+                    if !focused_exists {
+                        focused_folder.set(folders_from_db.first().map(|folder| folder.id));
+                    }
                     folders.set(folders_from_db);
                     is_loading.set(false);
                 }
@@ -53,6 +85,35 @@ pub fn HomePage() -> Element {
         fetch_folders();
     });
 
+    // Reveal the open folder in the tree, and leave it once it no longer exists, after a
+    // delete or from a stale link
+    use_effect(move || {
+        let Some(folder_id) = selected_folder.0() else {
+            return;
+        };
+        if is_loading() || loading_error.read().is_some() {
+            return;
+        }
+        match folder_ancestors(&folders.read(), folder_id) {
+            Some(ancestors) => {
+                expanded_folders.write().extend(ancestors);
+                focused_folder.set(Some(folder_id));
+            }
+            None => {
+                nav.replace(Route::Home {});
+            }
+        }
+    });
+
+    use_command_handler(move |command| {
+        if command == AppCommand::NewFolder {
+            dialog_mode.set(DialogMode::Create);
+            new_folder_name.set(String::new());
+            error_message.set(String::new());
+            show_dialog.set(true);
+        }
+    });
+
     let mut submit_folder = move |_| {
         let name = new_folder_name();
         let trimmed_name = name.trim();
@@ -73,7 +134,7 @@ pub fn HomePage() -> Element {
                             if let Err(e) =
                                 save_folder(trimmed_name.clone(), now, parent_id_for_save).await
                             {
-                                error_message.set(format!("Failed to save folder: {}", e));
+                                error_message.set(tr.t_args("folder-save-failed", &[("error", &e)]));
                             } else {
                                 new_folder_name.set(String::new());
                                 show_dialog.set(false);
@@ -83,7 +144,7 @@ pub fn HomePage() -> Element {
                         DialogMode::Update => {
                             if let Some(id) = *current_folder_id.read() {
                                 if let Err(e) = update_folder_name(id, trimmed_name.clone()).await {
-                                    error_message.set(format!("Failed to update folder: {}", e));
+                                    error_message.set(tr.t_args("folder-update-failed", &[("error", &e)]));
                                 } else {
                                     new_folder_name.set(String::new());
                                     show_dialog.set(false);
@@ -95,30 +156,45 @@ pub fn HomePage() -> Element {
                 }
             });
         } else {
-            error_message.set(String::from("You need to enter a valid value"));
+            error_message.set(tr.t("folder-name-required"));
         }
     };
 
-    let delete_folder = move |folder_id: i32| {
+    let mut delete_folder = move |folder_id: i32| {
+        pending_delete.set(None);
         spawn(async move {
             if let Err(e) = delete_folder_recursive(folder_id).await {
                 log::error!("Failed to delete folder: {}", e);
             } else {
-                if selected_subfolder() == Some(folder_id) {
-                    selected_subfolder.set(None);
-                }
                 fetch_folders();
             }
         });
     };
 
+    let export_folder = move |folder_id: i32| {
+        let theme_name = settings.0.peek().syntax_theme().to_string();
+        let policy = settings.0.peek().sanitizer_policy;
+        spawn(async move {
+            let message = match export_folder_html(folder_id, theme_name, policy).await {
+                Ok(path) => tr.t_args("exported-to", &[("path", &path.display())]),
+                Err(e) => {
+                    log::error!("Failed to export folder: {}", e);
+                    tr.t_args("export-failed", &[("error", &e)])
+                }
+            };
+            export_message.set(Some(message));
+            sleep(std::time::Duration::from_secs(4)).await;
+            export_message.set(None);
+        });
+    };
+
     rsx! {
         div { class: "flex h-screen w-full bg-[var(--background)] overflow-hidden",
             // Side panel
             div { class: "w-72 border-r border-[var(--primary)] bg-[var(--surface-container)] flex flex-col h-screen",
                 // Panel header
                 div { class: "flex flex-row p-4 border-b border-[var(--primary)] flex-shrink-0",
-                    h2 { class: "text-lg font-medium text-[var(--on-surface)]", "Folders" }
+                    h2 { class: "text-lg font-medium text-[var(--on-surface)]", {tr.t("folders-title")} }
                     button {
                         class: "ml-10 md:ml-32 text-[var(--secondary)] hover:text-[var(--secondary)] cursor-pointer",
                         onclick: move |_| {
@@ -127,27 +203,30 @@ pub fn HomePage() -> Element {
                             error_message.set(String::new());
                             show_dialog.set(true);
                         },
-                        "+ New"
+                        {tr.t("folders-new")}
                     }
                 }
 
                 // Folder list - this will scroll independently
                 div { class: "flex-1 overflow-y-auto overflow-x-hidden",
                     if *is_loading.read() {
-                        div { class: "text-[var(--on-surface-variant)] p-4", "Loading folders..." }
+                        div { class: "text-[var(--on-surface-variant)] p-4", {tr.t("folders-loading")} }
                     } else if let Some(err) = loading_error.read().as_ref() {
-                        div { class: "p-4 text-[var(--error)]", "Error loading folders: {err}" }
+                        div { class: "p-4 text-[var(--error)]", {tr.t_args("folders-load-failed", &[("error", err)])} }
                     } else if folders.read().is_empty() {
                         div { class: "text-[var(--on-surface-variant)] p-4",
-                            "No folders yet. Click '+ New' to create one."
+                            {tr.t("folders-empty")}
                         }
                     } else {
-                        div { class: "divide-y divide-[var(--primary)]",
+                        div {
+                            class: "divide-y divide-[var(--primary)]",
+                            role: "tree",
+                            aria_label: tr.t("folders-title"),
                             for folder in folders.read().iter() {
                                 FolderItem {
                                     folder: folder.clone(),
                                     show_dropdown_for_folder,
-                                    on_delete: delete_folder,
+                                    on_delete: move |folder| pending_delete.set(Some(folder)),
                                     on_update_click: move |(f_id, f_name)| {
                                         current_folder_id.set(Some(f_id));
                                         new_folder_name.set(f_name);
@@ -163,7 +242,10 @@ pub fn HomePage() -> Element {
                                         show_dialog.set(true);
                                     },
                                     on_select_subfolder: handle_select_subfolder,
+                                    on_export_click: export_folder,
                                     expanded_folders,
+                                    selected_folder: selected_folder.0,
+                                    focused_folder,
                                 }
                             }
                         }
@@ -173,52 +255,7 @@ pub fn HomePage() -> Element {
 
             // Main content area
             div { class: "flex-1 overflow-hidden",
-                match selected_subfolder() {
-                    Some(folder_id) => rsx! {
-                        div { key: "{folder_id}", class: "h-full overflow-auto",
-                            EditorPage { folder_id }
-                        }
-                    },
-                    None => rsx! {
-                        div { class: "h-full flex flex-col items-center justify-center text-center p-8",
-                            div { class: "max-w-md flex flex-col items-center gap-6",
-                                // Icon or illustration (using a simple SVG for now)
-                                svg {
-                                    class: "w-24 h-24 text-[var(--primary)]",
-                                    fill: "none",
-                                    stroke: "currentColor",
-                                    stroke_width: "1.5",
-                                    view_box: "0 0 24 24",
-                                    xmlns: "http://www.w3.org/2000/svg",
-                                    path {
-                                        stroke_linecap: "round",
-                                        stroke_linejoin: "round",
-                                        d: "M19.5 14.25v-2.625a3.375 3.375 0 00-3.375-3.375h-1.5A1.125 1.125 0 0113.5 7.125v-1.5a3.375 3.375 0 00-3.375-3.375H8.25m2.25 0H5.625c-.621 0-1.125.504-1.125 1.125v17.25c0 .621.504 1.125 1.125 1.125h12.75c.621 0 1.125-.504 1.125-1.125V11.25a9 9 0 00-9-9z",
-                                    }
-                                }
-                                h1 { class: "text-3xl font-bold text-[var(--on-surface)]", "Welcome to Runorna" }
-                                p { class: "text-lg text-[var(--on-surface-variant)] mb-6",
-                                    "Select a folder from the sidebar to view or create notes"
-                                }
-                                div { class: "flex flex-col sm:flex-row gap-4",
-                                    button {
-                                        class: "cursor-pointer px-6 py-3 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--primary-container)] hover:text-[var(--on-primary-container)] transition-colors",
-                                        onclick: move |_| {
-                                            dialog_mode.set(DialogMode::Create);
-                                            new_folder_name.set(String::new());
-                                            error_message.set(String::new());
-                                            show_dialog.set(true);
-                                        },
-                                        "Create New Folder"
-                                    }
-                                    button { class: "cursor-pointer px-6 py-3 rounded-lg border border-[var(--outline-variant)] text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-high)] transition-colors",
-                                        Link { to: Route::Faq {}, "Learn more about Runorna" }
-                                    }
-                                }
-                            }
-                        }
-                    },
-                }
+                Outlet::<Route> {}
             }
 
             // Dialog overlay
@@ -233,19 +270,13 @@ pub fn HomePage() -> Element {
                         onclick: move |e| e.stop_propagation(),
 
                         h2 { class: "text-xl font-semibold mb-4 text-[var(--on-surface)]",
-                            {
-                                match dialog_mode() {
-                                    DialogMode::Create => "Create New Folder",
-                                    DialogMode::Update => "Update Folder Name",
-                                    DialogMode::CreateSubfolder => "Create New Subfolder",
-                                }
-                            }
+                            {dialog_mode().title(tr)}
                         }
 
                         input {
                             r#type: "text",
                             class: "border border-[var(--outline-variant)] rounded-lg p-3 w-full mb-4 bg-[var(--surface-container-low)] text-[var(--on-surface)]",
-                            placeholder: "Folder name...",
+                            placeholder: tr.t("dialog-folder-name"),
                             value: "{new_folder_name}",
                             oninput: move |e| new_folder_name.set(e.value().clone()),
                             onkeydown: move |e| {
@@ -264,23 +295,169 @@ pub fn HomePage() -> Element {
                             button {
                                 class: "px-4 py-2 rounded-lg text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-high)]",
                                 onclick: move |_| show_dialog.set(false),
-                                "Cancel"
+                                {tr.t("cancel")}
                             }
                             button {
                                 class: "px-4 py-2 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--primary-container)]",
                                 onclick: move |_| submit_folder(()),
-                                {
-                                    match dialog_mode() {
-                                        DialogMode::Create => "Create",
-                                        DialogMode::Update => "Update",
-                                        DialogMode::CreateSubfolder => "Create Subfolder",
-                                    }
-                                }
+                                {dialog_mode().submit_label(tr)}
                             }
                         }
                     }
                 }
             }
+
+            if let Some((folder_id, name)) = pending_delete() {
+                div {
+                    class: "fixed inset-0 bg-black/50 backdrop-blur-lg flex items-center justify-center z-50",
+                    onclick: move |_| pending_delete.set(None),
+                    onkeydown: move |e| {
+                        if e.key() == Key::Escape {
+                            pending_delete.set(None);
+                        }
+                    },
+
+                    div {
+                        class: "bg-[var(--surface-container-low)] rounded-xl p-6 shadow-lg w-96",
+                        role: "alertdialog",
+                        aria_label: tr.t("folder-delete-confirm-title"),
+                        onclick: move |e| e.stop_propagation(),
+
+                        h2 { class: "text-xl font-semibold mb-2 text-[var(--on-surface)]",
+                            {tr.t("folder-delete-confirm-title")}
+                        }
+                        p { class: "text-sm mb-4 text-[var(--on-surface-variant)]",
+                            {tr.t_args("folder-delete-confirm-warning", &[("name", &name)])}
+                        }
+
+                        div { class: "flex justify-end gap-2",
+                            button {
+                                class: "px-4 py-2 rounded-lg text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-high)]",
+                                onmounted: move |e| async move {
+                                    let _ = e.set_focus(true).await;
+                                },
+                                onclick: move |_| pending_delete.set(None),
+                                {tr.t("cancel")}
+                            }
+                            button {
+                                class: "px-4 py-2 rounded-lg bg-[var(--error)] text-[var(--on-error)]",
+                                onclick: move |_| delete_folder(folder_id),
+                                {tr.t("folder-delete-confirm")}
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(message) = export_message() {
+                div { class: "fixed bottom-4 right-4 z-50",
+                    div { class: "bg-[var(--inverse-surface)] text-[var(--inverse-on-surface)] px-4 py-2 rounded-lg shadow-lg animate-fade-in",
+                        "{message}"
+                    }
+                }
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Shown at `/` while no folder is open.
+#[component]
+pub fn WelcomePage() -> Element {
+    let mut selected_folder = use_context::<SelectedFolder>();
+    let bus = use_context::<CommandBus>();
+    let tr = use_tr();
+
+    use_effect(move || selected_folder.0.set(None));
+
+    rsx! {
+        div { class: "h-full flex flex-col items-center justify-center text-center p-8",
+            div { class: "max-w-md flex flex-col items-center gap-6",
+                // Icon or illustration (using a simple SVG for now)
+                svg {
+                    class: "w-24 h-24 text-[var(--primary)]",
+                    fill: "none",
+                    stroke: "currentColor",
+                    stroke_width: "1.5",
+                    view_box: "0 0 24 24",
+                    xmlns: "http://www.w3.org/2000/svg",
+                    path {
+                        stroke_linecap: "round",
+                        stroke_linejoin: "round",
+                        d: "M19.5 14.25v-2.625a3.375 3.375 0 00-3.375-3.375h-1.5A1.125 1.125 0 0113.5 7.125v-1.5a3.375 3.375 0 00-3.375-3.375H8.25m2.25 0H5.625c-.621 0-1.125.504-1.125 1.125v17.25c0 .621.504 1.125 1.125 1.125h12.75c.621 0 1.125-.504 1.125-1.125V11.25a9 9 0 00-9-9z",
+                    }
+                }
+                h1 { class: "text-3xl font-bold text-[var(--on-surface)]", {tr.t("home-welcome")} }
+                p { class: "text-lg text-[var(--on-surface-variant)] mb-6",
+                    {tr.t("home-select-folder")}
+                }
+                div { class: "flex flex-col sm:flex-row gap-4",
+                    button {
+                        class: "cursor-pointer px-6 py-3 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--primary-container)] hover:text-[var(--on-primary-container)] transition-colors",
+                        // The folder dialog belongs to the `HomePage` layout
+                        onclick: move |_| bus.dispatch(AppCommand::NewFolder),
+                        {tr.t("dialog-create-folder")}
+                    }
+                    button { class: "cursor-pointer px-6 py-3 rounded-lg border border-[var(--outline-variant)] text-[var(--on-surface-variant)] hover:bg-[var(--surface-container-high)] transition-colors",
+                        Link { to: Route::Faq {}, {tr.t("home-learn-more")} }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A folder at `/folder/:folder_id`, which moves on to its latest note or opens an empty editor.
+#[component]
+pub fn FolderPage(folder_id: i32) -> Element {
+    let mut selected_folder = use_context::<SelectedFolder>();
+    let nav = navigator();
+    let notes = use_resource(use_reactive!(|folder_id| async move { get_notes(folder_id).await }));
+
+    use_effect(use_reactive!(|folder_id| selected_folder.0.set(Some(folder_id))));
+
+    let latest_note = use_memo(move || notes.read().as_ref()?.as_ref().ok()?.first().map(|note| note.id));
+    use_effect(move || {
+        if let Some(note_id) = latest_note() {
+            nav.replace(Route::NotePage { note_id });
+        }
+    });
+
+    let loaded = notes.read().is_some();
+    rsx! {
+        if loaded && latest_note().is_none() {
+            div { key: "{folder_id}", class: "h-full overflow-auto",
+                EditorPage { folder_id, note_id: None }
+            }
+        }
+    }
+}
+
+/// A note opened in its folder's editor, at `/note/:note_id`.
+#[component]
+pub fn NotePage(note_id: i32) -> Element {
+    let mut selected_folder = use_context::<SelectedFolder>();
+    let tr = use_tr();
+    let note = use_resource(use_reactive!(|note_id| async move { get_note(note_id).await }));
+    let folder_id = use_memo(move || note.read().as_ref()?.as_ref().ok().map(|note| note.folder_id));
+
+    use_effect(move || {
+        if let Some(folder_id) = folder_id() {
+            selected_folder.0.set(Some(folder_id));
+        }
+    });
+
+    let note = note.read();
+    match (&*note, folder_id()) {
+        (Some(Ok(_)), Some(folder_id)) => rsx! {
+            div { key: "{note_id}", class: "h-full overflow-auto",
+                EditorPage { folder_id, note_id: Some(note_id) }
+            }
+        },
+        (Some(Err(e)), _) => rsx! {
+            div { class: "h-full flex items-center justify-center p-8 text-[var(--error)]",
+                {tr.t_args("note-open-failed", &[("error", e)])}
+            }
+        },
+        _ => rsx! {},
+    }
+}
@@ -4,6 +4,6 @@ mod editor_page;
 mod faq_page;
 
 pub use settings_page::SettingsPage;
-pub use home_page::HomePage;
+pub use home_page::{FolderPage, HomePage, NotePage, WelcomePage};
 pub use editor_page::EditorPage;
 pub use faq_page::FaqPage;
\ No newline at end of file
@@ -1,58 +1,63 @@
-use crate::data::{load_latest_theme, save_theme};
-use dioxus::document::eval;
+use crate::components::{
+    KeyboardShortcutsComponent, LanguageAliasesComponent, LocalApiComponent, PalettesComponent,
+};
+use crate::helpers::{use_settings, use_tr};
+use crate::i18n::Locale;
+use crate::syntax::{import_theme, theme_names, SanitizerPolicy, THEMES_DIR};
 use dioxus::prelude::*;
 
+const TAB_WIDTHS: [u32; 3] = [2, 4, 8];
+const AUTOSAVE_INTERVALS: [(u32, &str); 5] = [
+    (0, "autosave-off"),
+    (10, "autosave-10s"),
+    (30, "autosave-30s"),
+    (60, "autosave-1m"),
+    (300, "autosave-5m"),
+];
+const SANITIZER_POLICIES: [(SanitizerPolicy, &str); 2] = [
+    (SanitizerPolicy::Strict, "html-policy-strict"),
+    (SanitizerPolicy::Relaxed, "html-policy-relaxed"),
+];
+
 #[component]
 pub fn SettingsPage() -> Element {
-    let mut dark_mode = use_signal(|| false);
-
-    use_effect({
-        to_owned![dark_mode];
-        move || {
-            spawn(async move {
-                if let Ok((_mode, is_dark)) = load_latest_theme().await {
-                    dark_mode.set(is_dark);
-                    let js = if is_dark {
-                        r#"document.getElementById("dark-css").disabled = false;"#
-                    } else {
-                        r#"document.getElementById("dark-css").disabled = true;"#
-                    };
-                    eval(js);
-                }
-            });
-        }
+    let settings = use_settings();
+    let tr = use_tr();
+    let dark_mode = use_memo(move || settings.0.read().dark_mode);
+    let mut theme_import_message = use_signal(|| None::<Result<String, String>>);
+    // Re-read after an import so new themes show up in the pickers
+    let mut available_themes = use_signal(|| {
+        let mut names = theme_names();
+        names.sort();
+        names
     });
 
-    let toggle_dark_mode = move |_| {
-        let enabled = !dark_mode();
-        dark_mode.set(enabled);
+    let toggle_dark_mode = move |_| settings.update(|settings| settings.dark_mode = !settings.dark_mode);
+    let toggle_light_mode = move |_| settings.update(|settings| settings.dark_mode = false);
 
-        let js = if enabled {
-            r#"document.getElementById("dark-css").disabled = false; document.getElementById("main-css").disabled = true;"#
-        } else {
-            r#"document.getElementById("dark-css").disabled = true; document.getElementById("main-css").disabled = false;"#
+    let handle_theme_import = move |evt: Event<FormData>| {
+        let Some(file_engine) = evt.files() else {
+            return;
         };
-        eval(js);
-
-        // Save theme to DB
-        spawn({
-            let mode = if enabled { "dark" } else { "light" }.to_string();
-            async move {
-                let _ = save_theme(mode, enabled).await;
-            }
-        });
-    };
-
-    let toggle_light_mode = move |_| {
-        dark_mode.set(false);
-
-        let js = r#"document.getElementById("dark-css").disabled = true; document.getElementById("main-css").disabled = false;"#;
-        eval(js);
-
-        spawn({
-            async move {
-                let _ = save_theme("light".to_string(), false).await;
+        spawn(async move {
+            let mut imported = Vec::new();
+            for file_name in file_engine.files() {
+                let Some(contents) = file_engine.read_file_to_string(&file_name).await else {
+                    theme_import_message.set(Some(Err(tr.t_args("file-read-failed", &[("file", &file_name)]))));
+                    return;
+                };
+                match import_theme(&file_name, &contents) {
+                    Ok(name) => imported.push(name),
+                    Err(e) => {
+                        theme_import_message.set(Some(Err(format!("{:#}", e))));
+                        return;
+                    }
+                }
             }
+            theme_import_message.set(Some(Ok(tr.t_args("themes-imported", &[("names", &imported.join(", "))]))));
+            let mut names = theme_names();
+            names.sort();
+            available_themes.set(names);
         });
     };
 
@@ -61,20 +66,20 @@ pub fn SettingsPage() -> Element {
             div { class: "max-w-3xl mx-auto px-6 py-12",
                 div { class: "bg-[var(--surface)] rounded-xl shadow-lg p-8",
                     div { class: "flex items-center justify-between mb-8",
-                        h1 { class: "text-3xl font-bold text-[var(--primary)]", "Settings" }
+                        h1 { class: "text-3xl font-bold text-[var(--primary)]", {tr.t("settings-title")} }
                         div { class: "flex items-center space-x-4",
                             span { class: "text-[var(--on-surface-variant)]",
                                 if dark_mode() {
-                                    "Dark Mode"
+                                    {tr.t("settings-dark-mode")}
                                 } else {
-                                    "Light Mode"
+                                    {tr.t("settings-light-mode")}
                                 }
                             }
                             button {
                                 onclick: toggle_dark_mode,
                                 class: "relative inline-flex h-6 w-11 items-center rounded-full transition-colors focus:outline-none focus:ring-2 focus:ring-[var(--primary)] focus:ring-offset-2",
                                 class: if dark_mode() { "bg-[var(--primary)]" } else { "bg-[var(--outline-variant)]" },
-                                span { class: "sr-only", "Toggle dark mode" }
+                                span { class: "sr-only", {tr.t("settings-toggle-dark-mode")} }
                                 span {
                                     class: "inline-block h-4 w-4 transform rounded-full bg-white transition-transform",
                                     class: if dark_mode() { "translate-x-6" } else { "translate-x-1" },
@@ -86,7 +91,29 @@ pub fn SettingsPage() -> Element {
                     div { class: "space-y-6",
                         div { class: "border-t border-[var(--outline-variant)] pt-6",
                             h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
-                                "Appearance"
+                                {tr.t("language-title")}
+                            }
+                            select {
+                                aria_label: tr.t("language-title"),
+                                class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                                onchange: move |e| {
+                                    if let Some(locale) = Locale::from_code(&e.value()) {
+                                        settings.update(|settings| settings.locale = locale);
+                                    }
+                                },
+                                for locale in Locale::ALL {
+                                    option {
+                                        value: locale.code(),
+                                        selected: settings.0.read().locale == locale,
+                                        {locale.native_name()}
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "border-t border-[var(--outline-variant)] pt-6",
+                            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                                {tr.t("settings-appearance")}
                             }
                             div { class: "grid grid-cols-2 gap-4",
                                 button {
@@ -95,7 +122,7 @@ pub fn SettingsPage() -> Element {
                                     class: if !dark_mode() { "border-[var(--primary)] bg-[var(--primary-container)]" } else { "border-[var(--outline-variant)] hover:border-[var(--primary)]" },
                                     div { class: "flex flex-col items-center",
                                         div { class: "w-full h-24 mb-2 rounded bg-[var(--surface-bright)] border border-[var(--outline-variant)]" }
-                                        span { "Light" }
+                                        span { {tr.t("settings-light")} }
                                     }
                                 }
                                 button {
@@ -104,11 +131,193 @@ pub fn SettingsPage() -> Element {
                                     class: if dark_mode() { "border-[var(--primary)] bg-[var(--primary-container)]" } else { "border-[var(--outline-variant)] hover:border-[var(--primary)]" },
                                     div { class: "flex flex-col items-center",
                                         div { class: "w-full h-24 mb-2 rounded bg-[var(--surface-dim)] border border-[var(--outline-variant)]" }
-                                        span { "Dark" }
+                                        span { {tr.t("settings-dark")} }
                                     }
                                 }
                             }
                         }
+
+                        PalettesComponent {}
+
+                        div { class: "border-t border-[var(--outline-variant)] pt-6",
+                            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                                {tr.t("themes-title")}
+                            }
+                            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                                {tr.t("themes-description-before")}
+                                " "
+                                code { "theme:" }
+                                " "
+                                {tr.t("themes-description-after")}
+                            }
+                            div { class: "grid grid-cols-[10rem_1fr] gap-3 items-center text-sm mb-4",
+                                label { class: "text-[var(--on-surface)]", r#for: "light-syntax-theme", {tr.t("themes-light")} }
+                                select {
+                                    id: "light-syntax-theme",
+                                    class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    onchange: move |e| settings.update(|settings| settings.light_syntax_theme = e.value()),
+                                    for name in available_themes() {
+                                        option {
+                                            key: "{name}",
+                                            value: "{name}",
+                                            selected: settings.0.read().light_syntax_theme == name,
+                                            "{name}"
+                                        }
+                                    }
+                                }
+                                label { class: "text-[var(--on-surface)]", r#for: "dark-syntax-theme", {tr.t("themes-dark")} }
+                                select {
+                                    id: "dark-syntax-theme",
+                                    class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    onchange: move |e| settings.update(|settings| settings.dark_syntax_theme = e.value()),
+                                    for name in available_themes() {
+                                        option {
+                                            key: "{name}",
+                                            value: "{name}",
+                                            selected: settings.0.read().dark_syntax_theme == name,
+                                            "{name}"
+                                        }
+                                    }
+                                }
+                            }
+                            // Imported themes are saved as files, which the browser build can't write
+                            if cfg!(not(feature = "web")) {
+                                p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                                    {tr.t_args("themes-import-description", &[("dir", &THEMES_DIR)])}
+                                }
+                                label { class: "inline-block cursor-pointer px-4 py-2 rounded-lg bg-[var(--primary)] text-[var(--on-primary)] hover:bg-[var(--tertiary)] hover:text-[var(--on-tertiary)]",
+                                    {tr.t("themes-import")}
+                                    input {
+                                        r#type: "file",
+                                        class: "hidden",
+                                        accept: ".tmTheme",
+                                        multiple: true,
+                                        onchange: handle_theme_import,
+                                    }
+                                }
+                            }
+                            match theme_import_message() {
+                                Some(Ok(msg)) => rsx! {
+                                    div { class: "mt-4 text-sm text-[var(--primary)]", "{msg}" }
+                                },
+                                Some(Err(err)) => rsx! {
+                                    div { class: "mt-4 p-2 rounded-lg bg-[var(--error)] text-[var(--on-error)] text-sm", "{err}" }
+                                },
+                                None => rsx! {},
+                            }
+                        }
+
+                        div { class: "border-t border-[var(--outline-variant)] pt-6",
+                            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                                {tr.t("editor-settings-title")}
+                            }
+                            div { class: "grid grid-cols-[10rem_1fr] gap-3 items-center text-sm",
+                                label { class: "text-[var(--on-surface)]", r#for: "editor-font", {tr.t("editor-font")} }
+                                input {
+                                    id: "editor-font",
+                                    r#type: "text",
+                                    class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    placeholder: "monospace",
+                                    value: "{settings.0.read().editor_font}",
+                                    onchange: move |e| {
+                                        let font = e.value().trim().to_string();
+                                        if !font.is_empty() {
+                                            settings.update(|settings| settings.editor_font = font);
+                                        }
+                                    },
+                                }
+                                label { class: "text-[var(--on-surface)]", r#for: "editor-font-size", {tr.t("editor-font-size")} }
+                                input {
+                                    id: "editor-font-size",
+                                    r#type: "number",
+                                    min: "8",
+                                    max: "40",
+                                    class: "w-24 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    value: "{settings.0.read().editor_font_size}",
+                                    onchange: move |e| {
+                                        if let Ok(size) = e.value().trim().parse::<u32>() {
+                                            settings.update(|settings| settings.editor_font_size = size.clamp(8, 40));
+                                        }
+                                    },
+                                }
+                                label { class: "text-[var(--on-surface)]", r#for: "editor-tab-width", {tr.t("editor-tab-width")} }
+                                select {
+                                    id: "editor-tab-width",
+                                    class: "w-24 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    onchange: move |e| {
+                                        if let Ok(width) = e.value().parse::<u32>() {
+                                            settings.update(|settings| settings.tab_width = width);
+                                        }
+                                    },
+                                    for width in TAB_WIDTHS {
+                                        option {
+                                            value: "{width}",
+                                            selected: settings.0.read().tab_width == width,
+                                            "{width}"
+                                        }
+                                    }
+                                }
+                                label { class: "text-[var(--on-surface)]", r#for: "editor-autosave", {tr.t("editor-autosave")} }
+                                select {
+                                    id: "editor-autosave",
+                                    class: "w-40 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)]",
+                                    onchange: move |e| {
+                                        if let Ok(interval) = e.value().parse::<u32>() {
+                                            settings.update(|settings| settings.autosave_interval = interval);
+                                        }
+                                    },
+                                    for (interval, label) in AUTOSAVE_INTERVALS {
+                                        option {
+                                            value: "{interval}",
+                                            selected: settings.0.read().autosave_interval == interval,
+                                            {tr.t(label)}
+                                        }
+                                    }
+                                }
+                                span { class: "text-[var(--on-surface)]", {tr.t("editor-word-wrap")} }
+                                button {
+                                    class: "relative inline-flex h-6 w-11 items-center rounded-full transition-colors focus:outline-none focus:ring-2 focus:ring-[var(--primary)] focus:ring-offset-2",
+                                    class: if settings.0.read().word_wrap { "bg-[var(--primary)]" } else { "bg-[var(--outline-variant)]" },
+                                    onclick: move |_| settings.update(|settings| settings.word_wrap = !settings.word_wrap),
+                                    span { class: "sr-only", {tr.t("editor-toggle-word-wrap")} }
+                                    span {
+                                        class: "inline-block h-4 w-4 transform rounded-full bg-white transition-transform",
+                                        class: if settings.0.read().word_wrap { "translate-x-6" } else { "translate-x-1" },
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "border-t border-[var(--outline-variant)] pt-6",
+                            h2 { class: "text-xl font-semibold text-[var(--on-surface)] mb-4",
+                                {tr.t("html-policy-title")}
+                            }
+                            p { class: "text-sm text-[var(--on-surface-variant)] mb-4",
+                                {tr.t("html-policy-description")}
+                            }
+                            select {
+                                aria_label: tr.t("html-policy-title"),
+                                class: "w-64 p-2 rounded border border-[var(--outline-variant)] bg-[var(--surface-container-low)] text-[var(--on-surface)] text-sm",
+                                onchange: move |e| {
+                                    if let Some(policy) = SanitizerPolicy::from_code(&e.value()) {
+                                        settings.update(|settings| settings.sanitizer_policy = policy);
+                                    }
+                                },
+                                for (policy, label) in SANITIZER_POLICIES {
+                                    option {
+                                        value: policy.code(),
+                                        selected: settings.0.read().sanitizer_policy == policy,
+                                        {tr.t(label)}
+                                    }
+                                }
+                            }
+                        }
+
+                        LanguageAliasesComponent {}
+
+                        KeyboardShortcutsComponent {}
+
+                        LocalApiComponent {}
                     }
                 }
             }
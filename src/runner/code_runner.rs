@@ -0,0 +1,295 @@
+#[cfg(not(feature = "web"))]
+use anyhow::Context;
+use anyhow::{anyhow, Result};
+use runorna::i18n::{translate, Locale};
+use runorna::syntax::RunSettings;
+use serde::Deserialize;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Duration;
+#[cfg(not(feature = "web"))]
+use rusqlite::{fallible_iterator::FallibleIterator, types::ValueRef, Batch, Connection};
+#[cfg(not(feature = "web"))]
+use std::{path::PathBuf, process::Stdio, time::Instant};
+#[cfg(not(feature = "web"))]
+use tokio::io::{AsyncBufReadExt, BufReader};
+#[cfg(not(feature = "web"))]
+use tokio::process::Command;
+
+pub const DEFAULT_RUN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Messages posted by the preview: its code block toolbar buttons and clicked links.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum CodeBlockAction {
+    // Only indices are trusted: the preview also holds HTML written in the note
+    Run { index: usize },
+    Save { index: usize },
+    // Language guesses of untagged blocks
+    Tag { index: usize },
+    Dismiss { index: usize },
+    Open { href: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "web", allow(dead_code))]
+pub enum RunEvent {
+    Stdout(String),
+    Stderr(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunOutput {
+    pub output: String,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+}
+
+impl RunOutput {
+    pub fn status(&self, locale: Locale) -> String {
+        if self.timed_out {
+            return translate(locale, "run-timed-out", &[]);
+        }
+        match self.exit_code {
+            Some(0) => translate(locale, "run-finished", &[]),
+            Some(code) => translate(locale, "run-exited", &[("code", &code)]),
+            None => translate(locale, "run-terminated", &[]),
+        }
+    }
+}
+
+pub fn run_timeout(settings: &RunSettings) -> Duration {
+    settings.timeout.map(Duration::from_secs).unwrap_or(DEFAULT_RUN_TIMEOUT)
+}
+
+/// Identifies what a confirmed run executes, so editing the code or where it runs asks again.
+pub fn run_fingerprint(lang: &str, code: &str, settings: &RunSettings) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (lang, code, &settings.cwd, &settings.env, &settings.database).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(not(feature = "web"))]
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            PathBuf::from(home).join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn build_command(lang: &str, code: &str, settings: &RunSettings) -> Result<Command> {
+    let mut command = match lang {
+        "bash" | "sh" => Command::new(lang),
+        "python" => Command::new(if cfg!(windows) { "python" } else { "python3" }),
+        _ => return Err(anyhow!("{} code blocks cannot be run", lang)),
+    };
+    command.arg("-c").arg(code);
+
+    if let Some(cwd) = &settings.cwd {
+        command.current_dir(expand_home(cwd));
+    }
+    command.envs(&settings.env);
+    // Its own process group, so a timeout also stops what the block started
+    #[cfg(unix)]
+    command.process_group(0);
+    Ok(command)
+}
+
+#[cfg(not(feature = "web"))]
+fn kill_process_tree(child: &tokio::process::Child) {
+    let Some(pid) = child.id() else {
+        return;
+    };
+    #[cfg(unix)]
+    // SAFETY: only signals the group created for this child by `process_group(0)`
+    unsafe {
+        libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+    }
+    #[cfg(windows)]
+    let _ = std::process::Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .output();
+}
+
+/// Runs a code block on this machine, streaming each output line through `on_event`.
+/// The process is killed once the note's timeout elapses.
+#[cfg(not(feature = "web"))]
+pub async fn run_code_block(
+    lang: &str,
+    code: &str,
+    settings: &RunSettings,
+    mut on_event: impl FnMut(RunEvent),
+) -> Result<RunOutput> {
+    if lang == "sql" {
+        return run_sql(code, settings, on_event).await;
+    }
+    let mut child = build_command(lang, code, settings)?
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {} process", lang))?;
+
+    let mut stdout = BufReader::new(child.stdout.take().context("Missing stdout pipe")?).lines();
+    let mut stderr = BufReader::new(child.stderr.take().context("Missing stderr pipe")?).lines();
+    let deadline = tokio::time::sleep(run_timeout(settings));
+    tokio::pin!(deadline);
+
+    let mut output = RunOutput::default();
+    let (mut stdout_open, mut stderr_open) = (true, true);
+    while stdout_open || stderr_open {
+        tokio::select! {
+            line = stdout.next_line(), if stdout_open => match line {
+                Ok(Some(line)) => {
+                    output.output.push_str(&line);
+                    output.output.push('\n');
+                    on_event(RunEvent::Stdout(line));
+                }
+                _ => stdout_open = false,
+            },
+            line = stderr.next_line(), if stderr_open => match line {
+                Ok(Some(line)) => {
+                    output.output.push_str(&line);
+                    output.output.push('\n');
+                    on_event(RunEvent::Stderr(line));
+                }
+                _ => stderr_open = false,
+            },
+            _ = &mut deadline => {
+                output.timed_out = true;
+                break;
+            }
+        }
+    }
+
+    if !output.timed_out {
+        tokio::select! {
+            status = child.wait() => {
+                output.exit_code = status.ok().and_then(|status| status.code());
+            }
+            _ = &mut deadline => output.timed_out = true,
+        }
+    }
+    if output.timed_out {
+        kill_process_tree(&child);
+        let _ = child.kill().await;
+    }
+
+    Ok(output)
+}
+
+// ```sql blocks run in the bundled SQLite, against the note's `run.database` or in memory.
+#[cfg(not(feature = "web"))]
+async fn run_sql(code: &str, settings: &RunSettings, mut on_event: impl FnMut(RunEvent)) -> Result<RunOutput> {
+    let database = settings.database.as_deref().map(|database| {
+        let path = expand_home(database);
+        match &settings.cwd {
+            Some(cwd) if path.is_relative() => expand_home(cwd).join(path),
+            _ => path,
+        }
+    });
+    let (code, timeout) = (code.to_string(), run_timeout(settings));
+
+    let (lines, error, timed_out) = tokio::task::spawn_blocking(move || {
+        let conn = match &database {
+            Some(path) => Connection::open(path),
+            None => Connection::open_in_memory(),
+        }
+        .context("Failed to open the SQL database")?;
+        let started = Instant::now();
+        conn.progress_handler(10_000, Some(move || started.elapsed() > timeout));
+
+        let mut lines = Vec::new();
+        let mut batch = Batch::new(&conn, &code);
+        let error = loop {
+            let result = batch.next().and_then(|statement| match statement {
+                Some(mut statement) if statement.column_count() > 0 => {
+                    sql_table_lines(&mut statement).map(|table| lines.extend(table)).map(Some)
+                }
+                Some(mut statement) => statement.execute([]).map(|_| Some(())),
+                None => Ok(None),
+            });
+            match result {
+                Ok(Some(_)) => {}
+                Ok(None) => break None,
+                Err(e) => break Some(e.to_string()),
+            }
+        };
+        anyhow::Ok((lines, error, started.elapsed() > timeout))
+    })
+    .await
+    .context("SQL block panicked")??;
+
+    let mut output = RunOutput::default();
+    for line in lines {
+        output.output.push_str(&line);
+        output.output.push('\n');
+        on_event(RunEvent::Stdout(line));
+    }
+    output.timed_out = timed_out;
+    output.exit_code = match error {
+        _ if timed_out => None,
+        Some(error) => {
+            output.output.push_str(&error);
+            output.output.push('\n');
+            on_event(RunEvent::Stderr(error));
+            Some(1)
+        }
+        None => Some(0),
+    };
+    Ok(output)
+}
+
+// A result set as aligned columns under a header, like `sqlite3 -header -column`.
+#[cfg(not(feature = "web"))]
+fn sql_table_lines(statement: &mut rusqlite::Statement) -> rusqlite::Result<Vec<String>> {
+    let columns = statement.column_count();
+    let mut rows = vec![statement.column_names().into_iter().map(str::to_string).collect::<Vec<_>>()];
+    let mut query = statement.query([])?;
+    while let Some(row) = query.next()? {
+        let mut values = Vec::with_capacity(columns);
+        for index in 0..columns {
+            values.push(match row.get_ref(index)? {
+                ValueRef::Null => String::new(),
+                ValueRef::Integer(i) => i.to_string(),
+                ValueRef::Real(f) => f.to_string(),
+                ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+                ValueRef::Blob(blob) => String::from_utf8_lossy(blob).into_owned(),
+            });
+        }
+        rows.push(values);
+    }
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let rule = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut rows = rows.into_iter();
+    let mut lines = vec![line(rows.next().unwrap_or_default()), line(rule)];
+    lines.extend(rows.map(line));
+    Ok(lines)
+}
+
+/// The browser can't start processes, and the web build never offers a Run button.
+#[cfg(feature = "web")]
+pub async fn run_code_block(
+    lang: &str,
+    _code: &str,
+    _settings: &RunSettings,
+    _on_event: impl FnMut(RunEvent),
+) -> Result<RunOutput> {
+    Err(anyhow!("Running {} code needs the desktop app", lang))
+}
@@ -0,0 +1,3 @@
+mod code_runner;
+
+pub use code_runner::*;
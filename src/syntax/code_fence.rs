@@ -0,0 +1,315 @@
+use crate::syntax::{
+    block_attribute, escape_html, is_diagram_lang, markdown_options, needs_language_guess, LanguageGuess,
+    HIGHLIGHT_CLASS_STYLE, SYNTAX_SET,
+};
+use crate::i18n::{translate, ui_locale};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+use std::ops::{Range, RangeInclusive};
+use syntect::html::ClassedHTMLGenerator;
+use syntect::parsing::SyntaxReference;
+use syntect::util::LinesWithEndings;
+
+pub const RUNNABLE_LANGS: &[&str] = &["bash", "sh", "python", "sql"];
+
+/// A parsed fence info string such as ```rust {3,5-7} title="main.rs" linenos
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FenceInfo {
+    pub lang: String,
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+    pub title: Option<String>,
+    pub line_numbers: bool,
+    // ```sql run blocks execute in the note's scratch database while rendering
+    pub run: bool,
+}
+
+impl FenceInfo {
+    pub fn parse(info: &str) -> Self {
+        let mut fence = FenceInfo::default();
+        let mut rest = info.trim();
+
+        while !rest.is_empty() {
+            if let Some(ranges) = rest.strip_prefix('{') {
+                let end = ranges.find('}').unwrap_or(ranges.len());
+                fence.highlighted_lines.extend(parse_line_ranges(&ranges[..end]));
+                rest = ranges.get(end + 1..).unwrap_or_default();
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let (token, after) = rest.split_at(end);
+
+                if let Some((key, value)) = token.split_once('=') {
+                    // Quoted values may contain spaces: title="my file.rs"
+                    let (value, after) = if value.starts_with('"') {
+                        let quoted = &rest[key.len() + 2..];
+                        let close = quoted.find('"').unwrap_or(quoted.len());
+                        (&quoted[..close], quoted.get(close + 1..).unwrap_or_default())
+                    } else {
+                        (value, after)
+                    };
+                    if key == "title" {
+                        fence.title = Some(value.to_string());
+                    }
+                    rest = after;
+                } else {
+                    match token {
+                        "linenos" | "showLineNumbers" | "numberLines" => fence.line_numbers = true,
+                        "run" => fence.run = true,
+                        _ if fence.lang.is_empty() => fence.lang = token.to_string(),
+                        _ => {}
+                    }
+                    rest = after;
+                }
+            }
+            rest = rest.trim_start();
+        }
+        fence
+    }
+
+    pub fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines.iter().any(|range| range.contains(&line))
+    }
+
+    pub fn is_runnable(&self) -> bool {
+        // The browser build has no processes to run code in
+        cfg!(not(feature = "web"))
+            && RUNNABLE_LANGS.contains(&self.lang.as_str())
+            && !self.is_sql_playground()
+    }
+
+    pub fn is_sql_playground(&self) -> bool {
+        self.run && self.lang == "sql"
+    }
+}
+
+fn parse_line_ranges(ranges: &str) -> Vec<RangeInclusive<usize>> {
+    ranges
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            match part.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let line = part.parse().ok()?;
+                    Some(line..=line)
+                }
+            }
+        })
+        .collect()
+}
+
+// Splits classed HTML at its newlines, closing the spans still open at the end of a
+// line and reopening them on the next, so every line is a self-contained fragment.
+fn split_classed_lines(html: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut open_tags: Vec<&str> = Vec::new();
+    let mut line = String::new();
+    let mut rest = html;
+
+    while let Some(position) = rest.find(['<', '\n']) {
+        line.push_str(&rest[..position]);
+        rest = &rest[position..];
+        if let Some(after) = rest.strip_prefix('\n') {
+            line.push_str(&"</span>".repeat(open_tags.len()));
+            lines.push(std::mem::take(&mut line));
+            open_tags.iter().for_each(|tag| line.push_str(tag));
+            rest = after;
+        } else {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = &rest[..end];
+            if tag.starts_with("</") {
+                open_tags.pop();
+            } else {
+                open_tags.push(tag);
+            }
+            line.push_str(tag);
+            rest = &rest[end..];
+        }
+    }
+    line.push_str(rest);
+    lines.push(line);
+    lines
+}
+
+fn highlight_lines(code: &str, syntax: &SyntaxReference) -> Vec<String> {
+    let line_count = LinesWithEndings::from(code).count();
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, HIGHLIGHT_CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if generator.parse_html_for_line_which_includes_newline(line).is_err() {
+            return LinesWithEndings::from(code)
+                .map(|line| escape_html(line.trim_end_matches('\n')))
+                .collect();
+        }
+    }
+
+    let mut lines = split_classed_lines(&generator.finalize());
+    lines.truncate(line_count);
+    lines
+}
+
+/// Highlights a fenced block line by line so each line can be numbered or emphasized,
+/// and wraps it with a header holding the title, language badge and copy button.
+/// A guessed language gets a badge offering to write the tag into the source, and `block`
+/// is the block's index in `fenced_code_blocks`, which its buttons act on.
+pub fn render_fenced_code(
+    code: &str,
+    fence: &FenceInfo,
+    syntax: &SyntaxReference,
+    guess: Option<&LanguageGuess>,
+    block: usize,
+) -> String {
+    let mut body = String::new();
+    for (index, line_html) in highlight_lines(code, syntax).into_iter().enumerate() {
+        let number = index + 1;
+        body.push_str(&format!(
+            r#"<span class="code-line{}" data-line="{}">{}</span>"#,
+            if fence.is_highlighted(number) { " highlighted" } else { "" },
+            number,
+            line_html
+        ));
+    }
+
+    let mut header = String::from(r#"<div class="code-block-header">"#);
+    if let Some(title) = &fence.title {
+        header.push_str(&format!(r#"<span class="code-block-title">{}</span>"#, escape_html(title)));
+    }
+    let t = |key: &str| escape_html(&translate(ui_locale(), key, &[]));
+    if !fence.lang.is_empty() && needs_language_guess(&fence.lang) {
+        header.push_str(&format!(
+            r#"<span class="code-block-lang unresolved" title="{}">{}</span>"#,
+            t("code-unknown-language"),
+            escape_html(&fence.lang)
+        ));
+    } else if !fence.lang.is_empty() {
+        header.push_str(&format!(r#"<span class="code-block-lang">{}</span>"#, escape_html(&fence.lang)));
+    }
+    if let Some(guess) = guess {
+        header.push_str(&format!(
+            r#"<span class="code-guess" title="{detected}">{tag}?<button type="button" class="code-insert-lang" title="{insert_title}">{insert}</button><button type="button" class="code-dismiss-guess" title="{dismiss}">&times;</button></span>"#,
+            tag = escape_html(&guess.tag),
+            detected = t("code-guess-detected"),
+            insert_title = t("code-guess-insert-title"),
+            insert = t("code-guess-insert"),
+            dismiss = t("code-guess-dismiss"),
+        ));
+    }
+    if fence.is_runnable() {
+        header.push_str(&format!(
+            r#"<button type="button" class="code-run" title="{}">{}</button>"#,
+            t("code-run-title"),
+            t("code-run")
+        ));
+    }
+    header.push_str(&format!(
+        r#"<button type="button" class="code-copy" title="{}" data-copied="{}">{}</button></div>"#,
+        t("code-copy-title"),
+        t("code-copied"),
+        t("code-copy")
+    ));
+
+    format!(
+        r#"<div class="code-block" data-block="{}">{}<pre class="code-block-body{}">{}</pre></div>"#,
+        block_attribute(block),
+        header,
+        if fence.line_numbers { " line-numbers" } else { "" },
+        body
+    )
+}
+
+/// A fenced block rendered with the code block toolbar, in preview order.
+pub struct FencedBlock {
+    pub fence: FenceInfo,
+    pub range: Range<usize>,
+    pub code: String,
+}
+
+/// Source ranges of the blocks that `markdown_to_html` renders as `.code-block`,
+/// so the n-th block of the preview maps back to the note.
+pub fn fenced_code_blocks(markdown: &str) -> Vec<FencedBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<FencedBlock> = None;
+    for (event, range) in Parser::new_ext(markdown, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let fence = FenceInfo::parse(&info);
+                current = (!is_diagram_lang(&fence.lang)).then_some(FencedBlock {
+                    fence,
+                    range,
+                    code: String::new(),
+                });
+            }
+            Event::Text(text) => {
+                if let Some(block) = current.as_mut() {
+                    block.code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => blocks.extend(current.take()),
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Rewrites the opening fence of the `index`-th block so its language is `lang`,
+/// keeping the rest of the info string.
+pub fn set_fence_lang(markdown: &str, index: usize, lang: &str) -> Option<String> {
+    let block = fenced_code_blocks(markdown).into_iter().nth(index)?;
+    let fence_line = &markdown[block.range.start..];
+    let fence_line = &fence_line[..fence_line.find('\n').unwrap_or(fence_line.len())];
+    let indent = fence_line.len() - fence_line.trim_start().len();
+    let marker = fence_line[indent..].chars().next()?;
+    let fence_len = fence_line[indent..]
+        .find(|c| c != marker)
+        .unwrap_or(fence_line.len() - indent);
+    let info_start = indent + fence_len;
+    let info = &fence_line[info_start..];
+
+    let new_info = if block.fence.lang.is_empty() {
+        format!("{} {}", lang, info.trim()).trim_end().to_string()
+    } else {
+        info.replacen(&block.fence.lang, lang, 1)
+    };
+    let start = block.range.start + info_start;
+    Some(format!("{}{}{}", &markdown[..start], new_info, &markdown[start + info.len()..]))
+}
+
+/// Writes `output` as an ```output block right after the `index`-th fenced block,
+/// replacing the output block a previous run left there.
+pub fn insert_output_block(markdown: &str, index: usize, output: &str) -> Option<String> {
+    let blocks = fenced_code_blocks(markdown);
+    let block = blocks.get(index)?;
+
+    // The fence must be longer than any backtick run inside the output
+    let longest_run = output
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let mut body = output.to_string();
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    let output_block = format!("{fence}output\n{body}{fence}\n");
+
+    let end = block.range.end;
+    let mut replaced = end;
+    if let Some(next) = blocks.get(index + 1) {
+        if next.fence.lang == "output" && markdown[end..next.range.start].trim().is_empty() {
+            replaced = next.range.end;
+        }
+    }
+
+    let mut updated = String::with_capacity(markdown.len() + output_block.len() + 2);
+    updated.push_str(&markdown[..end]);
+    if !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push('\n');
+    updated.push_str(&output_block);
+    let rest = markdown[replaced..].trim_start_matches('\n');
+    if !rest.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(rest);
+    Some(updated)
+}
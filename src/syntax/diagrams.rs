@@ -0,0 +1,390 @@
+use crate::i18n::{translate, ui_locale};
+use crate::syntax::escape_html;
+use layout::backends::svg::SVGWriter;
+use layout::gv::{DotParser, GraphBuilder};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+pub fn is_diagram_lang(lang: &str) -> bool {
+    matches!(lang, "dot" | "graphviz" | "mermaid")
+}
+
+/// Renders a ```dot, ```graphviz or ```mermaid fence to inline SVG.
+/// Parse errors are shown next to the original source instead.
+pub fn render_diagram(lang: &str, source: &str) -> String {
+    let result = match lang {
+        "mermaid" => mermaid_to_svg(source),
+        _ => dot_to_svg(source),
+    };
+
+    match result {
+        Ok(svg) => format!(
+            r#"<div class="diagram">{}</div>"#,
+            scope_svg_ids(&svg, source)
+        ),
+        Err(e) => format!(
+            r#"<div class="diagram-error"><p>{}</p><pre><code>{}</code></pre></div>"#,
+            escape_html(&translate(ui_locale(), "diagram-error", &[("error", &e)])),
+            escape_html(source)
+        ),
+    }
+}
+
+fn dot_to_svg(source: &str) -> Result<String, String> {
+    // The parser and layout engine panic on some malformed or degenerate graphs; never take
+    // the preview down with them.
+    catch_unwind(AssertUnwindSafe(|| {
+        let graph = DotParser::new(source).process()?;
+        let mut builder = GraphBuilder::new();
+        builder.visit_graph(&graph);
+        let mut visual_graph = builder.get();
+        if visual_graph.num_nodes() == 0 {
+            return Err("The graph has no nodes".to_string());
+        }
+        let mut writer = SVGWriter::new();
+        visual_graph.do_it(false, false, false, &mut writer);
+        Ok(theme_colors(&writer.finalize()))
+    }))
+    .map_err(|_| "The graph could not be laid out".to_string())?
+}
+
+// The layout engine draws in black on white; use the surrounding text color instead so
+// diagrams stay readable with dark themes. Colors set in the DOT source are kept.
+fn theme_colors(svg: &str) -> String {
+    svg.replace(r##"stroke="#000000ff""##, r#"stroke="currentColor""#)
+        .replace(r##"fill="#ffffffff""##, r#"fill="none""#)
+        .replace("<text ", r#"<text fill="currentColor" "#)
+        .replace("<text>", r#"<text fill="currentColor">"#)
+}
+
+// Element ids such as `arrow0` are only unique within one SVG; prefix them per diagram
+// so several diagrams can live in one preview.
+fn scope_svg_ids(svg: &str, source: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    let prefix = format!("d{:x}-", hasher.finish());
+
+    let svg = svg.trim_start_matches(r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#);
+    svg.replace("id=\"", &format!("id=\"{}", prefix))
+        .replace("url(#", &format!("url(#{}", prefix))
+        .replace("href=\"#", &format!("href=\"#{}", prefix))
+}
+
+fn mermaid_to_svg(source: &str) -> Result<String, String> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"));
+
+    let header = lines.next().ok_or("Empty diagram")?;
+    let mut header_words = header.split_whitespace();
+    match header_words.next() {
+        Some("graph") | Some("flowchart") => {
+            let direction = header_words.next().unwrap_or("TD");
+            dot_to_svg(&flowchart_to_dot(direction, lines)?)
+        }
+        Some("sequenceDiagram") => sequence_to_svg(lines),
+        _ => Err(format!(
+            "Unsupported diagram type '{}', expected flowchart, graph or sequenceDiagram",
+            header
+        )),
+    }
+}
+
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Parses `A`, `A[Label]`, `A(Label)` or `A((Label))` into (id, optional node statement).
+fn flowchart_node(text: &str) -> Result<(String, Option<String>), String> {
+    let text = text.trim();
+    let id_end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    let (id, rest) = text.split_at(id_end);
+    if id.is_empty() {
+        return Err(format!("Expected a node id in '{}'", text));
+    }
+
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return Ok((id.to_string(), None));
+    }
+
+    let (label, shape) = if let Some(label) = rest.strip_prefix("((").and_then(|r| r.strip_suffix("))")) {
+        (label, "circle")
+    } else if let Some(label) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        (label, "box")
+    } else if let Some(label) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        (label, "box")
+    } else if let Some(label) = rest.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+        (label, "box")
+    } else {
+        return Err(format!("Unrecognized node shape in '{}'", text));
+    };
+
+    let statement = format!(
+        "{} [label={}, shape={}];",
+        dot_quote(id),
+        dot_quote(label.trim().trim_matches('"')),
+        shape
+    );
+    Ok((id.to_string(), Some(statement)))
+}
+
+fn flowchart_to_dot<'a>(direction: &str, lines: impl Iterator<Item = &'a str>) -> Result<String, String> {
+    const EDGES: [&str; 6] = ["-.->", "==>", "-->", "---", "-.-", "--"];
+
+    let rankdir = match direction {
+        "LR" | "RL" => "LR",
+        "TD" | "TB" | "BT" => "TB",
+        other => return Err(format!("Unsupported flowchart direction '{}'", other)),
+    };
+
+    let mut dot = format!("digraph {{\nrankdir={};\nnode [shape=box];\n", rankdir);
+    for (number, line) in lines.enumerate() {
+        let line = line.trim_end_matches(';');
+
+        let Some((position, edge)) = EDGES
+            .iter()
+            .filter_map(|edge| line.find(edge).map(|pos| (pos, *edge)))
+            .min_by_key(|(pos, edge)| (*pos, std::cmp::Reverse(edge.len())))
+        else {
+            let (_, statement) = flowchart_node(line)
+                .map_err(|e| format!("Line {}: {}", number + 2, e))?;
+            if let Some(statement) = statement {
+                dot.push_str(&statement);
+                dot.push('\n');
+            }
+            continue;
+        };
+
+        let from = &line[..position];
+        let mut rest = &line[position + edge.len()..];
+        let mut label = None;
+
+        // `A -- text --> B` form
+        if edge == "--" {
+            let Some(end) = rest.find("-->") else {
+                return Err(format!("Line {}: unterminated edge label", number + 2));
+            };
+            label = Some(rest[..end].trim().to_string());
+            rest = &rest[end + 3..];
+        }
+        // `A -->|text| B` form
+        let rest = rest.trim_start();
+        let rest = if let Some(labelled) = rest.strip_prefix('|') {
+            let end = labelled
+                .find('|')
+                .ok_or_else(|| format!("Line {}: unterminated edge label", number + 2))?;
+            label = Some(labelled[..end].trim().to_string());
+            &labelled[end + 1..]
+        } else {
+            rest
+        };
+
+        let (from_id, from_statement) = flowchart_node(from).map_err(|e| format!("Line {}: {}", number + 2, e))?;
+        let (to_id, to_statement) = flowchart_node(rest).map_err(|e| format!("Line {}: {}", number + 2, e))?;
+        for statement in [from_statement, to_statement].into_iter().flatten() {
+            dot.push_str(&statement);
+            dot.push('\n');
+        }
+
+        let mut attributes = Vec::new();
+        if let Some(label) = label {
+            attributes.push(format!("label={}", dot_quote(&label)));
+        }
+        if edge.contains('.') {
+            attributes.push("style=dashed".to_string());
+        }
+        dot.push_str(&format!("{} -> {}", dot_quote(&from_id), dot_quote(&to_id)));
+        if !attributes.is_empty() {
+            dot.push_str(&format!(" [{}]", attributes.join(", ")));
+        }
+        dot.push_str(";\n");
+    }
+    dot.push('}');
+    Ok(dot)
+}
+
+enum SequenceStep {
+    Message {
+        from: usize,
+        to: usize,
+        text: String,
+        dashed: bool,
+    },
+    Note {
+        over: usize,
+        text: String,
+    },
+}
+
+const PARTICIPANT_WIDTH: f64 = 120.;
+const PARTICIPANT_GAP: f64 = 40.;
+const PARTICIPANT_HEIGHT: f64 = 36.;
+const ROW_HEIGHT: f64 = 44.;
+
+fn sequence_to_svg<'a>(lines: impl Iterator<Item = &'a str>) -> Result<String, String> {
+    const ARROWS: [(&str, bool); 6] = [
+        ("-->>", true),
+        ("->>", false),
+        ("--x", true),
+        ("-x", false),
+        ("-->", true),
+        ("->", false),
+    ];
+
+    // (id, label)
+    let mut participants: Vec<(String, String)> = Vec::new();
+    let mut steps = Vec::new();
+
+    fn participant_index(participants: &mut Vec<(String, String)>, id: &str) -> usize {
+        if let Some(index) = participants.iter().position(|(p, _)| p == id) {
+            return index;
+        }
+        participants.push((id.to_string(), id.to_string()));
+        participants.len() - 1
+    }
+
+    for (number, line) in lines.enumerate() {
+        if line == "autonumber" {
+            continue;
+        }
+        if let Some(rest) = line
+            .strip_prefix("participant ")
+            .or_else(|| line.strip_prefix("actor "))
+        {
+            let (id, label) = match rest.split_once(" as ") {
+                Some((id, label)) => (id.trim(), label.trim()),
+                None => (rest.trim(), rest.trim()),
+            };
+            let index = participant_index(&mut participants, id);
+            participants[index].1 = label.to_string();
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("Note ").or_else(|| line.strip_prefix("note ")) {
+            let (target, text) = rest
+                .split_once(':')
+                .ok_or_else(|| format!("Line {}: expected ':' in note", number + 2))?;
+            let target = target
+                .trim()
+                .trim_start_matches("over ")
+                .trim_start_matches("left of ")
+                .trim_start_matches("right of ");
+            let first = target.split(',').next().unwrap_or(target).trim();
+            let over = participant_index(&mut participants, first);
+            steps.push(SequenceStep::Note {
+                over,
+                text: text.trim().to_string(),
+            });
+            continue;
+        }
+
+        let Some((head, text)) = line.split_once(':') else {
+            return Err(format!("Line {}: unrecognized statement '{}'", number + 2, line));
+        };
+        let Some((arrow, dashed)) = ARROWS.iter().find(|(arrow, _)| head.contains(arrow)) else {
+            return Err(format!("Line {}: expected an arrow such as ->> in '{}'", number + 2, line));
+        };
+        let (from, to) = head.split_once(arrow).unwrap_or_default();
+        let (from, to) = (from.trim(), to.trim().trim_start_matches(['+', '-']));
+        if from.is_empty() || to.is_empty() {
+            return Err(format!("Line {}: message needs a sender and a receiver", number + 2));
+        }
+        let from = participant_index(&mut participants, from);
+        let to = participant_index(&mut participants, to);
+        steps.push(SequenceStep::Message {
+            from,
+            to,
+            text: text.trim().to_string(),
+            dashed: *dashed,
+        });
+    }
+
+    if participants.is_empty() {
+        return Err("The sequence diagram has no participants".to_string());
+    }
+
+    let center = |index: usize| 10. + PARTICIPANT_WIDTH / 2. + index as f64 * (PARTICIPANT_WIDTH + PARTICIPANT_GAP);
+    let width = 20. + participants.len() as f64 * (PARTICIPANT_WIDTH + PARTICIPANT_GAP) - PARTICIPANT_GAP;
+    let lifeline_end = 10. + PARTICIPANT_HEIGHT + (steps.len() as f64 + 1.) * ROW_HEIGHT;
+    let height = lifeline_end + PARTICIPANT_HEIGHT + 10.;
+
+    let mut svg = format!(
+        r#"<svg width="{w}" height="{h}" viewBox="0 0 {w} {h}" xmlns="http://www.w3.org/2000/svg"><defs><marker id="seqarrow" markerWidth="10" markerHeight="7" refX="10" refY="3.5" orient="auto"><polygon points="0 0, 10 3.5, 0 7" fill="currentColor"></polygon></marker></defs>"#,
+        w = width,
+        h = height
+    );
+
+    for (index, (_, label)) in participants.iter().enumerate() {
+        let x = center(index);
+        for y in [10., lifeline_end] {
+            svg.push_str(&format!(
+                r##"<rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="none" stroke="currentColor" stroke-width="1"></rect><text x="{}" y="{}" fill="currentColor" text-anchor="middle" dominant-baseline="middle" font-size="14">{}</text>"##,
+                x - PARTICIPANT_WIDTH / 2.,
+                y,
+                PARTICIPANT_WIDTH,
+                PARTICIPANT_HEIGHT,
+                x,
+                y + PARTICIPANT_HEIGHT / 2.,
+                escape_html(label)
+            ));
+        }
+        svg.push_str(&format!(
+            r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="gray" stroke-width="1" stroke-dasharray="4,4"></line>"#,
+            10. + PARTICIPANT_HEIGHT,
+            lifeline_end,
+            x = x
+        ));
+    }
+
+    for (row, step) in steps.iter().enumerate() {
+        let y = 10. + PARTICIPANT_HEIGHT + (row as f64 + 1.) * ROW_HEIGHT;
+        match step {
+            SequenceStep::Message { from, to, text, dashed } => {
+                let dash = if *dashed { r#" stroke-dasharray="5,5""# } else { "" };
+                let (x1, x2) = (center(*from), center(*to));
+                if from == to {
+                    svg.push_str(&format!(
+                        r#"<path d="M {x1} {y0} C {x3} {y0}, {x3} {y}, {x1} {y}" fill="none" stroke="currentColor" stroke-width="1"{dash} marker-end="url(#seqarrow)"></path><text x="{tx}" y="{ty}" fill="currentColor" font-size="12">{text}</text>"#,
+                        x1 = x1,
+                        x3 = x1 + 40.,
+                        y0 = y - 16.,
+                        y = y,
+                        dash = dash,
+                        tx = x1 + 46.,
+                        ty = y - 8.,
+                        text = escape_html(text)
+                    ));
+                } else {
+                    svg.push_str(&format!(
+                        r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="currentColor" stroke-width="1"{} marker-end="url(#seqarrow)"></line><text x="{}" y="{}" fill="currentColor" text-anchor="middle" font-size="12">{}</text>"#,
+                        x1,
+                        x2,
+                        dash,
+                        (x1 + x2) / 2.,
+                        y - 6.,
+                        escape_html(text),
+                        y = y
+                    ));
+                }
+            }
+            SequenceStep::Note { over, text } => {
+                let x = center(*over);
+                svg.push_str(&format!(
+                    r##"<rect x="{}" y="{}" width="{}" height="26" fill="#fff8c5" stroke="#d4a72c" stroke-width="1"></rect><text x="{}" y="{}" fill="#3b2f00" text-anchor="middle" dominant-baseline="middle" font-size="12">{}</text>"##,
+                    x - PARTICIPANT_WIDTH / 2.,
+                    y - 18.,
+                    PARTICIPANT_WIDTH,
+                    x,
+                    y - 5.,
+                    escape_html(text)
+                ));
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
@@ -0,0 +1,144 @@
+use crate::i18n::{translate, ui_locale};
+use crate::syntax::{escape_html, markdown_options};
+use std::fmt::Display;
+use pulldown_cmark::{Event, MetadataBlockKind, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Note metadata from a `+++` TOML or `---` YAML front matter block.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoteMeta {
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub language: Option<String>,
+    pub aliases: Vec<String>,
+    // Syntax theme pinned for this note, overriding the global preference
+    pub theme: Option<String>,
+    pub run: RunSettings,
+}
+
+/// Local execution of runnable code blocks, off unless the note sets `run.enabled`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunSettings {
+    pub enabled: bool,
+    // Seconds before the process is killed
+    pub timeout: Option<u64>,
+    pub cwd: Option<String>,
+    pub env: BTreeMap<String, String>,
+    // SQLite database for ```sql blocks, in memory when unset
+    pub database: Option<String>,
+}
+
+impl NoteMeta {
+    pub fn is_empty(&self) -> bool {
+        *self == NoteMeta::default()
+    }
+}
+
+pub fn parse_front_matter(kind: MetadataBlockKind, source: &str) -> Result<NoteMeta, String> {
+    if source.trim().is_empty() {
+        return Ok(NoteMeta::default());
+    }
+    match kind {
+        MetadataBlockKind::PlusesStyle => toml::from_str(source).map_err(|e| e.message().to_string()),
+        MetadataBlockKind::YamlStyle => serde_yaml::from_str(source).map_err(|e| e.to_string()),
+    }
+}
+
+/// Metadata of the note's front matter block, or the default if it has none.
+pub fn note_meta(markdown: &str) -> Result<NoteMeta, String> {
+    let mut parser = Parser::new_ext(markdown, markdown_options());
+    let Some(Event::Start(Tag::MetadataBlock(kind))) = parser.next() else {
+        return Ok(NoteMeta::default());
+    };
+
+    let mut source = String::new();
+    for event in parser {
+        match event {
+            Event::Text(text) => source.push_str(&text),
+            Event::End(TagEnd::MetadataBlock(_)) => break,
+            _ => {}
+        }
+    }
+    parse_front_matter(kind, &source)
+}
+
+/// The note with `title` set in its front matter, adding a YAML block if it has none.
+pub fn with_title(markdown: &str, title: &str) -> Result<String, String> {
+    let meta = note_meta(markdown).map_err(|e| format!("Invalid front matter: {}", e))?;
+    if meta.title.is_some() {
+        return Err("The note already has a title in its front matter".to_string());
+    }
+    let kind = match Parser::new_ext(markdown, markdown_options()).next() {
+        Some(Event::Start(Tag::MetadataBlock(kind))) => Some(kind),
+        _ => None,
+    };
+    // Keep the note's line endings so a CRLF block stays a single block
+    let newline = if markdown.split('\n').next().is_some_and(|line| line.ends_with('\r')) { "\r\n" } else { "\n" };
+    let yaml_title = serde_yaml::to_string(&BTreeMap::from([("title", title)]))
+        .map_err(|e| e.to_string())?
+        .replace('\n', newline);
+
+    match kind {
+        Some(MetadataBlockKind::YamlStyle) => {
+            let rest = markdown.split_once('\n').map_or("", |(_, rest)| rest);
+            Ok(format!("---{}{}{}", newline, yaml_title, rest))
+        }
+        Some(MetadataBlockKind::PlusesStyle) => {
+            let rest = markdown.split_once('\n').map_or("", |(_, rest)| rest);
+            let toml_title = format!("title = {}", toml::Value::String(title.to_string()));
+            Ok(format!("+++{}{}{}{}", newline, toml_title, newline, rest))
+        }
+        None => Ok(format!("---{}{}---{}{}{}", newline, yaml_title, newline, newline, markdown)),
+    }
+}
+
+pub fn render_note_meta(meta: &Result<NoteMeta, String>) -> String {
+    let t = |key: &str, args: &[(&str, &dyn Display)]| escape_html(&translate(ui_locale(), key, args));
+    let meta = match meta {
+        Ok(meta) if meta.is_empty() => return String::new(),
+        Ok(meta) => meta,
+        Err(e) => {
+            return format!(
+                r#"<div class="note-meta note-meta-error">{}</div>"#,
+                t("note-meta-error", &[("error", e)])
+            );
+        }
+    };
+
+    let mut html = String::from(r#"<div class="note-meta">"#);
+    if let Some(title) = &meta.title {
+        html.push_str(&format!(r#"<div class="note-meta-title">{}</div>"#, escape_html(title)));
+    }
+    if !meta.tags.is_empty() {
+        html.push_str(r#"<div class="note-meta-tags">"#);
+        for tag in &meta.tags {
+            html.push_str(&format!(r#"<span class="note-tag">#{}</span>"#, escape_html(tag)));
+        }
+        html.push_str("</div>");
+    }
+
+    let mut details = Vec::new();
+    if let Some(language) = &meta.language {
+        details.push(t("note-meta-language", &[("language", language)]));
+    }
+    if !meta.aliases.is_empty() {
+        details.push(t("note-meta-aliases", &[("aliases", &meta.aliases.join(", "))]));
+    }
+    if let Some(theme) = &meta.theme {
+        details.push(t("note-meta-theme", &[("theme", theme)]));
+    }
+    if meta.run.enabled {
+        details.push(t("note-meta-run-enabled", &[]));
+    }
+    if !details.is_empty() {
+        html.push_str(&format!(
+            r#"<div class="note-meta-details">{}</div>"#,
+            details.join(" · ")
+        ));
+    }
+    html.push_str("</div>");
+    html
+}
@@ -1,23 +1,131 @@
 use pulldown_cmark::Options;
 use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
-use std::sync::LazyLock;
-use syntect::highlighting::ThemeSet;
-use syntect::html::highlighted_html_for_string;
+use crate::syntax::{
+    anchor_headings, detect_language, is_diagram_lang, needs_language_guess, render_fenced_code, FenceInfo,
+    HeadingEntry, LanguageGuess, latex_to_mathml, parse_front_matter, render_diagram, render_note_meta, resolve_syntax,
+};
+#[cfg(not(feature = "web"))]
+use crate::syntax::{clear_sql_results, SqlPlayground};
+#[cfg(feature = "web")]
+use crate::i18n::{translate, ui_locale};
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
 use syntect::parsing::SyntaxSet;
 
-pub fn markdown_to_html(markdown: &str, theme_name: &str) -> String {
-    static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
-    static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+pub static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
 
-    let theme = THEME_SET
-        .themes
-        .get(theme_name)
-        .unwrap_or_else(|| &THEME_SET.themes["base16-eighties.dark"]);
+const HIGHLIGHT_CACHE_CAPACITY: usize = 1024;
 
-    let mut sr = SYNTAX_SET.find_syntax_plain_text();
-    let mut code = String::new();
-    let mut code_block = false;
+// Info string, content hash and block index of a rendered fenced block.
+type HighlightKey = (String, u64, usize);
+
+// Rendered HTML of fenced blocks, so typing outside a code block never re-runs syntect
+// or the diagram layout on it.
+static HIGHLIGHT_CACHE: LazyLock<Mutex<HashMap<HighlightKey, String>>> =
+    LazyLock::new(Default::default);
+
+// Hashes of code blocks whose language guess the user dismissed this session.
+static DISMISSED_GUESSES: LazyLock<Mutex<HashSet<u64>>> = LazyLock::new(Default::default);
+
+fn code_hash(code: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn render_math(tex: &str, display: bool) -> String {
+    match latex_to_mathml(tex, display) {
+        Ok(mathml) => mathml,
+        Err(e) => format!(
+            r#"<span class="math-error" title="{}"><code>{}</code> <em>{}</em></span>"#,
+            escape_html(&e),
+            escape_html(tex),
+            escape_html(&e),
+        ),
+    }
+}
+
+pub fn clear_highlight_cache() {
+    HIGHLIGHT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+    #[cfg(not(feature = "web"))]
+    clear_sql_results();
+}
+
+/// Stops guessing the language of this code block and renders it as plain text.
+pub fn dismiss_language_guess(code: &str) {
+    let hash = code_hash(code);
+    DISMISSED_GUESSES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(hash);
+    HIGHLIGHT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(_, cached_hash, _), _| *cached_hash != hash);
+}
+
+/// The language the preview offers for a block tagged `lang`, unless the guess was dismissed.
+pub fn language_guess(lang: &str, code: &str) -> Option<LanguageGuess> {
+    let dismissed = DISMISSED_GUESSES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(&code_hash(code));
+    (needs_language_guess(lang) && !dismissed)
+        .then(|| detect_language(code))
+        .flatten()
+}
+
+fn render_code_block(code: &str, info: &str, block: usize) -> String {
+    let key = (info.to_string(), code_hash(code), block);
+
+    if let Some(html) = HIGHLIGHT_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key)
+    {
+        return html.clone();
+    }
+
+    let fence = FenceInfo::parse(info);
+    let html = if is_diagram_lang(&fence.lang) {
+        render_diagram(&fence.lang, code)
+    } else {
+        let guess = language_guess(&fence.lang, code);
+        let syntax = match &guess {
+            Some(guess) => guess.syntax,
+            None => resolve_syntax(&fence.lang).unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text()),
+        };
+        render_fenced_code(code, &fence, syntax, guess.as_ref(), block)
+    };
+
+    let mut cache = HIGHLIGHT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= HIGHLIGHT_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(key, html.clone());
+    html
+}
 
+pub fn markdown_options() -> Options {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_TASKLISTS);
@@ -28,33 +136,101 @@ pub fn markdown_to_html(markdown: &str, theme_name: &str) -> String {
     options.insert(Options::ENABLE_SMART_PUNCTUATION);
     options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
     options.insert(Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS);
+    options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
     options.insert(Options::ENABLE_SUBSCRIPT);
     options.insert(Options::ENABLE_SUPERSCRIPT);
+    options
+}
 
-    let parser = Parser::new_ext(markdown, options).filter_map(|event| match event {
-        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
-            let lang = lang.trim();
-            sr = SYNTAX_SET
-                .find_syntax_by_token(&lang)
-                .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+/// A note rendered in one pass over its markdown.
+pub struct RenderedMarkdown {
+    pub html: String,
+    /// Headings with the ids they carry in `html`.
+    pub outline: Vec<HeadingEntry>,
+}
+
+/// Renders a note to HTML. Code blocks carry highlighting classes only, their colors
+/// come from the `theme_stylesheet` of the active theme.
+pub fn markdown_to_html(markdown: &str) -> String {
+    render_markdown(markdown).html
+}
+
+// The browser build has no SQLite to run playground blocks with.
+#[cfg(feature = "web")]
+fn sql_playground_unavailable() -> String {
+    format!(
+        r#"<div class="sql-result"><div class="sql-summary">{}</div></div>"#,
+        escape_html(&translate(ui_locale(), "sql-unavailable", &[]))
+    )
+}
+
+/// Renders a note to HTML and collects its outline from the same parse.
+pub fn render_markdown(markdown: &str) -> RenderedMarkdown {
+    let mut code_info = String::new();
+    let mut code = String::new();
+    let mut code_block = false;
+    // Index of the next block in `fenced_code_blocks`, which leaves out diagrams
+    let mut block_index = 0;
+    let mut metadata_block = false;
+    let mut metadata = String::new();
+    #[cfg(not(feature = "web"))]
+    let mut sql_playground = SqlPlayground::default();
+
+    let mut events: Vec<_> = Parser::new_ext(markdown, markdown_options())
+        .into_offset_iter()
+        .collect();
+    let outline = anchor_headings(&mut events);
+
+    let parser = events.into_iter().filter_map(|(event, _)| match event {
+        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+            code_info = info.trim().to_string();
             code_block = true;
             None
         }
-        Event::End(TagEnd::CodeBlock) => {
-            let html = highlighted_html_for_string(&code, &SYNTAX_SET, &sr, theme)
-                .unwrap_or(code.clone());
+        // Indented blocks stay plain `<pre><code>`, without the toolbar of fenced ones
+        Event::End(TagEnd::CodeBlock) if code_block => {
+            let html = render_code_block(&code, &code_info, block_index);
+            if !is_diagram_lang(&FenceInfo::parse(&code_info).lang) {
+                block_index += 1;
+            }
+            // Results depend on the blocks before this one, the playground caches them itself
+            #[cfg(not(feature = "web"))]
+            let html = if FenceInfo::parse(&code_info).is_sql_playground() {
+                html + &sql_playground.execute(&code)
+            } else {
+                html
+            };
+            #[cfg(feature = "web")]
+            let html = if FenceInfo::parse(&code_info).is_sql_playground() {
+                html + &sql_playground_unavailable()
+            } else {
+                html
+            };
             code.clear();
             code_block = false;
             Some(Event::Html(html.into()))
         }
+        Event::Start(Tag::MetadataBlock(_)) => {
+            metadata_block = true;
+            None
+        }
+        Event::End(TagEnd::MetadataBlock(kind)) => {
+            let meta = parse_front_matter(kind, &metadata);
+            metadata_block = false;
+            metadata.clear();
+            Some(Event::Html(render_note_meta(&meta).into()))
+        }
         Event::Code(code) => Some(Event::Html(
             format!(r#"<code class="inline">{}</code>"#, code).into(),
         )),
         Event::Html(html) => Some(Event::Html(html)),
-        Event::DisplayMath(text) => Some(Event::Html(
-            format!(r#"<span class="math">{}</span>"#, text).into(),
-        )),
+        Event::InlineMath(text) => Some(Event::Html(render_math(&text, false).into())),
+        Event::DisplayMath(text) => Some(Event::Html(render_math(&text, true).into())),
         Event::Text(t) => {
+            if metadata_block {
+                metadata.push_str(&t);
+                return None;
+            }
             if code_block {
                 code.push_str(&t);
                 return None;
@@ -66,5 +242,8 @@ pub fn markdown_to_html(markdown: &str, theme_name: &str) -> String {
 
     let mut html_output = String::new();
     pulldown_cmark::html::push_html(&mut html_output, parser);
-    html_output
+    RenderedMarkdown {
+        html: html_output,
+        outline,
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use crate::i18n::{translate, ui_locale};
+use crate::syntax::{
+    escape_html, markdown_outline, note_meta, render_markdown, theme_stylesheet, HeadingEntry,
+    SanitizerPolicy,
+    DEFAULT_THEME,
+};
+
+const MAIN_CSS: &str = include_str!("../../assets/main.css");
+const DARK_CSS: &str = include_str!("../../assets/dark.css");
+
+// Stand-in for the Tailwind `prose` styles of the preview, which exports cannot load.
+const EXPORT_CSS: &str = r#"
+body {
+    margin: 0;
+    font-family: system-ui, -apple-system, "Segoe UI", sans-serif;
+    line-height: 1.6;
+    color: var(--on-surface);
+    background-color: var(--surface-container-lowest, var(--background));
+}
+
+main {
+    max-width: 56rem;
+    margin: 0 auto;
+    padding: 2rem 1.5rem 4rem;
+}
+
+a {
+    color: var(--primary);
+}
+
+pre, code {
+    font-family: ui-monospace, "SFMono-Regular", Menlo, monospace;
+}
+
+code.inline {
+    padding: 0.1rem 0.3rem;
+    border-radius: 4px;
+    background-color: var(--surface-container-high);
+}
+
+blockquote {
+    margin-left: 0;
+    padding-left: 1rem;
+    border-left: 3px solid var(--outline-variant);
+}
+
+table {
+    border-collapse: collapse;
+}
+
+th, td {
+    padding: 0.25rem 0.75rem;
+    border: 1px solid var(--outline-variant);
+}
+
+img {
+    max-width: 100%;
+}
+
+.code-copy, .code-run, .code-insert-lang, .code-dismiss-guess {
+    display: none;
+}
+
+.export-index li {
+    margin: 0.3rem 0;
+}
+"#;
+
+/// Title used for exported notes: front matter title, then first heading.
+pub fn note_title(markdown: &str) -> String {
+    let title = note_meta(markdown).ok().and_then(|meta| meta.title);
+    match title {
+        Some(title) => title_or_untitled(Some(title), &[]),
+        None => title_or_untitled(None, &markdown_outline(markdown)),
+    }
+}
+
+fn title_or_untitled(title: Option<String>, outline: &[HeadingEntry]) -> String {
+    title
+        .or_else(|| outline.first().map(|heading| heading.title.clone()))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| translate(ui_locale(), "export-untitled-note", &[]))
+}
+
+fn standalone_document(title: &str, theme_name: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{}</title>
+<style>
+{}
+@media (prefers-color-scheme: dark) {{
+{}
+}}
+{}
+{}
+</style>
+</head>
+<body>
+<main>
+{}
+</main>
+</body>
+</html>
+"#,
+        ui_locale().code(),
+        escape_html(title),
+        MAIN_CSS,
+        DARK_CSS,
+        EXPORT_CSS,
+        theme_stylesheet(theme_name),
+        body
+    )
+}
+
+/// Renders a note into one self-contained HTML document, sanitized like the preview.
+pub fn note_to_standalone_html(markdown: &str, theme_name: &str, policy: SanitizerPolicy) -> String {
+    let meta = note_meta(markdown).unwrap_or_default();
+    let rendered = render_markdown(markdown);
+    // A theme pinned in the note's front matter wins, as in the preview
+    let theme_name = meta.theme.unwrap_or_else(|| theme_name.to_string());
+    let body = policy.clean(&rendered.html);
+    standalone_document(&title_or_untitled(meta.title, &rendered.outline), &theme_name, &body)
+}
+
+/// Index page of a folder export; `sections` are (folder name, [(note title, file name)]).
+pub fn folder_index_html(title: &str, sections: &[(String, Vec<(String, String)>)]) -> String {
+    let mut body = format!(
+        r#"<div class="export-index"><h1>{}</h1>"#,
+        escape_html(title)
+    );
+    for (folder_name, notes) in sections {
+        if notes.is_empty() {
+            continue;
+        }
+        if sections.len() > 1 {
+            body.push_str(&format!("<h2>{}</h2>", escape_html(folder_name)));
+        }
+        body.push_str("<ul>");
+        for (note_title, file_name) in notes {
+            body.push_str(&format!(
+                r#"<li><a href="{}">{}</a></li>"#,
+                escape_html(file_name),
+                escape_html(note_title)
+            ));
+        }
+        body.push_str("</ul>");
+    }
+    body.push_str("</div>");
+    standalone_document(title, DEFAULT_THEME, &body)
+}
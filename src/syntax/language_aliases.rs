@@ -0,0 +1,65 @@
+use crate::syntax::{clear_highlight_cache, SYNTAX_SET};
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, RwLock};
+use syntect::parsing::SyntaxReference;
+
+/// Fence tags that syntect doesn't know, mapped to the closest syntax it has.
+pub const DEFAULT_LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("ts", "javascript"),
+    ("tsx", "javascript"),
+    ("typescript", "javascript"),
+    ("jsx", "javascript"),
+    ("jsonc", "json"),
+    ("json5", "json"),
+    ("zsh", "bash"),
+    ("shell", "bash"),
+    ("console", "bash"),
+    ("shell-session", "bash"),
+    ("shellsession", "bash"),
+    ("dockerfile", "bash"),
+    ("docker", "bash"),
+    ("yml", "yaml"),
+    ("c#", "cs"),
+    ("csharp", "cs"),
+    ("c-sharp", "cs"),
+    ("golang", "go"),
+    ("py3", "python"),
+];
+
+// Defaults overlaid with the user's entries from Settings
+static LANGUAGE_ALIASES: LazyLock<RwLock<BTreeMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(with_defaults(&BTreeMap::new())));
+
+fn with_defaults(custom: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let mut aliases: BTreeMap<String, String> = DEFAULT_LANGUAGE_ALIASES
+        .iter()
+        .map(|(alias, lang)| (alias.to_string(), lang.to_string()))
+        .collect();
+    for (alias, lang) in custom {
+        aliases.insert(alias.to_lowercase(), lang.clone());
+    }
+    aliases
+}
+
+/// Replaces the user-defined aliases. Cached blocks are re-highlighted on the next render.
+pub fn set_custom_language_aliases(custom: &BTreeMap<String, String>) {
+    *LANGUAGE_ALIASES.write().unwrap_or_else(|e| e.into_inner()) = with_defaults(custom);
+    clear_highlight_cache();
+}
+
+/// Syntax for a fence tag, looked up through the alias table first.
+pub fn resolve_syntax(lang: &str) -> Option<&'static SyntaxReference> {
+    let aliases = LANGUAGE_ALIASES.read().unwrap_or_else(|e| e.into_inner());
+    let token = aliases
+        .get(&lang.to_lowercase())
+        .map(String::as_str)
+        .unwrap_or(lang);
+    SYNTAX_SET
+        .find_syntax_by_token(token)
+        .or_else(|| SYNTAX_SET.find_syntax_by_name(token))
+}
+
+/// Whether `lang` names a syntax directly, without going through the alias table.
+pub fn is_known_language(lang: &str) -> bool {
+    SYNTAX_SET.find_syntax_by_token(lang).is_some()
+}
@@ -0,0 +1,284 @@
+use crate::syntax::{resolve_syntax, SYNTAX_SET};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use syntect::parsing::SyntaxReference;
+
+// Fence tags that ask for no highlighting, so they never get a guess.
+const PLAIN_LANGS: &[&str] = &["output", "text", "txt", "plain", "plaintext", "nohighlight"];
+
+const MIN_SCORE: u32 = 6;
+// Counting a token more than this many times would let one keyword decide alone
+const MAX_TOKEN_COUNT: u32 = 4;
+const STRONG_WEIGHT: u32 = 3;
+
+struct LanguageSignature {
+    tag: &'static str,
+    case_insensitive: bool,
+    tokens: &'static [(&'static str, u32)],
+}
+
+const SIGNATURES: &[LanguageSignature] = &[
+    LanguageSignature {
+        tag: "rust",
+        case_insensitive: false,
+        tokens: &[
+            ("fn", 3), ("let", 1), ("mut", 3), ("impl", 3), ("pub", 2), ("use", 1), ("struct", 1),
+            ("enum", 1), ("match", 2), ("::", 1), ("->", 1), ("println", 3), ("Some", 2), ("Ok", 2),
+            ("Err", 2), ("Vec", 2), ("self", 1), ("crate", 3), ("unwrap", 3), ("&mut", 3),
+        ],
+    },
+    LanguageSignature {
+        tag: "python",
+        case_insensitive: false,
+        tokens: &[
+            ("def", 3), ("import", 1), ("from", 1), ("self", 1), ("elif", 4), ("None", 2),
+            ("True", 2), ("False", 2), ("print", 2), ("lambda", 3), ("__init__", 4), ("in", 1),
+            ("not", 1), ("and", 1), ("or", 1), ("is", 1), ("pass", 2), ("with", 1), ("as", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "javascript",
+        case_insensitive: false,
+        tokens: &[
+            ("function", 2), ("const", 2), ("let", 1), ("var", 2), ("=>", 2), ("console", 4),
+            ("require", 3), ("export", 2), ("===", 3), ("!==", 3), ("undefined", 4), ("async", 1),
+            ("await", 1), ("document", 3), ("window", 3), ("null", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "go",
+        case_insensitive: false,
+        tokens: &[
+            ("func", 4), ("package", 3), (":=", 3), ("fmt", 4), ("chan", 4), ("defer", 4),
+            ("nil", 2), ("err", 1), ("go", 1), ("interface", 1), ("struct", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "java",
+        case_insensitive: false,
+        tokens: &[
+            ("public", 2), ("class", 1), ("static", 1), ("void", 1), ("private", 2), ("System", 4),
+            ("extends", 2), ("implements", 2), ("import", 1), ("final", 2), ("String", 1), ("new", 1),
+            ("@Override", 4),
+        ],
+    },
+    LanguageSignature {
+        tag: "c",
+        case_insensitive: false,
+        tokens: &[
+            ("#include", 3), ("int", 1), ("char", 2), ("printf", 3), ("malloc", 4), ("free", 2),
+            ("void", 1), ("struct", 1), ("NULL", 3), ("sizeof", 3), ("->", 1), ("#define", 3),
+        ],
+    },
+    LanguageSignature {
+        tag: "cpp",
+        case_insensitive: false,
+        tokens: &[
+            ("#include", 2), ("std", 4), ("::", 1), ("cout", 4), ("template", 3), ("namespace", 2),
+            ("class", 1), ("vector", 2), ("<<", 2), ("auto", 2), ("nullptr", 4), ("const", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "cs",
+        case_insensitive: false,
+        tokens: &[
+            ("using", 3), ("namespace", 2), ("class", 1), ("public", 1), ("var", 1), ("Console", 4),
+            ("static", 1), ("void", 1), ("string", 2), ("async", 1), ("await", 1), ("get", 1),
+            ("set", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "bash",
+        case_insensitive: false,
+        tokens: &[
+            ("echo", 3), ("fi", 4), ("then", 2), ("do", 1), ("done", 4), ("esac", 4), ("export", 1),
+            ("$(", 3), ("${", 2), ("sudo", 4), ("&&", 1), ("apt", 3), ("cd", 2), ("grep", 2),
+            ("curl", 2), ("docker", 2), ("git", 2), ("npm", 2), ("cargo", 2), ("ls", 2),
+        ],
+    },
+    LanguageSignature {
+        tag: "sql",
+        case_insensitive: true,
+        tokens: &[
+            ("SELECT", 4), ("FROM", 2), ("WHERE", 3), ("INSERT", 3), ("INTO", 2), ("UPDATE", 2),
+            ("CREATE", 2), ("TABLE", 3), ("JOIN", 3), ("VALUES", 3), ("ORDER", 1), ("GROUP", 1),
+            ("BY", 1), ("PRIMARY", 3), ("KEY", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "html",
+        case_insensitive: false,
+        tokens: &[
+            ("</", 3), ("/>", 2), ("div", 2), ("class", 1), ("href", 3), ("html", 2), ("body", 2),
+            ("span", 2), ("script", 2), ("head", 2),
+        ],
+    },
+    LanguageSignature {
+        tag: "css",
+        case_insensitive: false,
+        tokens: &[
+            ("color", 2), ("margin", 3), ("padding", 3), ("display", 2), ("px", 2), ("font", 2),
+            ("background", 2), ("border", 2), ("width", 1), ("rem", 2), ("flex", 1),
+        ],
+    },
+    LanguageSignature {
+        tag: "php",
+        case_insensitive: false,
+        tokens: &[("<?php", 8), ("$this", 4), ("echo", 1), ("function", 1), ("->", 1), ("namespace", 1)],
+    },
+    LanguageSignature {
+        tag: "ruby",
+        case_insensitive: false,
+        tokens: &[
+            ("def", 2), ("end", 3), ("puts", 4), ("require", 1), ("do", 1), ("elsif", 4), ("nil", 2),
+            ("attr_accessor", 4), ("unless", 3),
+        ],
+    },
+];
+
+/// A language guessed for a fence that had no usable tag.
+#[derive(Debug, Clone)]
+pub struct LanguageGuess {
+    /// Tag to write into the fence, e.g. `rust`.
+    pub tag: String,
+    pub syntax: &'static SyntaxReference,
+}
+
+/// Whether a fence's language should be guessed: it is missing or resolves to no syntax,
+/// even through the alias table.
+pub fn needs_language_guess(lang: &str) -> bool {
+    !PLAIN_LANGS.contains(&lang) && resolve_syntax(lang).is_none()
+}
+
+fn guess_from_syntax(syntax: &'static SyntaxReference) -> LanguageGuess {
+    let tag = SIGNATURES
+        .iter()
+        .find(|signature| {
+            SYNTAX_SET
+                .find_syntax_by_token(signature.tag)
+                .is_some_and(|candidate| candidate.name == syntax.name)
+        })
+        .map(|signature| signature.tag.to_string())
+        .or_else(|| syntax.file_extensions.first().cloned())
+        .unwrap_or_else(|| syntax.name.to_lowercase());
+    LanguageGuess { tag, syntax }
+}
+
+// Identifiers (with a leading # or @ for directives and annotations) and the
+// operators that tell languages apart.
+fn tokenize(code: &str) -> HashMap<&str, u32> {
+    const OPERATORS: &[&str] = &[
+        "<?php", "===", "!==", "&mut", "=>", "->", "::", ":=", "<<", "$(", "${", "</", "/>", "&&",
+    ];
+    let mut counts = HashMap::new();
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        if let Some(operator) = OPERATORS.iter().find(|operator| rest.starts_with(*operator)) {
+            *counts.entry(*operator).or_insert(0) += 1;
+            rest = &rest[operator.len()..];
+            continue;
+        }
+        let is_word_start = c.is_ascii_alphabetic() || c == '_';
+        let is_prefixed = (c == '#' || c == '@' || c == '$')
+            && rest[1..].starts_with(|next: char| next.is_ascii_alphabetic());
+        if is_word_start || is_prefixed {
+            let end = rest[1..]
+                .find(|next: char| !(next.is_ascii_alphanumeric() || next == '_'))
+                .map_or(rest.len(), |end| end + 1);
+            *counts.entry(&rest[..end]).or_insert(0) += 1;
+            rest = &rest[end..];
+        } else {
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    counts
+}
+
+fn looks_like_json(code: &str) -> bool {
+    let trimmed = code.trim();
+    (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+}
+
+fn looks_like_yaml(code: &str) -> bool {
+    let lines: Vec<&str> = code
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    let mapping_lines = lines
+        .iter()
+        .filter(|line| {
+            let line = line.strip_prefix("- ").unwrap_or(line);
+            line.split_once(':').is_some_and(|(key, value)| {
+                !key.is_empty()
+                    && key.chars().all(|c| c.is_alphanumeric() || "_-. \"'".contains(c))
+                    && (value.is_empty() || value.starts_with(' '))
+            }) || line.starts_with("- ")
+        })
+        .count();
+    lines.len() >= 2 && mapping_lines * 10 >= lines.len() * 8
+}
+
+fn classify(code: &str) -> Option<&'static str> {
+    let counts = tokenize(code);
+    let mut upper_counts: HashMap<String, u32> = HashMap::new();
+    for (token, count) in &counts {
+        *upper_counts.entry(token.to_uppercase()).or_insert(0) += count;
+    }
+
+    let mut scores: Vec<(u32, &'static str)> = SIGNATURES
+        .iter()
+        .map(|signature| {
+            let matches: Vec<(u32, u32)> = signature
+                .tokens
+                .iter()
+                .map(|(token, weight)| {
+                    let count = if signature.case_insensitive {
+                        upper_counts.get(*token).copied().unwrap_or(0)
+                    } else {
+                        counts.get(token).copied().unwrap_or(0)
+                    };
+                    (*weight, count.min(MAX_TOKEN_COUNT))
+                })
+                .collect();
+            // Common English words alone must not turn a paragraph into code
+            let has_strong_token = matches
+                .iter()
+                .any(|(weight, count)| *weight >= STRONG_WEIGHT && *count > 0);
+            let score = matches.iter().map(|(weight, count)| weight * count).sum();
+            (if has_strong_token { score } else { 0 }, signature.tag)
+        })
+        .collect();
+    scores.sort_by_key(|(score, _)| Reverse(*score));
+
+    let (best, tag) = scores[0];
+    let runner_up = scores.get(1).map_or(0, |(score, _)| *score);
+    // A near tie is more likely a wrong guess than a useful one
+    (best >= MIN_SCORE && best * 4 >= runner_up * 5).then_some(tag)
+}
+
+/// Guesses the language of an untagged code block: first-line markers such as shebangs
+/// and `<?php` via syntect, then a keyword-frequency classifier.
+pub fn detect_language(code: &str) -> Option<LanguageGuess> {
+    if code.trim().is_empty() {
+        return None;
+    }
+    let first_line = code.lines().next().unwrap_or_default();
+    if let Some(syntax) = SYNTAX_SET.find_syntax_by_first_line(first_line) {
+        return Some(guess_from_syntax(syntax));
+    }
+
+    let tag = if looks_like_json(code) {
+        "json"
+    } else if looks_like_yaml(code) {
+        "yaml"
+    } else {
+        classify(code)?
+    };
+    let syntax = SYNTAX_SET.find_syntax_by_token(tag)?;
+    Some(LanguageGuess {
+        tag: tag.to_string(),
+        syntax,
+    })
+}
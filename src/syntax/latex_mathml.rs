@@ -0,0 +1,567 @@
+use crate::syntax::escape_html;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// A small LaTeX math to MathML converter covering the subset people actually type in
+// notes: scripts, fractions, roots, greek letters, common operators, accents, `\text`,
+// font commands, `\left`/`\right` and matrix-like environments.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Command(String),
+    Char(char),
+    Open,
+    Close,
+    Sup,
+    Sub,
+    Align,
+    Space,
+}
+
+fn tokenize(tex: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = tex.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => tokens.push(Token::Command(read_command(&mut chars))),
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '^' => tokens.push(Token::Sup),
+            '_' => tokens.push(Token::Sub),
+            '&' => tokens.push(Token::Align),
+            c if c.is_whitespace() => {
+                if tokens.last() != Some(&Token::Space) {
+                    tokens.push(Token::Space);
+                }
+            }
+            c => tokens.push(Token::Char(c)),
+        }
+    }
+    tokens
+}
+
+fn read_command(chars: &mut Peekable<Chars>) -> String {
+    let mut name = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_alphabetic() {
+            name.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    if name.is_empty() {
+        // Control symbols such as `\,`, `\{` or `\\`
+        if let Some(c) = chars.next() {
+            name.push(c);
+        }
+    }
+    name
+}
+
+// Deeper input is rejected rather than risking a stack overflow in the recursive parser.
+const MAX_NESTING: usize = 100;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+    environments: usize,
+}
+
+#[derive(PartialEq)]
+enum Until {
+    End,
+    Close,
+    Right,
+    Environment,
+}
+
+impl Parser {
+    // Whitespace only matters inside `\text{...}`, so the regular cursor skips it.
+    fn skip_spaces(&mut self) {
+        while self.tokens.get(self.pos) == Some(&Token::Space) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_spaces();
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.skip_spaces();
+        self.next_raw()
+    }
+
+    fn next_raw(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_sequence(&mut self, until: Until) -> Result<String, String> {
+        let mut out = String::new();
+        let in_environment = self.environments > 0;
+        loop {
+            match self.peek() {
+                None => {
+                    return match until {
+                        Until::End => Ok(out),
+                        Until::Close => Err("Missing closing brace '}'".to_string()),
+                        Until::Right => Err("\\left without matching \\right".to_string()),
+                        Until::Environment => Err("\\begin without matching \\end".to_string()),
+                    };
+                }
+                Some(Token::Close) => {
+                    if until == Until::Close {
+                        self.next();
+                        return Ok(out);
+                    }
+                    return Err("Unexpected closing brace '}'".to_string());
+                }
+                Some(Token::Command(name)) if name == "right" => {
+                    if until == Until::Right {
+                        return Ok(out);
+                    }
+                    return Err("\\right without matching \\left".to_string());
+                }
+                Some(Token::Command(name)) if name == "end" => {
+                    if until == Until::Environment {
+                        return Ok(out);
+                    }
+                    return Err("\\end without matching \\begin".to_string());
+                }
+                Some(Token::Align) if until == Until::Environment => return Ok(out),
+                // A `\left..\right` group sits inside one cell, so its `&` can only space out
+                Some(Token::Align) if until == Until::Right && in_environment => {
+                    self.next();
+                    out.push_str(r#"<mspace width="1em"></mspace>"#);
+                }
+                Some(Token::Command(name)) if name == "\\" && until == Until::Environment => {
+                    return Ok(out);
+                }
+                _ => out.push_str(&self.parse_scripted()?),
+            }
+        }
+    }
+
+    fn parse_scripted(&mut self) -> Result<String, String> {
+        let base = self.parse_atom()?;
+        let mut sub = None;
+        let mut sup = None;
+
+        loop {
+            match self.peek() {
+                Some(Token::Sub) if sub.is_none() => {
+                    self.next();
+                    sub = Some(self.parse_argument()?);
+                }
+                Some(Token::Sup) if sup.is_none() => {
+                    self.next();
+                    sup = Some(self.parse_argument()?);
+                }
+                Some(Token::Sub) | Some(Token::Sup) => return Err("Double subscript or superscript".to_string()),
+                _ => break,
+            }
+        }
+
+        Ok(match (sub, sup) {
+            (Some(sub), Some(sup)) => format!("<msubsup>{}{}{}</msubsup>", base, sub, sup),
+            (Some(sub), None) => format!("<msub>{}{}</msub>", base, sub),
+            (None, Some(sup)) => format!("<msup>{}{}</msup>", base, sup),
+            (None, None) => base,
+        })
+    }
+
+    // A single required argument: either a `{...}` group or one atom.
+    fn parse_argument(&mut self) -> Result<String, String> {
+        match self.peek() {
+            None => Err("Missing argument".to_string()),
+            Some(Token::Open) => self.parse_atom(),
+            Some(Token::Sub) | Some(Token::Sup) => Err("Missing argument before script".to_string()),
+            _ => self.parse_atom(),
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<String, String> {
+        if self.depth >= MAX_NESTING {
+            return Err("Expression is nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let atom = self.parse_bare_atom();
+        self.depth -= 1;
+        atom
+    }
+
+    fn parse_bare_atom(&mut self) -> Result<String, String> {
+        match self.next() {
+            None | Some(Token::Space) => Err("Unexpected end of input".to_string()),
+            Some(Token::Open) => {
+                let inner = self.parse_sequence(Until::Close)?;
+                Ok(format!("<mrow>{}</mrow>", inner))
+            }
+            Some(Token::Close) => Err("Unexpected closing brace '}'".to_string()),
+            Some(Token::Sub) | Some(Token::Sup) => Err("Script without a base".to_string()),
+            Some(Token::Align) => Err("'&' outside of an environment".to_string()),
+            Some(Token::Char(c)) if c.is_ascii_digit() || c == '.' => {
+                let mut number = c.to_string();
+                while let Some(Token::Char(next)) = self.peek() {
+                    if next.is_ascii_digit() || *next == '.' {
+                        number.push(*next);
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                Ok(format!("<mn>{}</mn>", number))
+            }
+            Some(Token::Char(c)) if c.is_alphabetic() => Ok(format!("<mi>{}</mi>", c)),
+            Some(Token::Char(c)) => Ok(format!("<mo>{}</mo>", escape_html(&c.to_string()))),
+            Some(Token::Command(name)) => self.parse_command(&name),
+        }
+    }
+
+    fn parse_text_argument(&mut self) -> Result<String, String> {
+        if self.next() != Some(Token::Open) {
+            return Err("Expected '{' after text command".to_string());
+        }
+        let mut text = String::new();
+        loop {
+            match self.next_raw() {
+                None => return Err("Missing closing brace '}'".to_string()),
+                Some(Token::Close) => return Ok(text),
+                Some(Token::Char(c)) => text.push(c),
+                Some(Token::Command(name)) => text.push_str(&name),
+                Some(Token::Space) => text.push(' '),
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn parse_name_argument(&mut self) -> Result<String, String> {
+        let name = self.parse_text_argument()?;
+        Ok(name.trim_end_matches('*').to_string())
+    }
+
+    fn parse_delimiter(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Char('.')) => Ok(String::new()),
+            Some(Token::Char(c)) => Ok(format!("<mo stretchy=\"true\">{}</mo>", escape_html(&c.to_string()))),
+            Some(Token::Command(name)) => match symbol(&name) {
+                Some(Symbol::Operator(op)) => Ok(format!("<mo stretchy=\"true\">{}</mo>", op)),
+                _ => Err(format!("Invalid delimiter \\{}", name)),
+            },
+            _ => Err("Missing delimiter".to_string()),
+        }
+    }
+
+    fn parse_command(&mut self, name: &str) -> Result<String, String> {
+        match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                Ok(format!("<mfrac>{}{}</mfrac>", numerator, denominator))
+            }
+            "sqrt" => {
+                if self.peek() == Some(&Token::Char('[')) {
+                    self.next();
+                    let mut index = String::new();
+                    loop {
+                        match self.peek() {
+                            None => return Err("Missing ']' in \\sqrt".to_string()),
+                            Some(Token::Char(']')) => {
+                                self.next();
+                                break;
+                            }
+                            _ => index.push_str(&self.parse_scripted()?),
+                        }
+                    }
+                    let radicand = self.parse_argument()?;
+                    Ok(format!("<mroot>{}<mrow>{}</mrow></mroot>", radicand, index))
+                } else {
+                    Ok(format!("<msqrt>{}</msqrt>", self.parse_argument()?))
+                }
+            }
+            "text" | "textrm" | "mbox" => {
+                let text = self.parse_text_argument()?;
+                Ok(format!("<mtext>{}</mtext>", escape_html(&text)))
+            }
+            "mathrm" | "mathbf" | "mathit" | "mathbb" | "mathcal" | "mathsf" | "mathtt" | "operatorname" => {
+                let variant = match name {
+                    "mathbf" => "bold",
+                    "mathit" => "italic",
+                    "mathbb" => "double-struck",
+                    "mathcal" => "script",
+                    "mathsf" => "sans-serif",
+                    "mathtt" => "monospace",
+                    _ => "normal",
+                };
+                let text = self.parse_text_argument()?;
+                Ok(format!("<mi mathvariant=\"{}\">{}</mi>", variant, escape_html(&text)))
+            }
+            "hat" | "bar" | "vec" | "dot" | "ddot" | "tilde" | "overline" | "widehat" | "widetilde" => {
+                let accent = match name {
+                    "hat" | "widehat" => "^",
+                    "bar" | "overline" => "¯",
+                    "vec" => "→",
+                    "dot" => "˙",
+                    "ddot" => "¨",
+                    _ => "~",
+                };
+                let base = self.parse_argument()?;
+                Ok(format!("<mover accent=\"true\">{}<mo>{}</mo></mover>", base, accent))
+            }
+            "underline" => {
+                let base = self.parse_argument()?;
+                Ok(format!("<munder>{}<mo>_</mo></munder>", base))
+            }
+            "left" => {
+                let open = self.parse_delimiter()?;
+                let inner = self.parse_sequence(Until::Right)?;
+                self.next();
+                let close = self.parse_delimiter()?;
+                Ok(format!("<mrow>{}{}{}</mrow>", open, inner, close))
+            }
+            "begin" => self.parse_environment(),
+            "\\" => Ok(r#"<mspace linebreak="newline"></mspace>"#.to_string()),
+            "," | ":" | ";" | " " | "quad" | "qquad" | "!" => {
+                let width = match name {
+                    "," => "0.167em",
+                    ":" => "0.222em",
+                    ";" => "0.278em",
+                    " " => "0.25em",
+                    "quad" => "1em",
+                    "qquad" => "2em",
+                    _ => "-0.167em",
+                };
+                Ok(format!("<mspace width=\"{}\"></mspace>", width))
+            }
+            _ => match symbol(name) {
+                Some(Symbol::Identifier(ident)) => Ok(format!("<mi>{}</mi>", ident)),
+                Some(Symbol::Operator(op)) => Ok(format!("<mo>{}</mo>", op)),
+                Some(Symbol::Function(func)) => Ok(format!("<mi mathvariant=\"normal\">{}</mi>", func)),
+                None => Err(format!("Unknown command \\{}", name)),
+            },
+        }
+    }
+
+    fn parse_environment(&mut self) -> Result<String, String> {
+        let env = self.parse_name_argument()?;
+        let (open, close) = match env.as_str() {
+            "matrix" | "aligned" | "align" | "gathered" | "array" => ("", ""),
+            "pmatrix" => ("(", ")"),
+            "bmatrix" => ("[", "]"),
+            "vmatrix" => ("|", "|"),
+            "cases" => ("{", ""),
+            _ => return Err(format!("Unknown environment '{}'", env)),
+        };
+        if env == "array" && self.peek() == Some(&Token::Open) {
+            // Column spec, irrelevant for MathML layout
+            self.parse_text_argument()?;
+        }
+
+        self.environments += 1;
+        let rows = self.parse_rows(&env);
+        self.environments -= 1;
+
+        let table = format!("<mtable>{}</mtable>", rows?);
+        let open = if open.is_empty() { String::new() } else { format!("<mo>{}</mo>", open) };
+        let close = if close.is_empty() { String::new() } else { format!("<mo>{}</mo>", close) };
+        Ok(format!("<mrow>{}{}{}</mrow>", open, table, close))
+    }
+
+    fn parse_rows(&mut self, env: &str) -> Result<String, String> {
+        let mut rows = String::new();
+        let mut row = String::new();
+        loop {
+            let cell = self.parse_sequence(Until::Environment)?;
+            row.push_str(&format!("<mtd>{}</mtd>", cell));
+            match self.next() {
+                Some(Token::Align) => {}
+                Some(Token::Command(name)) if name == "\\" => {
+                    rows.push_str(&format!("<mtr>{}</mtr>", row));
+                    row.clear();
+                }
+                Some(Token::Command(name)) if name == "end" => {
+                    let end = self.parse_name_argument()?;
+                    if end != env {
+                        return Err(format!("\\begin{{{}}} closed by \\end{{{}}}", env, end));
+                    }
+                    rows.push_str(&format!("<mtr>{}</mtr>", row));
+                    return Ok(rows);
+                }
+                _ => return Err(format!("Unterminated environment '{}'", env)),
+            }
+        }
+    }
+}
+
+enum Symbol {
+    Identifier(&'static str),
+    Operator(&'static str),
+    Function(&'static str),
+}
+
+fn symbol(name: &str) -> Option<Symbol> {
+    use Symbol::*;
+
+    let symbol = match name {
+        "alpha" => Identifier("α"),
+        "beta" => Identifier("β"),
+        "gamma" => Identifier("γ"),
+        "delta" => Identifier("δ"),
+        "epsilon" => Identifier("ϵ"),
+        "varepsilon" => Identifier("ε"),
+        "zeta" => Identifier("ζ"),
+        "eta" => Identifier("η"),
+        "theta" => Identifier("θ"),
+        "vartheta" => Identifier("ϑ"),
+        "iota" => Identifier("ι"),
+        "kappa" => Identifier("κ"),
+        "lambda" => Identifier("λ"),
+        "mu" => Identifier("μ"),
+        "nu" => Identifier("ν"),
+        "xi" => Identifier("ξ"),
+        "pi" => Identifier("π"),
+        "rho" => Identifier("ρ"),
+        "sigma" => Identifier("σ"),
+        "tau" => Identifier("τ"),
+        "upsilon" => Identifier("υ"),
+        "phi" => Identifier("ϕ"),
+        "varphi" => Identifier("φ"),
+        "chi" => Identifier("χ"),
+        "psi" => Identifier("ψ"),
+        "omega" => Identifier("ω"),
+        "Gamma" => Identifier("Γ"),
+        "Delta" => Identifier("Δ"),
+        "Theta" => Identifier("Θ"),
+        "Lambda" => Identifier("Λ"),
+        "Xi" => Identifier("Ξ"),
+        "Pi" => Identifier("Π"),
+        "Sigma" => Identifier("Σ"),
+        "Upsilon" => Identifier("Υ"),
+        "Phi" => Identifier("Φ"),
+        "Psi" => Identifier("Ψ"),
+        "Omega" => Identifier("Ω"),
+        "infty" => Identifier("∞"),
+        "partial" => Identifier("∂"),
+        "nabla" => Identifier("∇"),
+        "emptyset" => Identifier("∅"),
+        "hbar" => Identifier("ℏ"),
+        "ell" => Identifier("ℓ"),
+        "sum" => Operator("∑"),
+        "prod" => Operator("∏"),
+        "int" => Operator("∫"),
+        "iint" => Operator("∬"),
+        "oint" => Operator("∮"),
+        "bigcup" => Operator("⋃"),
+        "bigcap" => Operator("⋂"),
+        "cdot" => Operator("⋅"),
+        "times" => Operator("×"),
+        "div" => Operator("÷"),
+        "pm" => Operator("±"),
+        "mp" => Operator("∓"),
+        "ast" => Operator("∗"),
+        "circ" => Operator("∘"),
+        "le" | "leq" => Operator("≤"),
+        "ge" | "geq" => Operator("≥"),
+        "ne" | "neq" => Operator("≠"),
+        "ll" => Operator("≪"),
+        "gg" => Operator("≫"),
+        "approx" => Operator("≈"),
+        "equiv" => Operator("≡"),
+        "sim" => Operator("∼"),
+        "simeq" => Operator("≃"),
+        "propto" => Operator("∝"),
+        "to" | "rightarrow" => Operator("→"),
+        "leftarrow" | "gets" => Operator("←"),
+        "Rightarrow" | "implies" => Operator("⇒"),
+        "Leftarrow" => Operator("⇐"),
+        "leftrightarrow" => Operator("↔"),
+        "Leftrightarrow" | "iff" => Operator("⇔"),
+        "mapsto" => Operator("↦"),
+        "in" => Operator("∈"),
+        "notin" => Operator("∉"),
+        "ni" => Operator("∋"),
+        "subset" => Operator("⊂"),
+        "subseteq" => Operator("⊆"),
+        "supset" => Operator("⊃"),
+        "supseteq" => Operator("⊇"),
+        "cup" => Operator("∪"),
+        "cap" => Operator("∩"),
+        "setminus" => Operator("∖"),
+        "land" | "wedge" => Operator("∧"),
+        "lor" | "vee" => Operator("∨"),
+        "neg" | "lnot" => Operator("¬"),
+        "forall" => Operator("∀"),
+        "exists" => Operator("∃"),
+        "ldots" | "dots" => Operator("…"),
+        "cdots" => Operator("⋯"),
+        "vdots" => Operator("⋮"),
+        "ddots" => Operator("⋱"),
+        "langle" => Operator("⟨"),
+        "rangle" => Operator("⟩"),
+        "lfloor" => Operator("⌊"),
+        "rfloor" => Operator("⌋"),
+        "lceil" => Operator("⌈"),
+        "rceil" => Operator("⌉"),
+        "mid" | "vert" | "|" => Operator("|"),
+        "{" | "lbrace" => Operator("{"),
+        "}" | "rbrace" => Operator("}"),
+        "%" => Operator("%"),
+        "$" => Operator("$"),
+        "#" => Operator("#"),
+        "&" => Operator("&amp;"),
+        "_" => Operator("_"),
+        "sin" => Function("sin"),
+        "cos" => Function("cos"),
+        "tan" => Function("tan"),
+        "cot" => Function("cot"),
+        "sec" => Function("sec"),
+        "csc" => Function("csc"),
+        "arcsin" => Function("arcsin"),
+        "arccos" => Function("arccos"),
+        "arctan" => Function("arctan"),
+        "sinh" => Function("sinh"),
+        "cosh" => Function("cosh"),
+        "tanh" => Function("tanh"),
+        "log" => Function("log"),
+        "ln" => Function("ln"),
+        "exp" => Function("exp"),
+        "lim" => Function("lim"),
+        "max" => Function("max"),
+        "min" => Function("min"),
+        "sup" => Function("sup"),
+        "inf" => Function("inf"),
+        "det" => Function("det"),
+        "gcd" => Function("gcd"),
+        "deg" => Function("deg"),
+        "dim" => Function("dim"),
+        "ker" => Function("ker"),
+        "Pr" => Function("Pr"),
+        "mod" | "bmod" => Function("mod"),
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+/// Converts a LaTeX math expression to a `<math>` element.
+/// `display` selects block layout for `$$...$$` math.
+pub fn latex_to_mathml(tex: &str, display: bool) -> Result<String, String> {
+    let mut parser = Parser {
+        tokens: tokenize(tex),
+        pos: 0,
+        depth: 0,
+        environments: 0,
+    };
+    let body = parser.parse_sequence(Until::End)?;
+
+    Ok(format!(
+        r#"<math display="{}"><mrow>{}</mrow></math>"#,
+        if display { "block" } else { "inline" },
+        body
+    ))
+}
@@ -1,3 +1,27 @@
 mod highlighted_markdown;
+mod syntax_themes;
+mod latex_mathml;
+mod diagrams;
+mod outline;
+mod front_matter;
+mod code_fence;
+#[cfg(not(feature = "web"))]
+mod sql_playground;
+mod sanitizer;
+mod html_export;
+mod language_detection;
+mod language_aliases;
 
 pub use highlighted_markdown::*;
+pub use syntax_themes::*;
+pub use latex_mathml::*;
+pub use diagrams::*;
+pub use outline::*;
+pub use front_matter::*;
+pub use code_fence::*;
+#[cfg(not(feature = "web"))]
+pub use sql_playground::*;
+pub use sanitizer::*;
+pub use html_export::*;
+pub use language_detection::*;
+pub use language_aliases::*;
@@ -0,0 +1,97 @@
+use crate::syntax::markdown_options;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::collections::HashSet;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadingEntry {
+    pub level: u8,
+    pub title: String,
+    pub id: String,
+    // Byte offset of the heading in the markdown source
+    pub offset: usize,
+}
+
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Gives every heading without an explicit `{#id}` a slug id, deduplicated with a
+/// numeric suffix, and returns the resulting outline in document order.
+pub(crate) fn anchor_headings(events: &mut [(Event, Range<usize>)]) -> Vec<HeadingEntry> {
+    let mut used_ids: HashSet<String> = events
+        .iter()
+        .filter_map(|(event, _)| match event {
+            Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let mut outline = Vec::new();
+    let mut index = 0;
+    while index < events.len() {
+        let (Event::Start(Tag::Heading { level, id, .. }), range) = &events[index] else {
+            index += 1;
+            continue;
+        };
+        let (level, offset, explicit_id) = (*level as u8, range.start, id.clone());
+
+        let mut title = String::new();
+        let mut end = index + 1;
+        while end < events.len() {
+            match &events[end].0 {
+                Event::End(TagEnd::Heading(_)) => break,
+                Event::Text(text) | Event::Code(text) => title.push_str(text),
+                _ => {}
+            }
+            end += 1;
+        }
+
+        let id = match explicit_id {
+            Some(id) => id.to_string(),
+            None => {
+                let base = slugify(&title);
+                let mut id = base.clone();
+                let mut suffix = 1;
+                while used_ids.contains(&id) {
+                    id = format!("{}-{}", base, suffix);
+                    suffix += 1;
+                }
+                used_ids.insert(id.clone());
+                if let (Event::Start(Tag::Heading { id: heading_id, .. }), _) = &mut events[index] {
+                    *heading_id = Some(id.clone().into());
+                }
+                id
+            }
+        };
+
+        outline.push(HeadingEntry {
+            level,
+            title,
+            id,
+            offset,
+        });
+        index = end;
+    }
+    outline
+}
+
+pub fn markdown_outline(markdown: &str) -> Vec<HeadingEntry> {
+    let mut events: Vec<_> = Parser::new_ext(markdown, markdown_options())
+        .into_offset_iter()
+        .collect();
+    anchor_headings(&mut events)
+}
@@ -0,0 +1,149 @@
+use ammonia::{Builder, UrlRelative};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::LazyLock;
+
+/// Scheme of links between notes, such as `runorna://note/12`.
+pub const NOTE_LINK_SCHEME: &str = "runorna";
+
+/// How much raw HTML written in a note survives in the preview and in exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SanitizerPolicy {
+    /// Only the markup Runorna renders itself, no inline styles.
+    #[default]
+    Strict,
+    /// Also keeps presentational inline styles on text and table elements.
+    Relaxed,
+}
+
+// Inline styles the relaxed policy keeps; anything that can load a URL or move
+// content outside the preview is dropped.
+const RELAXED_STYLE_TAGS: [&str; 13] = [
+    "span", "div", "p", "mark", "code", "pre", "table", "tr", "th", "td", "h1", "h2", "h3",
+];
+const RELAXED_STYLE_PROPERTIES: [&str; 17] = [
+    "color", "background-color", "font-weight", "font-style", "font-size", "font-family",
+    "text-align", "text-decoration", "vertical-align", "white-space", "border", "border-color",
+    "border-style", "border-width", "padding", "margin", "width",
+];
+
+// Prefix of the `data-block` index of each rendered fenced block. Raw HTML in a note can't
+// know it, so it can't pass its own elements off as code blocks to the preview's buttons.
+static BLOCK_KEY: LazyLock<String> =
+    LazyLock::new(|| format!("{:x}:", RandomState::new().hash_one("data-block")));
+
+static STRICT_SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(base_builder);
+
+static RELAXED_SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = base_builder();
+    for tag in RELAXED_STYLE_TAGS {
+        builder.add_tag_attributes(tag, ["style"]);
+    }
+    builder.filter_style_properties(RELAXED_STYLE_PROPERTIES.into());
+    builder
+});
+
+impl SanitizerPolicy {
+    pub const ALL: [SanitizerPolicy; 2] = [SanitizerPolicy::Strict, SanitizerPolicy::Relaxed];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            SanitizerPolicy::Strict => "strict",
+            SanitizerPolicy::Relaxed => "relaxed",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<SanitizerPolicy> {
+        SanitizerPolicy::ALL.into_iter().find(|policy| policy.code() == code)
+    }
+
+    /// The policy's builder, built on first use and shared afterwards.
+    pub fn builder(self) -> &'static Builder<'static> {
+        match self {
+            SanitizerPolicy::Strict => &STRICT_SANITIZER,
+            SanitizerPolicy::Relaxed => &RELAXED_SANITIZER,
+        }
+    }
+
+    pub fn clean(self, html: &str) -> String {
+        self.builder().clean(html).to_string()
+    }
+}
+
+// Only in-page anchors, such as heading links and diagram markers, may be relative. Other
+// relative URLs would resolve against the app itself.
+fn keep_fragment(url: &str) -> Option<Cow<'_, str>> {
+    url.starts_with('#').then_some(Cow::Borrowed(url))
+}
+
+/// The `data-block` value of the `index`-th fenced block; sanitizing leaves only the index.
+pub(crate) fn block_attribute(index: usize) -> String {
+    format!("{}{}", *BLOCK_KEY, index)
+}
+
+// HTML policy for rendered notes, shared by the live preview and HTML exports.
+fn base_builder() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        .add_tags([
+            "pre", "code", "span", "input", "label", "div", "section", "article", "table", "thead",
+            "tbody", "tfoot", "tr", "th", "td", "del", "ins", "mark", "sup", "sub", "details",
+            "summary", "math", "mrow", "mi", "mo", "mn", "msup", "msub", "msubsup", "mfrac",
+            "msqrt", "mroot", "mtext", "mspace", "mover", "munder", "munderover", "mtable", "mtr",
+            "mtd", "em", "p", "svg", "defs", "marker", "g", "rect", "circle", "ellipse", "line",
+            "polyline", "polygon", "path", "text", "tspan", "textPath", "clipPath", "button",
+        ])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .add_tag_attributes("button", ["type"])
+        .add_tag_attributes("math", ["display"])
+        .add_tag_attributes("mi", ["mathvariant"])
+        .add_tag_attributes("mo", ["stretchy"])
+        .add_tag_attributes("mover", ["accent"])
+        .add_tag_attributes("mspace", ["width", "linebreak"])
+        .add_generic_attributes(["title"])
+        // Safe SVG subset for rendered diagrams: shapes and text only, no scripts,
+        // foreignObject, images or external references.
+        .add_tag_attributes("svg", ["width", "height", "viewBox"])
+        .add_tag_attributes("marker", ["markerWidth", "markerHeight", "refX", "refY", "orient"])
+        .add_tag_attributes("textPath", ["href", "startOffset", "text-anchor"])
+        .add_tag_attributes("clipPath", ["id"])
+        .add_generic_attributes([
+            "x", "y", "x1", "y1", "x2", "y2", "cx", "cy", "r", "rx", "ry", "width", "height", "d",
+            "points", "fill", "stroke", "stroke-width", "stroke-dasharray", "marker-start",
+            "marker-end", "clip-path", "text-anchor", "dominant-baseline", "dy", "font-size",
+        ])
+        .add_generic_attributes(["class", "id", "aria-hidden"])
+        .add_generic_attribute_prefixes(["data-"])
+        .attribute_filter(|_, attribute, value| match attribute {
+            "data-block" => value.strip_prefix(BLOCK_KEY.as_str()).map(Cow::Borrowed),
+            _ => Some(Cow::Borrowed(value)),
+        })
+        .url_schemes(HashSet::from(["http", "https", "mailto", NOTE_LINK_SCHEME]))
+        .url_relative(UrlRelative::Custom(Box::new(keep_fragment)));
+    builder
+}
+
+/// The URL to hand to the system browser for a link clicked in the preview, or `None` for
+/// anything but `http(s)` and `mailto` links, such as `javascript:` or `file:`.
+pub fn external_link(href: &str) -> Option<&str> {
+    let href = href.trim();
+    let (scheme, rest) = href.split_once(':')?;
+    let allowed = ["http", "https", "mailto"]
+        .iter()
+        .any(|allowed| scheme.eq_ignore_ascii_case(allowed));
+    let has_host = rest.starts_with("//") && rest.len() > 2;
+    (allowed && (has_host || scheme.eq_ignore_ascii_case("mailto"))).then_some(href)
+}
+
+/// The note id of a `runorna://note/ID` link.
+pub fn note_link(href: &str) -> Option<i32> {
+    let (scheme, rest) = href.trim().split_once("://")?;
+    if !scheme.eq_ignore_ascii_case(NOTE_LINK_SCHEME) {
+        return None;
+    }
+    let id = rest.strip_prefix("note/")?.trim_end_matches('/').parse().ok()?;
+    (id > 0).then_some(id)
+}
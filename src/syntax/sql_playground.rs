@@ -0,0 +1,174 @@
+use crate::i18n::{translate, ui_locale};
+use crate::syntax::escape_html;
+use rusqlite::fallible_iterator::FallibleIterator;
+use rusqlite::limits::Limit;
+use rusqlite::types::ValueRef;
+use rusqlite::{Batch, Connection, Statement};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+const MAX_ROWS: usize = 200;
+const BLOCK_TIME_LIMIT: Duration = Duration::from_secs(1);
+const RESULT_CACHE_CAPACITY: usize = 256;
+
+// Rendered results keyed by the hash of a block and every block before it, so renders
+// that leave the SQL untouched never execute it again.
+static RESULT_CACHE: LazyLock<Mutex<HashMap<u64, String>>> = LazyLock::new(Default::default);
+
+pub fn clear_sql_results() {
+    RESULT_CACHE.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Scratch in-memory database shared by the ```sql run fences of one note,
+/// so later blocks see the tables and rows created by earlier ones.
+#[derive(Default)]
+pub struct SqlPlayground {
+    conn: Option<Connection>,
+    chain: u64,
+    // Blocks answered from the cache, run before the next block that isn't
+    skipped: Vec<String>,
+}
+
+impl SqlPlayground {
+    fn connection(&mut self) -> rusqlite::Result<&Connection> {
+        if self.conn.is_none() {
+            let conn = Connection::open_in_memory()?;
+            // ATTACH and VACUUM INTO could reach files on disk such as folder.db
+            conn.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0)?;
+            self.conn = Some(conn);
+        }
+        Ok(self.conn.as_ref().expect("connection was just opened"))
+    }
+
+    /// Renders the result of a block, executing it only if it or a block before it changed.
+    pub fn execute(&mut self, sql: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        (self.chain, sql).hash(&mut hasher);
+        self.chain = hasher.finish();
+
+        let cached = RESULT_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&self.chain)
+            .cloned();
+        if let Some(html) = cached {
+            self.skipped.push(sql.to_string());
+            return html;
+        }
+
+        for skipped in std::mem::take(&mut self.skipped) {
+            self.run(&skipped);
+        }
+        let html = self.run(sql);
+        let mut cache = RESULT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if cache.len() >= RESULT_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(self.chain, html.clone());
+        html
+    }
+
+    /// Executes every statement of a block and renders the result sets as tables.
+    /// The first failing statement stops the block and is reported inline.
+    fn run(&mut self, sql: &str) -> String {
+        let conn = match self.connection() {
+            Ok(conn) => conn,
+            Err(e) => return render_error(&e.to_string()),
+        };
+
+        // Runaway queries such as unbounded recursive CTEs would otherwise freeze the preview
+        let started = Instant::now();
+        conn.progress_handler(10_000, Some(move || started.elapsed() > BLOCK_TIME_LIMIT));
+
+        let mut html = String::from(r#"<div class="sql-result">"#);
+        let mut changes = 0;
+        let mut batch = Batch::new(conn, sql);
+        loop {
+            let result = batch.next().and_then(|statement| match statement {
+                Some(mut statement) if statement.column_count() > 0 => {
+                    render_rows(&mut statement).map(|table| html.push_str(&table)).map(Some)
+                }
+                Some(mut statement) => statement.execute([]).map(|count| changes += count).map(Some),
+                None => Ok(None),
+            });
+            match result {
+                Ok(Some(())) => {}
+                Ok(None) => break,
+                Err(e) => {
+                    let message = if started.elapsed() > BLOCK_TIME_LIMIT {
+                        translate(ui_locale(), "sql-stopped", &[("seconds", &BLOCK_TIME_LIMIT.as_secs())])
+                    } else {
+                        e.to_string()
+                    };
+                    html.push_str(&render_error(&message));
+                    break;
+                }
+            }
+        }
+        conn.progress_handler(0, None::<fn() -> bool>);
+
+        if changes > 0 {
+            html.push_str(&format!(
+                r#"<div class="sql-summary">{}</div>"#,
+                t("sql-rows-changed", &[("count", &changes)])
+            ));
+        }
+        html.push_str("</div>");
+        html
+    }
+}
+
+fn render_error(message: &str) -> String {
+    format!(r#"<div class="sql-error">{}</div>"#, t("sql-error", &[("error", &message)]))
+}
+
+fn t(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    escape_html(&translate(ui_locale(), key, args))
+}
+
+fn render_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => r#"<span class="sql-null">NULL</span>"#.to_string(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(text) => escape_html(&String::from_utf8_lossy(text)),
+        ValueRef::Blob(blob) => format!(r#"<span class="sql-null">{}</span>"#, t("sql-blob", &[("bytes", &blob.len())])),
+    }
+}
+
+fn render_rows(statement: &mut Statement) -> rusqlite::Result<String> {
+    let columns = statement.column_count();
+    let mut html = String::from(r#"<table class="sql-table"><thead><tr>"#);
+    for name in statement.column_names() {
+        html.push_str(&format!("<th>{}</th>", escape_html(name)));
+    }
+    html.push_str("</tr></thead><tbody>");
+
+    let mut rows = statement.query([])?;
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        count += 1;
+        if count > MAX_ROWS {
+            continue;
+        }
+        html.push_str("<tr>");
+        for index in 0..columns {
+            html.push_str(&format!("<td>{}</td>", render_value(row.get_ref(index)?)));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+
+    if count > MAX_ROWS {
+        html.push_str(&format!(
+            r#"<div class="sql-summary">{}</div>"#,
+            t("sql-rows-shown", &[("shown", &MAX_ROWS), ("total", &count)])
+        ));
+    } else if count == 0 {
+        html.push_str(&format!(r#"<div class="sql-summary">{}</div>"#, t("sql-no-rows", &[])));
+    }
+    Ok(html)
+}
@@ -0,0 +1,153 @@
+use crate::syntax::SYNTAX_SET;
+use anyhow::{anyhow, Context, Result};
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+use syntect::highlighting::{Color, ThemeSet};
+use syntect::html::{css_for_theme_with_class_style, highlighted_html_for_string, ClassStyle};
+
+pub const THEMES_DIR: &str = "themes";
+pub const DEFAULT_THEME: &str = "base16-eighties.dark";
+/// Used for code blocks while the app is in light mode, until the user picks another.
+pub const DEFAULT_LIGHT_THEME: &str = "InspiredGitHub";
+
+pub const HIGHLIGHT_CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "sy-" };
+
+// Rules are nested under the code block body so they outrank the preview's Tailwind
+// color utilities.
+const HIGHLIGHT_SCOPE: &str = ".code-block pre.code-block-body";
+
+const PREVIEW_SNIPPET: &str = "fn main() {\n    let name = \"Runorna\";\n    println!(\"{}\", name); // 42\n}\n";
+
+// Built-in syntect themes plus every `.tmTheme` found in `THEMES_DIR`.
+pub static THEME_SET: LazyLock<RwLock<ThemeSet>> = LazyLock::new(|| {
+    let mut theme_set = ThemeSet::load_defaults();
+    load_custom_themes(&mut theme_set);
+    RwLock::new(theme_set)
+});
+
+fn load_custom_themes(theme_set: &mut ThemeSet) {
+    if !Path::new(THEMES_DIR).is_dir() {
+        return;
+    }
+
+    let paths = match ThemeSet::discover_theme_paths(THEMES_DIR) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log::error!("Failed to read themes directory: {}", e);
+            return;
+        }
+    };
+
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match ThemeSet::get_theme(&path) {
+            Ok(theme) => {
+                theme_set.themes.insert(name.to_string(), theme);
+            }
+            Err(e) => log::error!("Failed to load theme {}: {}", path.display(), e),
+        }
+    }
+}
+
+pub fn theme_names() -> Vec<String> {
+    let set = THEME_SET.read().unwrap_or_else(|e| e.into_inner());
+    set.themes.keys().cloned().collect()
+}
+
+pub fn theme_preview_html(theme_name: &str) -> String {
+    let set = THEME_SET.read().unwrap_or_else(|e| e.into_inner());
+    let Some(theme) = set.themes.get(theme_name) else {
+        return String::new();
+    };
+    let syntax = SYNTAX_SET
+        .find_syntax_by_token("rust")
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+
+    highlighted_html_for_string(PREVIEW_SNIPPET, &SYNTAX_SET, syntax, theme).unwrap_or_default()
+}
+
+fn css_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}{:02x}", color.r, color.g, color.b, color.a)
+}
+
+/// Stylesheet coloring the classes emitted by `render_fenced_code` with the given theme.
+/// Switching themes only swaps this stylesheet, the rendered HTML stays the same.
+pub fn theme_stylesheet(theme_name: &str) -> String {
+    let set = THEME_SET.read().unwrap_or_else(|e| e.into_inner());
+    let Some(theme) = set.themes.get(theme_name).or_else(|| set.themes.get(DEFAULT_THEME)) else {
+        return String::new();
+    };
+    let settings = &theme.settings;
+
+    let mut css = String::new();
+    let mut base = String::new();
+    if let Some(foreground) = settings.foreground {
+        base.push_str(&format!(" color: {};", css_color(foreground)));
+    }
+    if let Some(background) = settings.background {
+        base.push_str(&format!(" background-color: {};", css_color(background)));
+    }
+    css.push_str(&format!("{} {{{} }}\n", HIGHLIGHT_SCOPE, base));
+    css.push_str(&format!("{} * {{ color: inherit; }}\n", HIGHLIGHT_SCOPE));
+    if let Some(line_highlight) = settings.line_highlight {
+        css.push_str(&format!(
+            "{} .code-line.highlighted {{ background-color: {}; }}\n",
+            HIGHLIGHT_SCOPE,
+            css_color(line_highlight)
+        ));
+    }
+    if let Some(selection) = settings.selection {
+        css.push_str(&format!(
+            "{} ::selection {{ background-color: {}; }}\n",
+            HIGHLIGHT_SCOPE,
+            css_color(selection)
+        ));
+    }
+
+    let classes = css_for_theme_with_class_style(theme, HIGHLIGHT_CLASS_STYLE).unwrap_or_default();
+    for line in classes.lines() {
+        match line.strip_suffix(" {") {
+            Some(selectors) if !line.starts_with(' ') => {
+                let scoped: Vec<String> = selectors
+                    .split(", ")
+                    .map(|selector| format!("{} {}", HIGHLIGHT_SCOPE, selector))
+                    .collect();
+                css.push_str(&scoped.join(", "));
+                css.push_str(" {");
+            }
+            _ => css.push_str(line),
+        }
+        css.push('\n');
+    }
+    css
+}
+
+/// Validates a `.tmTheme` document, copies it into `THEMES_DIR` and registers it.
+/// Returns the theme name, which is the file stem.
+pub fn import_theme(file_name: &str, contents: &str) -> Result<String> {
+    let name = Path::new(file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("Invalid theme file name: {}", file_name))?
+        .to_string();
+
+    let theme = ThemeSet::load_from_reader(&mut Cursor::new(contents.as_bytes()))
+        .context("Failed to parse .tmTheme file")?;
+
+    std::fs::create_dir_all(THEMES_DIR).context("Failed to create themes directory")?;
+    std::fs::write(Path::new(THEMES_DIR).join(format!("{}.tmTheme", name)), contents)
+        .context("Failed to copy theme into themes directory")?;
+
+    THEME_SET
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .themes
+        .insert(name.clone(), theme);
+
+    Ok(name)
+}
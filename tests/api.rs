@@ -0,0 +1,261 @@
+// The local API serves the SQLite database, which the web build doesn't have
+#![cfg(not(feature = "web"))]
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use http_body_util::BodyExt;
+use runorna::api::{router, ApiServer, OPENAPI_JSON};
+use runorna::data::DATABASE_ENV;
+use serde_json::{json, Value};
+use std::sync::Once;
+use tower::ServiceExt;
+
+const TOKEN: &str = "test-token";
+
+// Every test thread opens its own connection, all to the same temp database.
+fn use_temp_database() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let path = std::env::temp_dir().join(format!("runorna-api-test-{}.db", std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        std::env::set_var(DATABASE_ENV, path);
+    });
+}
+
+async fn send(method: Method, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+    use_temp_database();
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = router(TOKEN.to_string()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, value)
+}
+
+#[tokio::test]
+async fn rejects_missing_or_wrong_token() {
+    let (status, body) = send(Method::GET, "/api/folders", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body["error"].is_string());
+
+    let (status, _) = send(Method::GET, "/api/folders", Some("wrong-token"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn openapi_is_public_and_documents_every_route() {
+    let (status, spec) = send(Method::GET, "/api/openapi.json", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(spec, serde_json::from_str::<Value>(OPENAPI_JSON).unwrap());
+    for path in [
+        "/api/folders",
+        "/api/folders/{id}/notes",
+        "/api/notes",
+        "/api/notes/{id}",
+        "/api/search",
+    ] {
+        assert!(spec["paths"][path].is_object(), "{} is not documented", path);
+    }
+}
+
+#[tokio::test]
+async fn creates_lists_and_nests_folders() {
+    let (status, parent) =
+        send(Method::POST, "/api/folders", Some(TOKEN), Some(json!({ "name": "Api folders" }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let parent_id = parent["id"].as_i64().unwrap();
+
+    let (status, child) = send(
+        Method::POST,
+        "/api/folders",
+        Some(TOKEN),
+        Some(json!({ "name": "Child", "parent_id": parent_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(child["parent_id"], parent_id);
+
+    let (status, folders) = send(Method::GET, "/api/folders", Some(TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = folders
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|folder| folder["id"] == parent_id)
+        .unwrap();
+    assert_eq!(listed["children"][0]["name"], "Child");
+
+    let (status, _) =
+        send(Method::POST, "/api/folders", Some(TOKEN), Some(json!({ "name": "  " }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(
+        Method::POST,
+        "/api/folders",
+        Some(TOKEN),
+        Some(json!({ "name": "Orphan", "parent_id": 999_999 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn note_lifecycle() {
+    let (status, note) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "folder_path": "Api notes/Compose", "content": "---\ntitle: Stack\n---\n\n`docker compose up`\n" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(note["meta"]["title"], "Stack");
+    let note_id = note["id"].as_i64().unwrap();
+    let folder_id = note["folder_id"].as_i64().unwrap();
+
+    let (status, notes) =
+        send(Method::GET, &format!("/api/folders/{}/notes", folder_id), Some(TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(notes[0]["id"], note_id);
+
+    let (status, second) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "folder_id": folder_id, "content": "second" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(second["folder_id"], folder_id);
+
+    let (status, updated) = send(
+        Method::PUT,
+        &format!("/api/notes/{}", note_id),
+        Some(TOKEN),
+        Some(json!({ "content": "# Renamed\n" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["content"], "# Renamed\n");
+
+    let (status, _) = send(Method::DELETE, &format!("/api/notes/{}", note_id), Some(TOKEN), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(Method::GET, &format!("/api/notes/{}", note_id), Some(TOKEN), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rejects_ambiguous_note_folder() {
+    let (status, _) = send(Method::POST, "/api/notes", Some(TOKEN), Some(json!({ "content": "x" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "content": "x", "folder_id": 1, "folder_path": "A" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn searches_note_content() {
+    let (status, _) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "folder_path": "Api search", "content": "kubectl rollout restart deploy/web" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, hits) = send(Method::GET, "/api/search?q=rollout%20restart", Some(TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(hits[0]["folder_name"], "Api search");
+    assert!(hits[0]["snippet"].as_str().unwrap().contains("rollout restart"));
+}
+
+#[tokio::test]
+async fn server_listens_on_localhost_only() {
+    use_temp_database();
+    let server = ApiServer::start(0, TOKEN.to_string()).unwrap();
+    assert!(server.addr.ip().is_loopback());
+
+    let mut stream = tokio::net::TcpStream::connect(server.addr).await.unwrap();
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    stream
+        .write_all(b"GET /api/folders HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+}
+
+#[tokio::test]
+async fn front_matter_errors_keep_stored_metadata() {
+    let (status, note) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "folder_path": "Api meta", "content": "---\ntitle: Deploy\ntags: [\"ops, prod\", k8s]\n---\n" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(note["meta"]["tags"], json!(["ops, prod", "k8s"]));
+    let uri = format!("/api/notes/{}", note["id"]);
+
+    let (status, updated) = send(
+        Method::PUT,
+        &uri,
+        Some(TOKEN),
+        Some(json!({ "content": "---\ntitle: [Deploy\n---\n" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["content"], "---\ntitle: [Deploy\n---\n");
+    assert_eq!(updated["meta"]["title"], "Deploy");
+    assert_eq!(updated["meta"]["tags"], json!(["ops, prod", "k8s"]));
+}
+
+#[tokio::test]
+async fn quick_switcher_lists_stored_labels() {
+    let (status, note) = send(
+        Method::POST,
+        "/api/notes",
+        Some(TOKEN),
+        Some(json!({ "folder_path": "Api switcher", "content": "# Runbook\n\nsteps\n" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let note_id = note["id"].as_i64().unwrap() as i32;
+    let uri = format!("/api/notes/{}", note_id);
+    let label = || async {
+        runorna::data::get_quick_switch_items()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|item| item.note_id == Some(note_id))
+            .map(|item| item.label)
+    };
+    assert_eq!(label().await.as_deref(), Some("Runbook"));
+
+    send(Method::PUT, &uri, Some(TOKEN), Some(json!({ "content": "---\ntitle: Oncall\n---\n# Runbook\n" }))).await;
+    assert_eq!(label().await.as_deref(), Some("Oncall"));
+
+    send(Method::PUT, &uri, Some(TOKEN), Some(json!({ "content": "---\ntitle: [Oncall\n---\n" }))).await;
+    assert_eq!(label().await.as_deref(), Some("Oncall"));
+}
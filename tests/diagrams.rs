@@ -0,0 +1,44 @@
+use runorna::syntax::render_diagram;
+
+fn assert_themed(html: &str) {
+    assert!(html.starts_with(r#"<div class="diagram">"#), "{}", html);
+    for hard_coded in ["#000000ff", "#ffffffff", "#eef0ff", r#""black""#, r#""white""#] {
+        assert!(!html.contains(hard_coded), "{} survived in {}", hard_coded, html);
+    }
+    assert!(html.contains(r#"stroke="currentColor""#), "{}", html);
+}
+
+#[test]
+fn diagrams_follow_the_theme_text_color() {
+    assert_themed(&render_diagram("dot", r#"digraph { a -> b [label="next"]; }"#));
+    assert_themed(&render_diagram("mermaid", "flowchart LR\nA[Start] --> B((End))"));
+    assert_themed(&render_diagram("mermaid", "sequenceDiagram\nA->>B: hi\nB-->>A: ok\nNote over A: done"));
+}
+
+#[test]
+fn dot_colors_are_kept() {
+    let html = render_diagram("dot", r#"digraph { a [color="red"]; a -> b; }"#);
+    assert!(html.contains(r##"stroke="#ff0000ff""##), "{}", html);
+}
+
+#[test]
+fn malformed_diagrams_render_an_error() {
+    for (lang, source) in [
+        ("dot", ""),
+        ("dot", "digraph {"),
+        ("dot", "digraph { a -> }"),
+        ("dot", "digraph { \"a }"),
+        ("dot", "digraph { a [label=] }"),
+        ("dot", "digraph { a:b:c -> d }"),
+        ("dot", "digraph { }"),
+        ("graphviz", "strict"),
+        ("mermaid", ""),
+        ("mermaid", "pie\n\"a\": 1"),
+        ("mermaid", "flowchart XY\nA --> B"),
+        ("mermaid", "flowchart LR\nA -- label B"),
+        ("mermaid", "sequenceDiagram\nA B"),
+    ] {
+        let html = render_diagram(lang, source);
+        assert!(html.starts_with(r#"<div class="diagram-error">"#), "{:?}: {}", source, html);
+    }
+}
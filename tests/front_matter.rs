@@ -0,0 +1,40 @@
+use runorna::syntax::{note_meta, with_title};
+
+fn title_of(markdown: &str) -> Option<String> {
+    note_meta(markdown).unwrap().title
+}
+
+#[test]
+fn adds_title_to_existing_block() {
+    let titled = with_title("---\ntags: [ops]\n---\n\nBody\n", "Deploy").unwrap();
+    assert_eq!(titled, "---\ntitle: Deploy\ntags: [ops]\n---\n\nBody\n");
+
+    let titled = with_title("+++\ntags = [\"ops\"]\n+++\n\nBody\n", "Deploy").unwrap();
+    assert_eq!(title_of(&titled).as_deref(), Some("Deploy"));
+    assert_eq!(note_meta(&titled).unwrap().tags, ["ops"]);
+}
+
+#[test]
+fn keeps_crlf_front_matter_a_single_block() {
+    let note = "---\r\ntags: [ops]\r\n---\r\n\r\nBody\r\n";
+    let titled = with_title(note, "Deploy").unwrap();
+    assert_eq!(titled, "---\r\ntitle: Deploy\r\ntags: [ops]\r\n---\r\n\r\nBody\r\n");
+    assert_eq!(titled.matches("---").count(), 2);
+    assert_eq!(note_meta(&titled).unwrap().tags, ["ops"]);
+}
+
+#[test]
+fn adds_block_to_notes_without_one() {
+    let titled = with_title("# Heading\n", "Deploy: prod").unwrap();
+    assert_eq!(title_of(&titled).as_deref(), Some("Deploy: prod"));
+    assert!(titled.ends_with("---\n\n# Heading\n"));
+
+    let titled = with_title("Body\r\n", "Deploy").unwrap();
+    assert_eq!(titled, "---\r\ntitle: Deploy\r\n---\r\n\r\nBody\r\n");
+}
+
+#[test]
+fn refuses_titled_or_invalid_front_matter() {
+    assert!(with_title("---\ntitle: Kept\n---\n", "Deploy").is_err());
+    assert!(with_title("---\ntitle: [Kept\n---\n", "Deploy").unwrap_err().starts_with("Invalid front matter"));
+}
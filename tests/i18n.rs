@@ -0,0 +1,137 @@
+use runorna::i18n::{catalog, format_message, parse_catalog, placeables, plural_category, translate, Locale};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+// Calls whose first string argument is a message key
+const KEY_PREFIXES: &[&str] = &["t(\"", "t_args(\"", "translate(locale, \"", "translate(ui_locale(), \""];
+
+fn rust_sources(dir: &Path, files: &mut Vec<(String, String)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rust_sources(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push((path.display().to_string(), std::fs::read_to_string(&path).unwrap()));
+        }
+    }
+}
+
+fn literal_after<'a>(source: &'a str, prefix: &str) -> Vec<&'a str> {
+    source
+        .match_indices(prefix)
+        // `t(` must not be the end of another name such as `select(`
+        .filter(|(start, _)| {
+            !source[..*start].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .filter_map(|(start, _)| {
+            let rest = &source[start + prefix.len()..];
+            rest.find('"').map(|end| &rest[..end])
+        })
+        .collect()
+}
+
+fn used_keys() -> BTreeSet<(String, String)> {
+    let mut files = Vec::new();
+    rust_sources(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+    let mut keys = BTreeSet::new();
+    for (file, source) in &files {
+        for prefix in KEY_PREFIXES {
+            for key in literal_after(source, prefix) {
+                keys.insert((file.clone(), key.to_string()));
+            }
+        }
+        // Command labels are looked up as command-<id>
+        if file.ends_with("commands.rs") {
+            for id in literal_after(source, "=> \"").into_iter().filter(|id| !id.contains('+')) {
+                keys.insert((file.clone(), format!("command-{}", id.replace('_', "-"))));
+            }
+        }
+        // Select options keep their message keys in a table
+        if file.ends_with("settings_page.rs") {
+            for prefix in ["autosave-", "html-policy-"] {
+                for key in literal_after(source, &format!(", \"{}", prefix)) {
+                    keys.insert((file.clone(), format!("{}{}", prefix, key)));
+                }
+            }
+        }
+    }
+    keys
+}
+
+#[test]
+fn every_catalog_has_the_english_keys() {
+    let english: BTreeSet<&String> = catalog(Locale::En).keys().collect();
+    for locale in Locale::ALL {
+        let keys: BTreeSet<&String> = catalog(locale).keys().collect();
+        let missing: Vec<_> = english.difference(&keys).collect();
+        let extra: Vec<_> = keys.difference(&english).collect();
+        assert!(missing.is_empty(), "{} catalog is missing {:?}", locale.code(), missing);
+        assert!(extra.is_empty(), "{} catalog has unknown keys {:?}", locale.code(), extra);
+    }
+}
+
+#[test]
+fn translations_keep_the_english_placeables() {
+    for locale in Locale::ALL {
+        for (key, english) in catalog(Locale::En) {
+            let Some(message) = catalog(locale).get(key) else {
+                continue;
+            };
+            let mut expected = placeables(english);
+            let mut actual = placeables(message);
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual, "{} placeables differ in {}", locale.code(), key);
+        }
+    }
+}
+
+#[test]
+fn every_key_used_in_the_source_exists() {
+    let english = catalog(Locale::En);
+    let keys = used_keys();
+    assert!(keys.iter().any(|(_, key)| key == "command-save-note"));
+    let missing: Vec<_> = keys.iter().filter(|(_, key)| !english.contains_key(key)).collect();
+    assert!(missing.is_empty(), "keys missing from en.ftl: {:?}", missing);
+}
+
+#[test]
+fn plural_categories_follow_each_language() {
+    assert_eq!(plural_category(Locale::En, "1"), "one");
+    assert_eq!(plural_category(Locale::En, "0"), "other");
+    assert_eq!(plural_category(Locale::En, "1.0"), "other");
+    assert_eq!(plural_category(Locale::Fr, "0"), "one");
+    assert_eq!(plural_category(Locale::Fr, "1"), "one");
+    assert_eq!(plural_category(Locale::Fr, "2"), "other");
+    assert_eq!(plural_category(Locale::Fr, "many"), "other");
+}
+
+#[test]
+fn selects_pick_the_plural_variant() {
+    let characters = |locale, count: usize| translate(locale, "editor-characters", &[("count", &count)]);
+    assert_eq!(characters(Locale::En, 1), "1 character");
+    assert_eq!(characters(Locale::En, 0), "0 characters");
+    assert_eq!(characters(Locale::En, 12), "12 characters");
+    assert_eq!(characters(Locale::Fr, 0), "0 caractère");
+    assert_eq!(characters(Locale::Fr, 12), "12 caractères");
+}
+
+#[test]
+fn selects_match_exact_values_then_the_default() {
+    let messages = parse_catalog(
+        "items = { $count ->\n    [0] Nothing\n    [one] One item\n   *[other] { $count } items\n  } in { $place }\n",
+    )
+    .unwrap();
+    let items = &messages["items"];
+    assert_eq!(placeables(items), ["count", "place"]);
+    let format = |count: &str| format_message(Locale::En, items, &[("count", &count), ("place", &"stock")]);
+    assert_eq!(format("0"), "Nothing in stock");
+    assert_eq!(format("1"), "One item in stock");
+    assert_eq!(format("3"), "3 items in stock");
+    assert_eq!(format("many"), "many items in stock");
+    for locale in Locale::ALL {
+        for message in catalog(locale).values() {
+            assert_eq!(message.matches("->").count(), message.matches("*[").count(), "{}", message);
+        }
+    }
+}
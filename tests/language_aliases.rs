@@ -0,0 +1,31 @@
+use runorna::syntax::{resolve_syntax, set_custom_language_aliases};
+use std::collections::BTreeMap;
+
+fn syntax_name(lang: &str) -> Option<&'static str> {
+    resolve_syntax(lang).map(|syntax| syntax.name.as_str())
+}
+
+// One test, since the alias table is shared by the whole process
+#[test]
+fn custom_aliases_override_defaults_case_insensitively() {
+    assert_eq!(syntax_name("TS"), Some("JavaScript"));
+    assert_eq!(syntax_name("Golang"), Some("Go"));
+
+    let custom = BTreeMap::from([
+        ("TS".to_string(), "rust".to_string()),
+        ("Notes".to_string(), "Plain Text".to_string()),
+    ]);
+    set_custom_language_aliases(&custom);
+    assert_eq!(syntax_name("ts"), Some("Rust"));
+    assert_eq!(syntax_name("tS"), Some("Rust"));
+    assert_eq!(syntax_name("typescript"), Some("JavaScript"));
+    // Targets are syntax tokens or full syntax names
+    assert_eq!(syntax_name("notes"), Some("Plain Text"));
+
+    // Tags outside the table are looked up as they are
+    assert_eq!(syntax_name("Markdown"), Some("Markdown"));
+    assert_eq!(syntax_name("no-such-language"), None);
+
+    set_custom_language_aliases(&BTreeMap::new());
+    assert_eq!(syntax_name("TS"), Some("JavaScript"));
+}
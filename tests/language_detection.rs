@@ -0,0 +1,52 @@
+use runorna::syntax::{detect_language, dismiss_language_guess, language_guess, needs_language_guess};
+
+fn guessed(code: &str) -> Option<String> {
+    detect_language(code).map(|guess| guess.tag)
+}
+
+#[test]
+fn classifies_common_languages() {
+    let cases = [
+        ("rust", "fn main() {\n    let mut total = 0;\n    println!(\"{}\", total);\n}\n"),
+        ("python", "def greet(name):\n    if name is None:\n        return\n    print(name)\n"),
+        ("javascript", "const total = items.reduce((a, b) => a + b, 0);\nconsole.log(total);\n"),
+        ("go", "package main\n\nimport \"fmt\"\n\nfunc main() {\n    fmt.Println(\"hi\")\n}\n"),
+        ("sql", "SELECT id, name FROM users WHERE active = 1 ORDER BY name;\n"),
+        ("json", "{\n  \"name\": \"runorna\",\n  \"private\": true\n}\n"),
+        ("yaml", "services:\n  web:\n    image: nginx\n    ports:\n      - 80:80\n"),
+    ];
+    for (tag, code) in cases {
+        assert_eq!(guessed(code).as_deref(), Some(tag), "{}", code);
+    }
+}
+
+#[test]
+fn uses_first_line_markers() {
+    let guess = detect_language("#!/bin/bash\necho hi\n").unwrap();
+    assert_eq!(guess.tag, "bash");
+}
+
+#[test]
+fn leaves_prose_and_empty_blocks_alone() {
+    assert_eq!(guessed(""), None);
+    assert_eq!(guessed("   \n"), None);
+    assert_eq!(guessed("Remember to water the plants and call the landlord.\n"), None);
+}
+
+#[test]
+fn guesses_only_missing_or_unknown_tags() {
+    assert!(needs_language_guess(""));
+    assert!(needs_language_guess("not-a-language"));
+    assert!(!needs_language_guess("rust"));
+    assert!(!needs_language_guess("text"));
+    assert!(!needs_language_guess("output"));
+}
+
+#[test]
+fn dismissed_guesses_stay_dismissed() {
+    let code = "fn dismissed() -> Option<u8> {\n    let mut x = Some(1);\n    x.take()\n}\n";
+    assert_eq!(language_guess("", code).map(|guess| guess.tag).as_deref(), Some("rust"));
+    assert!(language_guess("rust", code).is_none());
+    dismiss_language_guess(code);
+    assert!(language_guess("", code).is_none());
+}
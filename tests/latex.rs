@@ -0,0 +1,88 @@
+use runorna::syntax::latex_to_mathml;
+
+fn body(tex: &str) -> String {
+    let math = latex_to_mathml(tex, false).unwrap();
+    math.strip_prefix(r#"<math display="inline"><mrow>"#)
+        .and_then(|rest| rest.strip_suffix("</mrow></math>"))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn display_selects_block_layout() {
+    assert!(latex_to_mathml("x", true).unwrap().starts_with(r#"<math display="block">"#));
+    assert!(latex_to_mathml("x", false).unwrap().starts_with(r#"<math display="inline">"#));
+}
+
+#[test]
+fn fractions_roots_and_scripts() {
+    assert_eq!(body(r"\frac{1}{2}"), "<mfrac><mrow><mn>1</mn></mrow><mrow><mn>2</mn></mrow></mfrac>");
+    assert_eq!(body(r"\frac ab"), "<mfrac><mi>a</mi><mi>b</mi></mfrac>");
+    assert_eq!(body(r"\sqrt[3]{x}"), "<mroot><mrow><mi>x</mi></mrow><mrow><mn>3</mn></mrow></mroot>");
+    assert_eq!(body("x_i^2"), "<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>");
+    assert_eq!(body(r"\alpha \le 3.14"), "<mi>α</mi><mo>≤</mo><mn>3.14</mn>");
+}
+
+#[test]
+fn environments_build_tables() {
+    assert_eq!(
+        body(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"),
+        "<mrow><mo>(</mo><mtable><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr>\
+         <mtr><mtd><mi>c</mi></mtd><mtd><mi>d</mi></mtd></mtr></mtable><mo>)</mo></mrow>"
+    );
+    assert_eq!(
+        body(r"\begin{array}{cc} 1 & 2 \end{array}"),
+        "<mrow><mtable><mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr></mtable></mrow>"
+    );
+    assert!(body(r"\begin{align*} x &= 1 \end{align*}").contains("<mtable>"));
+}
+
+#[test]
+fn delimiters_stretch() {
+    assert_eq!(
+        body(r"\left( x \right."),
+        r#"<mrow><mo stretchy="true">(</mo><mi>x</mi></mrow>"#
+    );
+    assert_eq!(
+        body(r"\left\langle x \right\rangle"),
+        r#"<mrow><mo stretchy="true">⟨</mo><mi>x</mi><mo stretchy="true">⟩</mo></mrow>"#
+    );
+    // `&` inside a `\left..\right` group within an environment stays in its cell
+    let cases = body(r"\begin{cases} \left( a & b \right) & c \end{cases}");
+    assert!(cases.contains(r#"<mi>a</mi><mspace width="1em"></mspace><mi>b</mi>"#), "{}", cases);
+    assert_eq!(cases.matches("<mtd>").count(), 2, "{}", cases);
+}
+
+#[test]
+fn malformed_input_is_an_error() {
+    for (tex, message) in [
+        ("{x", "Missing closing brace '}'"),
+        ("x}", "Unexpected closing brace '}'"),
+        (r"\left( x", "\\left without matching \\right"),
+        (r"x \right)", "\\right without matching \\left"),
+        (r"\begin{matrix} x", "\\begin without matching \\end"),
+        (r"\begin{matrix} x \end{pmatrix}", "\\begin{matrix} closed by \\end{pmatrix}"),
+        (r"\begin{tabular} x \end{tabular}", "Unknown environment 'tabular'"),
+        ("x^2^3", "Double subscript or superscript"),
+        ("^2", "Script without a base"),
+        (r"\frac{1}", "Missing argument"),
+        ("a & b", "'&' outside of an environment"),
+        (r"\left( a & b \right)", "'&' outside of an environment"),
+        (r"\foo", "Unknown command \\foo"),
+        (r"\left\foo x \right)", "Invalid delimiter \\foo"),
+    ] {
+        assert_eq!(latex_to_mathml(tex, false), Err(message.to_string()), "{}", tex);
+    }
+}
+
+#[test]
+fn deep_nesting_is_rejected_without_overflowing() {
+    let braces = format!("{}x{}", "{".repeat(100_000), "}".repeat(100_000));
+    assert_eq!(latex_to_mathml(&braces, false), Err("Expression is nested too deeply".to_string()));
+
+    let roots = format!("{}x", r"\sqrt".repeat(100_000));
+    assert_eq!(latex_to_mathml(&roots, false), Err("Expression is nested too deeply".to_string()));
+
+    let fractions = r"\frac{1}{".repeat(40) + "x" + &"}".repeat(40);
+    assert!(latex_to_mathml(&fractions, false).is_ok());
+}
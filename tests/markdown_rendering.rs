@@ -0,0 +1,70 @@
+use runorna::syntax::{clear_highlight_cache, fenced_code_blocks, markdown_to_html, SanitizerPolicy};
+use std::time::{Duration, Instant};
+
+// Unoptimized test builds are roughly ten times slower than `cargo bench` or a release build.
+const SLOWDOWN: u32 = if cfg!(debug_assertions) { 12 } else { 1 };
+// Re-rendering after a keystroke must skip syntect entirely; a cold render is slower than this.
+const CACHED_RENDER_BUDGET: Duration = Duration::from_millis(40);
+// Everything the preview does once the debounce fires: markdown, cached blocks and sanitizing.
+const PREVIEW_BUDGET: Duration = Duration::from_millis(300);
+
+// Same ~5,000 line runbook as `benches/markdown_rendering.rs`.
+fn large_note() -> String {
+    let mut note = String::new();
+    for section in 0..300 {
+        note.push_str(&format!("## Step {}\n\nRun the following and check the output.\n\n", section));
+        note.push_str("```rust\n");
+        for line in 0..8 {
+            note.push_str(&format!("let value_{} = compute({}, \"step\");\n", line, section));
+        }
+        note.push_str("```\n\n");
+        note.push_str("- check logs\n- restart service\n\n");
+    }
+    note
+}
+
+// Best of a few runs, so a busy machine doesn't fail the budget on one slow run.
+fn fastest<T>(mut f: impl FnMut() -> T) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn large_note_stays_responsive_while_typing() {
+    let mut note = large_note();
+    assert!(note.lines().count() >= 5_000);
+    clear_highlight_cache();
+    markdown_to_html(&note);
+
+    note.push_str("One more keystroke");
+    let render = fastest(|| markdown_to_html(&note));
+    assert!(render < CACHED_RENDER_BUDGET * SLOWDOWN, "cached render took {:?}", render);
+
+    let preview = fastest(|| SanitizerPolicy::Strict.clean(&markdown_to_html(&note)));
+    assert!(preview < PREVIEW_BUDGET * SLOWDOWN, "preview update took {:?}", preview);
+}
+
+#[test]
+fn indented_code_blocks_render_without_the_toolbar() {
+    let html = markdown_to_html("```bash\necho hi\n```\n\nText\n\n    indented <code>\n\n```python\nprint(1)\n```\n");
+    assert_eq!(html.matches(r#"class="code-block""#).count(), 2);
+    assert!(html.contains("<pre><code>indented &lt;code&gt;\n</code></pre>"), "{}", html);
+}
+
+#[test]
+fn block_indices_follow_fenced_code_blocks_past_indented_blocks() {
+    let note = "```bash\necho hi\n```\n\nText\n\n    indented\n\n```mermaid\ngraph TD\nA-->B\n```\n\n```python\nprint(1)\n```\n";
+    let html = SanitizerPolicy::Strict.clean(&markdown_to_html(note));
+    let blocks = fenced_code_blocks(note);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(html.matches("data-block=").count(), 2);
+    let python = html.find(r#"data-block="1""#).unwrap();
+    assert!(html[python..].contains("print"), "{}", html);
+    assert_eq!(blocks[1].fence.lang, "python");
+}
@@ -0,0 +1,31 @@
+use runorna::syntax::{markdown_outline, markdown_to_html, slugify};
+
+fn ids(markdown: &str) -> Vec<String> {
+    markdown_outline(markdown).into_iter().map(|heading| heading.id).collect()
+}
+
+#[test]
+fn repeated_titles_get_numbered_slugs() {
+    assert_eq!(ids("# Intro\n\n## Intro\n\n# Intro\n"), ["intro", "intro-1", "intro-2"]);
+    assert_eq!(slugify("  Hello, World -- again!  "), "hello-world-again");
+}
+
+#[test]
+fn generated_suffixes_never_reuse_a_taken_slug() {
+    assert_eq!(ids("# Intro 1\n\n# Intro\n\n# Intro\n"), ["intro-1", "intro", "intro-2"]);
+    assert_eq!(ids("# Intro\n\n# Intro\n\n# Intro 1\n"), ["intro", "intro-1", "intro-1-1"]);
+}
+
+#[test]
+fn explicit_ids_are_kept_and_reserved() {
+    assert_eq!(ids("# Intro\n\n# Setup {#intro}\n"), ["intro-1", "intro"]);
+    let html = markdown_to_html("# Setup {#Custom_ID}\n\n# Custom_ID\n");
+    assert!(html.contains(r#"id="Custom_ID""#), "{}", html);
+    assert!(html.contains(r#"id="custom_id""#), "{}", html);
+}
+
+#[test]
+fn punctuation_only_titles_fall_back_to_section() {
+    assert_eq!(slugify("?!… --"), "section");
+    assert_eq!(ids("# ???\n\n# !!!\n"), ["section", "section-1"]);
+}
@@ -0,0 +1,42 @@
+use runorna::data::{contrast_ratio, palette_stylesheet, parse_shared_palette};
+use std::collections::BTreeMap;
+
+#[test]
+fn contrast_ratio_spans_one_to_twenty_one() {
+    let ratio = contrast_ratio("#000000", "#ffffff").unwrap();
+    assert!((ratio - 21.0).abs() < 1e-9, "{}", ratio);
+    assert_eq!(contrast_ratio("#ffffff", "#000000"), Some(ratio));
+    assert_eq!(contrast_ratio("#4c5c92", "#4c5c92"), Some(1.0));
+    for bad in ["000000", "#fff", "#gggggg", "#0000000", ""] {
+        assert_eq!(contrast_ratio(bad, "#ffffff"), None, "{}", bad);
+    }
+}
+
+#[test]
+fn shared_palettes_keep_only_known_variables_with_hex_colors() {
+    let shared = parse_shared_palette(
+        r##"{"name": "Dusk", "colors": {"primary": "#112233", "on-primary": "red", "unknown": "#ffffff", "surface": "#AABBCC"}}"##,
+    )
+    .unwrap();
+    assert_eq!(shared.name, "Dusk");
+    let kept: Vec<_> = shared.colors.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+    assert_eq!(kept, [("primary", "#112233"), ("surface", "#AABBCC")]);
+
+    assert!(parse_shared_palette(r##"{"name": "Empty", "colors": {"primary": "blue"}}"##).is_err());
+    assert!(parse_shared_palette(r##"{"name": " ", "colors": {"primary": "#112233"}}"##).is_err());
+    assert!(parse_shared_palette("not json").is_err());
+}
+
+#[test]
+fn stylesheet_only_writes_validated_colors() {
+    let colors: BTreeMap<String, String> = [
+        ("primary", "#112233"),
+        ("on-primary", "#fff; } body { background: url(evil) }"),
+        ("surface", "red"),
+        ("unknown", "#ffffff"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+    assert_eq!(palette_stylesheet(&colors), "html:root {\n    --primary: #112233;\n}");
+}
@@ -0,0 +1,139 @@
+use runorna::syntax::{external_link, markdown_to_html, note_link, SanitizerPolicy};
+
+const XSS_PAYLOADS: &[&str] = &[
+    "<script>alert(1)</script>",
+    "<img src=x onerror=alert(1)>",
+    "<svg onload=alert(1)><circle r=1 /></svg>",
+    "<svg><script>alert(1)</script></svg>",
+    "<svg><foreignObject><iframe src=javascript:alert(1)></iframe></foreignObject></svg>",
+    "<a href=\"javascript:alert(1)\">x</a>",
+    "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+    "<a href=\" javascript:alert(1)\">x</a>",
+    "<a href=\"&#106;avascript:alert(1)\">x</a>",
+    "<a href=\"java\tscript:alert(1)\">x</a>",
+    "<a href=\"data:text/html,<script>alert(1)</script>\">x</a>",
+    "<a href=\"vbscript:msgbox(1)\">x</a>",
+    "<iframe src=\"https://example.com\"></iframe>",
+    "<object data=\"x.swf\"></object>",
+    "<form action=\"https://example.com\"><button formaction=\"javascript:alert(1)\">x</button></form>",
+    "<div style=\"background-image: url(javascript:alert(1))\">x</div>",
+    "<p onclick=\"alert(1)\">x</p>",
+    "<math><mi xlink:href=\"javascript:alert(1)\">x</mi></math>",
+    "<details open ontoggle=alert(1)><summary>x</summary></details>",
+    "<textPath href=\"javascript:alert(1)\">x</textPath>",
+];
+
+fn assert_harmless(html: &str) {
+    let lower = html.to_lowercase();
+    for needle in ["<script", "<iframe", "<object", "<form", "javascript:", "vbscript:", "data:", " on", "url("] {
+        assert!(!lower.contains(needle), "{:?} survived in {}", needle, html);
+    }
+}
+
+#[test]
+fn xss_payloads_are_removed_by_every_policy() {
+    for policy in SanitizerPolicy::ALL {
+        for payload in XSS_PAYLOADS {
+            assert_harmless(&policy.clean(payload));
+            // The same payloads written as raw HTML in a note, through the markdown renderer
+            assert_harmless(&policy.clean(&markdown_to_html(payload)));
+        }
+    }
+}
+
+#[test]
+fn markdown_links_only_keep_web_schemes() {
+    let markdown = "[a](javascript:alert(1)) [b](file:///etc/passwd) [c](https://example.com) [d](mailto:me@example.com)";
+    for policy in SanitizerPolicy::ALL {
+        let html = policy.clean(&markdown_to_html(markdown));
+        assert!(!html.contains("javascript:"), "{}", html);
+        assert!(!html.contains("file:"), "{}", html);
+        assert!(html.contains(r#"href="https://example.com""#), "{}", html);
+        assert!(html.contains(r#"href="mailto:me@example.com""#), "{}", html);
+    }
+}
+
+#[test]
+fn only_fragment_urls_may_be_relative() {
+    let html = SanitizerPolicy::Strict.clean(
+        r##"<a href="#setup">a</a><a href="/etc/passwd">b</a><a href="../notes">c</a><a href="//evil.example">d</a><img src="secret.png">"##,
+    );
+    assert!(html.contains(r##"href="#setup""##), "{}", html);
+    for url in ["/etc/passwd", "../notes", "evil.example", "secret.png"] {
+        assert!(!html.contains(url), "{} survived in {}", url, html);
+    }
+}
+
+#[test]
+fn inline_styles_depend_on_the_policy() {
+    let source = r#"<span style="color: red; position: fixed; background: url(https://example.com/x.png)">x</span><a href="https://example.com" style="color: red">y</a>"#;
+
+    let strict = SanitizerPolicy::Strict.clean(source);
+    assert!(!strict.contains("style"), "{}", strict);
+
+    let relaxed = SanitizerPolicy::Relaxed.clean(source);
+    assert!(relaxed.contains(r#"<span style="color:red">"#), "{}", relaxed);
+    assert!(!relaxed.contains("position"), "{}", relaxed);
+    assert!(!relaxed.contains("url("), "{}", relaxed);
+    // Only text and table elements take styles
+    assert!(relaxed.contains(r#"<a href="https://example.com" rel="noopener noreferrer">"#), "{}", relaxed);
+}
+
+#[test]
+fn rendered_features_survive_the_strict_policy() {
+    let markdown = "# Setup\n\n```rust\nfn main() {}\n```\n\n- [x] done\n\n$x^2$\n";
+    let html = SanitizerPolicy::Strict.clean(&markdown_to_html(markdown));
+    assert!(html.contains(r#"id="setup""#), "{}", html);
+    assert!(html.contains("code-block"), "{}", html);
+    assert!(html.contains(r#"type="checkbox""#), "{}", html);
+    assert!(html.contains("<math"), "{}", html);
+}
+
+#[test]
+fn external_links_are_limited_to_web_and_mail() {
+    assert_eq!(external_link("https://example.com/a?b#c"), Some("https://example.com/a?b#c"));
+    assert_eq!(external_link(" HTTP://example.com "), Some("HTTP://example.com"));
+    assert_eq!(external_link("mailto:me@example.com"), Some("mailto:me@example.com"));
+    for href in [
+        "javascript:alert(1)",
+        "JavaScript:alert(1)",
+        "file:///etc/passwd",
+        "data:text/html,x",
+        "https:",
+        "https://",
+        "/etc/passwd",
+        "//example.com",
+        "notes.md",
+        "#setup",
+    ] {
+        assert_eq!(external_link(href), None, "{}", href);
+    }
+}
+
+#[test]
+fn note_links_survive_and_resolve_to_ids() {
+    for policy in SanitizerPolicy::ALL {
+        let html = policy.clean(&markdown_to_html("[setup](runorna://note/12)"));
+        assert!(html.contains(r#"href="runorna://note/12""#), "{}", html);
+    }
+
+    assert_eq!(note_link("runorna://note/12"), Some(12));
+    assert_eq!(note_link(" Runorna://note/7/ "), Some(7));
+    for href in ["runorna://note/", "runorna://note/0", "runorna://note/-3", "runorna://folder/2", "https://note/2", "runorna:note/2"] {
+        assert_eq!(note_link(href), None, "{}", href);
+    }
+    // Handled inside the app, never by the system browser
+    assert_eq!(external_link("runorna://note/12"), None);
+}
+
+#[test]
+fn raw_html_cannot_pose_as_a_code_block() {
+    let note = "<div class=\"code-block\" data-block=\"0\" hidden><button class=\"code-run\">Run</button></div>\n\n```bash\necho hi\n```\n";
+    for policy in SanitizerPolicy::ALL {
+        let html = policy.clean(&markdown_to_html(note));
+        assert_eq!(html.matches("data-block=").count(), 1, "{}", html);
+        let block = html.find(r#"data-block="0""#).unwrap();
+        assert!(html[block..].contains("echo"), "{}", html);
+        assert!(!html[..block].contains("echo"), "{}", html);
+    }
+}
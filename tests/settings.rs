@@ -0,0 +1,27 @@
+use runorna::data::Settings;
+
+fn font_family(editor_font: &str) -> String {
+    Settings {
+        editor_font: editor_font.to_string(),
+        ..Settings::default()
+    }
+    .editor_font_family()
+}
+
+#[test]
+fn quotes_named_font_families() {
+    assert_eq!(font_family("monospace"), "monospace");
+    assert_eq!(font_family("Fira Code, Monospace"), "\"Fira Code\", monospace");
+    assert_eq!(font_family("'JetBrains Mono', \"Iosevka\""), "\"JetBrains Mono\", \"Iosevka\"");
+    assert_eq!(font_family(" , "), "monospace");
+}
+
+#[test]
+fn editor_font_cannot_inject_declarations() {
+    let family = font_family("x; background: url(evil)");
+    assert_eq!(family, "\"x; background: url(evil)\"");
+
+    let family = font_family("a\"; color: red; \"b");
+    assert_eq!(family, "\"a\\\"; color: red; \\\"b\"");
+    assert!(!font_family("a\\\nb").contains('\n'));
+}
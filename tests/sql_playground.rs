@@ -0,0 +1,30 @@
+// The playground runs on the bundled SQLite, which the web build doesn't have
+#![cfg(not(feature = "web"))]
+
+use runorna::syntax::markdown_to_html;
+
+#[test]
+fn later_blocks_see_earlier_tables() {
+    let html = markdown_to_html(
+        "```sql run\nCREATE TABLE pod (name TEXT);\nINSERT INTO pod VALUES ('web'), ('db');\n```\n\n\
+         ```sql run\nSELECT name FROM pod ORDER BY name;\n```\n",
+    );
+    assert!(html.contains("2 rows changed"), "{}", html);
+    assert!(html.contains("<td>db</td></tr><tr><td>web</td>"), "{}", html);
+}
+
+#[test]
+fn unchanged_blocks_are_not_executed_again() {
+    let setup = "```sql run\nCREATE TABLE tick (n INTEGER);\nINSERT INTO tick VALUES (random());\n```\n\n";
+    let query = "```sql run\nSELECT n FROM tick;\n```\n";
+    let value = |html: &str| html.split("<tbody>").nth(1).unwrap().to_string();
+    let first = markdown_to_html(&format!("{}{}", setup, query));
+    // Typing in prose renders the same results instead of a new random value
+    let typed = markdown_to_html(&format!("{}Some prose\n\n{}", setup, query));
+    assert_eq!(value(&typed), value(&first));
+
+    // An edited later block replays the cached ones before it
+    let edited = markdown_to_html(&format!("{}```sql run\nSELECT count(*) AS total FROM tick;\n```\n", setup));
+    assert!(edited.contains("<th>total</th>"), "{}", edited);
+    assert!(edited.contains("<td>1</td>"), "{}", edited);
+}